@@ -0,0 +1,78 @@
+use std::{collections::HashMap, io::Write};
+
+use revm::{db::PlainAccount, primitives::Address};
+use serde::ser::{SerializeMap, Serializer};
+
+/// Write `accounts` to `writer` as a pretty-printed JSON object, in iteration order
+///
+/// Produces the same document as `serde_json::to_writer_pretty` on a map
+/// holding the same entries in the same order. The genesis state itself is
+/// still held in memory, since the state root and the other outputs need it.
+pub fn write_accounts<'a, W: Write>(
+    writer: W,
+    accounts: impl IntoIterator<Item = (&'a Address, &'a PlainAccount)>,
+) -> serde_json::Result<()> {
+    let mut serializer = serde_json::Serializer::pretty(writer);
+    let mut map = serializer.serialize_map(None)?;
+    for (address, account) in accounts {
+        map.serialize_entry(address, account)?;
+    }
+    map.end()
+}
+
+/// Write the genesis accounts sorted by address, so the same state always
+/// produces the same `genesis_accounts.json`
+pub fn write_genesis_accounts<W: Write>(
+    writer: W,
+    genesis_state: &HashMap<Address, PlainAccount>,
+) -> serde_json::Result<()> {
+    let mut addresses: Vec<&Address> = genesis_state.keys().collect();
+    addresses.sort_unstable();
+    write_accounts(
+        writer,
+        addresses
+            .into_iter()
+            .map(|address| (address, &genesis_state[address])),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use revm::primitives::{AccountInfo, Bytecode, Bytes, U256};
+
+    #[test]
+    fn test_streamed_accounts_match_serde_output() {
+        let mut genesis_state = HashMap::new();
+        for i in (1..=20u64).rev() {
+            let mut account = PlainAccount {
+                info: AccountInfo {
+                    balance: U256::from(i),
+                    nonce: i,
+                    ..AccountInfo::default()
+                },
+                storage: Default::default(),
+            };
+            if i % 3 == 0 {
+                account.info =
+                    AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from(vec![0x60, i as u8])));
+                account.storage.insert(U256::from(i), U256::from(i * 2));
+            }
+            genesis_state.insert(Address::with_last_byte(i as u8), account);
+        }
+
+        let mut streamed = Vec::new();
+        write_genesis_accounts(&mut streamed, &genesis_state).unwrap();
+        let sorted: BTreeMap<_, _> = genesis_state.iter().collect();
+        assert_eq!(
+            String::from_utf8(streamed).unwrap(),
+            serde_json::to_string_pretty(&sorted).unwrap()
+        );
+
+        let mut empty = Vec::new();
+        write_genesis_accounts(&mut empty, &HashMap::new()).unwrap();
+        assert_eq!(empty, b"{}");
+    }
+}
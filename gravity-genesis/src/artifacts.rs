@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use revm::{InMemoryDB, db::BundleState, primitives::Address};
+use tracing::info;
+
+use crate::{
+    execute::{BlockContext, load_db_from_genesis_accounts},
+    manifest::GenesisManifest,
+    utils::SYSTEM_ACCOUNT_INFO,
+};
+
+/// File names of the outputs within the output directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputNames {
+    pub gas_report: String,
+    pub bundle_state: String,
+    pub genesis_accounts: String,
+    pub genesis_contracts: String,
+    pub manifest: String,
+    pub attestation: String,
+    pub storage_labeled: String,
+    pub reth_chainspec: String,
+    pub supply_report: String,
+}
+
+impl Default for OutputNames {
+    fn default() -> Self {
+        Self::with_prefix("")
+    }
+}
+
+impl OutputNames {
+    /// The default names with `prefix` prepended, e.g. `mainnet-genesis_accounts.json`
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            gas_report: format!("{prefix}gas_report.json"),
+            bundle_state: format!("{prefix}bundle_state.json"),
+            genesis_accounts: format!("{prefix}genesis_accounts.json"),
+            genesis_contracts: format!("{prefix}genesis_contracts.json"),
+            manifest: format!("{prefix}manifest.json"),
+            attestation: format!("{prefix}genesis_attestation.json"),
+            storage_labeled: format!("{prefix}genesis_storage_labeled.json"),
+            reth_chainspec: format!("{prefix}reth_chainspec.json"),
+            supply_report: format!("{prefix}supply_report.json"),
+        }
+    }
+}
+
+/// Genesis state reloaded from the files `genesis_generate` wrote to an output directory
+pub struct GenesisOutput {
+    pub db: InMemoryDB,
+    pub bundle_state: BundleState,
+    pub block: BlockContext,
+    /// Caller the genesis transactions were sent from
+    pub system_caller: Address,
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &str) -> Result<T, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+/// Load `genesis_accounts.json`, `bundle_state.json` and `manifest.json` from `output_dir`,
+/// under the given `names`
+///
+/// The block context is taken from the manifest so verification calls run in
+/// the same block the genesis was generated in.
+pub fn load_genesis_output(output_dir: &str, names: &OutputNames) -> Result<GenesisOutput, String> {
+    let path = |name: &str| Path::new(output_dir).join(name).display().to_string();
+    let mut db = load_db_from_genesis_accounts(&path(&names.genesis_accounts))?;
+    let bundle_state: BundleState = read_json(&path(&names.bundle_state))?;
+    let manifest: GenesisManifest = read_json(&path(&names.manifest))?;
+    // Verification calls are sent from the system caller, as during generation
+    db.insert_account_info(manifest.system_caller, SYSTEM_ACCOUNT_INFO);
+    info!(
+        "Loaded {} accounts and {} bundle accounts from {}",
+        db.accounts.len(),
+        bundle_state.state.len(),
+        output_dir
+    );
+    Ok(GenesisOutput {
+        db,
+        bundle_state,
+        block: manifest.block_context(),
+        system_caller: manifest.system_caller,
+    })
+}
@@ -0,0 +1,140 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use revm_primitives::{Address, B256, Bytes, hex, keccak256};
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::GenesisManifest;
+
+/// Signature by a release manager over a generated genesis, written as `genesis_attestation.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisAttestation {
+    pub state_root: B256,
+    /// keccak256 of `manifest.json` as written to the output directory
+    pub manifest_hash: B256,
+    /// keccak256(state_root ++ manifest_hash), the digest that was signed
+    pub digest: B256,
+    /// 65-byte `r ++ s ++ v` signature with `v` in {27, 28}
+    pub signature: Bytes,
+    pub signer: Address,
+}
+
+/// Digest attested to for a genesis with the given state root and manifest hash
+pub fn attestation_digest(state_root: B256, manifest_hash: B256) -> B256 {
+    keccak256([state_root.as_slice(), manifest_hash.as_slice()].concat())
+}
+
+fn signer_address(key: &VerifyingKey) -> Address {
+    let point = key.to_encoded_point(false);
+    Address::from_slice(&keccak256(&point.as_bytes()[1..])[12..])
+}
+
+/// Read a hex-encoded secp256k1 private key, with or without a 0x prefix
+pub fn read_signing_key(path: &str) -> Result<SigningKey, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read sign key: {}", e))?;
+    let bytes = hex::decode(content.trim()).map_err(|e| format!("Invalid sign key hex: {}", e))?;
+    SigningKey::from_slice(&bytes).map_err(|e| format!("Invalid secp256k1 sign key: {}", e))
+}
+
+/// Sign the state root and the hash of `manifest.json` in `output_dir`
+pub fn attest_genesis(output_dir: &str, key: &SigningKey) -> Result<GenesisAttestation, String> {
+    attest_manifest(&Path::new(output_dir).join("manifest.json"), key)
+}
+
+/// Sign the state root and the hash of the manifest at `manifest_path`
+pub fn attest_manifest(
+    manifest_path: &Path,
+    key: &SigningKey,
+) -> Result<GenesisAttestation, String> {
+    let manifest_bytes = std::fs::read(manifest_path)
+        .map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?;
+    let manifest: GenesisManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|e| format!("Failed to parse {}: {}", manifest_path.display(), e))?;
+    let manifest_hash = keccak256(&manifest_bytes);
+    let digest = attestation_digest(manifest.state_root, manifest_hash);
+
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(digest.as_slice())
+        .map_err(|e| format!("Failed to sign genesis digest: {}", e))?;
+    let mut signature_bytes = signature.to_bytes().to_vec();
+    signature_bytes.push(27 + recovery_id.to_byte());
+
+    Ok(GenesisAttestation {
+        state_root: manifest.state_root,
+        manifest_hash,
+        digest,
+        signature: signature_bytes.into(),
+        signer: signer_address(key.verifying_key()),
+    })
+}
+
+impl GenesisAttestation {
+    /// Address that produced `signature` over `digest`
+    pub fn recover_signer(&self) -> Result<Address, String> {
+        if self.signature.len() != 65 {
+            return Err(format!(
+                "signature must be 65 bytes, got {}",
+                self.signature.len()
+            ));
+        }
+        let signature = Signature::from_slice(&self.signature[..64])
+            .map_err(|e| format!("Invalid signature: {}", e))?;
+        let recovery_id = RecoveryId::from_byte(self.signature[64].wrapping_sub(27))
+            .ok_or_else(|| format!("Invalid recovery id: {}", self.signature[64]))?;
+        let key =
+            VerifyingKey::recover_from_prehash(self.digest.as_slice(), &signature, recovery_id)
+                .map_err(|e| format!("Failed to recover signer: {}", e))?;
+        Ok(signer_address(&key))
+    }
+
+    pub fn write(&self, output_dir: &str) -> std::io::Result<()> {
+        self.write_to(&Path::new(output_dir).join("genesis_attestation.json"))
+    }
+
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execute::{BlockContext, prepare_env};
+    use revm_primitives::{SpecId, b256};
+
+    #[test]
+    fn test_attestation_recovers_to_signer_over_manifest_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_dir = dir.path().to_str().unwrap();
+
+        let mut manifest = GenesisManifest::from_env(
+            &prepare_env(&BlockContext {
+                chain_id: 1,
+                number: 0,
+                timestamp: 1_735_689_600,
+                basefee: 0,
+                coinbase: Address::ZERO,
+                gas_limit: None,
+            }),
+            SpecId::LATEST,
+        );
+        manifest.state_root =
+            b256!("1111111111111111111111111111111111111111111111111111111111111111");
+        manifest.write(output_dir).unwrap();
+
+        let key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let attestation = attest_genesis(output_dir, &key).unwrap();
+
+        let manifest_bytes = std::fs::read(dir.path().join("manifest.json")).unwrap();
+        assert_eq!(attestation.manifest_hash, keccak256(&manifest_bytes));
+        assert_eq!(
+            attestation.digest,
+            attestation_digest(manifest.state_root, attestation.manifest_hash)
+        );
+        assert_eq!(attestation.signer, signer_address(key.verifying_key()));
+        assert_eq!(attestation.recover_signer().unwrap(), attestation.signer);
+    }
+}
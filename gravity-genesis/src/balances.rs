@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use revm_primitives::U256;
+
+use crate::{context::RunContext, diagnostics::DiagnosticCode};
+
+/// Genesis balance in wei for each contract, keyed by contract name as listed in the contract registry
+pub type ContractBalances = HashMap<String, U256>;
+
+/// Balances contracts are deployed with unless overridden: 1 million ETH for
+/// `ValidatorManager` and `Genesis`, zero for everything else
+pub fn default_contract_balances() -> ContractBalances {
+    let one_million_eth = U256::from(1_000_000) * U256::from(10).pow(U256::from(18));
+    ["ValidatorManager", "Genesis"]
+        .into_iter()
+        .map(|name| (name.to_string(), one_million_eth))
+        .collect()
+}
+
+/// Apply the overrides in `balances.json` on top of the default balances
+///
+/// The file maps contract names to wei amounts, as decimal or 0x-prefixed hex
+/// strings. Names that are not in the contract registry are reported and ignored.
+pub fn read_contract_balances(
+    path: &str,
+    context: &RunContext,
+) -> Result<ContractBalances, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read balances file: {}", e))?;
+    let overrides: HashMap<String, U256> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse balances JSON: {}", e))?;
+    Ok(apply_balance_overrides(
+        default_contract_balances(),
+        overrides,
+        context,
+    ))
+}
+
+fn apply_balance_overrides(
+    mut balances: ContractBalances,
+    overrides: HashMap<String, U256>,
+    context: &RunContext,
+) -> ContractBalances {
+    for (name, balance) in overrides {
+        if context.registry.address_of(&name).is_none() {
+            context.diagnostics.emit(
+                DiagnosticCode::UnknownContractBalance,
+                format!("Ignoring balance for unknown contract {}", name),
+            );
+            continue;
+        }
+        balances.insert(name, balance);
+    }
+    balances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_replace_defaults_and_skip_unknown_contracts() {
+        let overrides: HashMap<String, U256> = serde_json::from_value(serde_json::json!({
+            "SystemReward": "1000",
+            "Genesis": "0x0",
+            "NotAContract": "5"
+        }))
+        .unwrap();
+        let balances = apply_balance_overrides(
+            default_contract_balances(),
+            overrides,
+            &RunContext::default(),
+        );
+
+        assert_eq!(balances["SystemReward"], U256::from(1000));
+        assert_eq!(balances["Genesis"], U256::ZERO);
+        assert_eq!(
+            balances["ValidatorManager"],
+            default_contract_balances()["ValidatorManager"]
+        );
+        assert!(!balances.contains_key("NotAContract"));
+    }
+}
@@ -0,0 +1,1096 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::File,
+    io::BufWriter,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use revm::{
+    InMemoryDB,
+    db::{BundleState, PlainAccount},
+    primitives::{AccountInfo, Address, EVMError, ExecutionResult, SpecId},
+};
+use serde::Serialize;
+use tracing::{debug, error, info};
+
+use crate::{
+    accounts_writer::write_genesis_accounts,
+    artifacts::OutputNames,
+    chainspec::{ExportFormat, RethChainSpec},
+    combined::{CombinedGenesis, CombinedMetadata},
+    config_validation::{format_config_errors, parse_config},
+    context::RunContext,
+    diagnostics::DiagnosticCode,
+    event_log::EventLog,
+    execute::{
+        BlockContext, DeployOptions, GENESIS_INITIALIZE_LABEL, build_genesis_transactions,
+        deploy_bsc_style, prepare_env,
+    },
+    extra_txns::read_extra_transactions,
+    gas_report::GasReport,
+    genesis::{GenesisConfig, check_total_voting_power, genesis_initialize_hint},
+    inspect::{SlotLabels, label_genesis_storage},
+    jwks::{read_jwks_input, read_oidc_input},
+    manifest::{GenesisManifest, config_hash},
+    state_root::compute_state_root,
+    supply_report::{DEFAULT_SUPPLY_REPORT_TOP, SupplyReport},
+    utils::{
+        DEFAULT_TX_TIME_BUDGET, GasLimits, PRODUCTION_GAS_LIMIT, analyze_txn_result,
+        execute_revm_sequential_timed,
+    },
+};
+
+/// Reasons building a genesis can fail
+#[derive(Debug)]
+pub enum GenesisError {
+    /// A required builder input was not provided
+    Missing(&'static str),
+    /// The genesis configuration, JWKs or OIDC providers could not be used
+    Config(String),
+    /// Contract bytecode could not be loaded or failed a deployment check
+    Deploy(String),
+    /// The EVM could not execute the genesis transactions
+    Execution(String),
+    /// A genesis transaction executed but did not succeed
+    TransactionFailed {
+        index: usize,
+        label: String,
+        analysis: String,
+    },
+    /// Several genesis transactions failed, reported together when continuing on error
+    TransactionsFailed(Vec<FailedTransaction>),
+    /// A prefunded account would overwrite a genesis contract account
+    AccountCollision(Address),
+    /// An output file could not be written
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl fmt::Display for GenesisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(input) => write!(f, "{} is required to build a genesis", input),
+            Self::Config(e) => write!(f, "Invalid config: {}", e),
+            Self::Deploy(e) => write!(f, "Deployment failed: {}", e),
+            Self::Execution(e) => write!(f, "Genesis execution failed: {}", e),
+            Self::TransactionFailed {
+                index,
+                label,
+                analysis,
+            } => write!(
+                f,
+                "Genesis transaction {} ({}) failed: {}",
+                index + 1,
+                label,
+                analysis
+            ),
+            Self::TransactionsFailed(failures) => {
+                write!(f, "{} genesis transactions failed:", failures.len())?;
+                for failure in failures {
+                    write!(
+                        f,
+                        "\n  transaction {} ({}): {}",
+                        failure.index + 1,
+                        failure.label,
+                        failure.analysis
+                    )?;
+                }
+                Ok(())
+            }
+            Self::AccountCollision(address) => write!(
+                f,
+                "Prefunded account {} collides with a genesis contract account",
+                address
+            ),
+            Self::Io { path, source } => {
+                write!(f, "Failed to write {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenesisError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A genesis transaction that executed but did not succeed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedTransaction {
+    pub index: usize,
+    pub label: String,
+    pub analysis: String,
+}
+
+/// Analysis of a failed transaction, with a hint for the common
+/// `Genesis.initialize` reverts
+fn failure_analysis(label: &str, result: &ExecutionResult, context: &RunContext) -> String {
+    let analysis = analyze_txn_result(result, context);
+    let hint = match result {
+        ExecutionResult::Revert { output, .. } if label == GENESIS_INITIALIZE_LABEL => {
+            genesis_initialize_hint(output)
+        }
+        _ => None,
+    };
+    match hint {
+        Some(hint) => format!("{}\nHint: {}", analysis, hint),
+        None => analysis,
+    }
+}
+
+/// Check that every genesis transaction succeeded
+///
+/// Stops at the first failure unless `continue_on_error` is set, in which
+/// case every failure is logged and they are all returned together.
+fn check_transaction_results(
+    labels: &[String],
+    results: &[ExecutionResult],
+    continue_on_error: bool,
+    context: &RunContext,
+) -> Result<(), GenesisError> {
+    let mut failures = Vec::new();
+    for (i, r) in results.iter().enumerate() {
+        if !r.is_success() {
+            error!("=== Transaction {} ({}) failed ===", i + 1, labels[i]);
+            let failure = FailedTransaction {
+                index: i,
+                label: labels[i].clone(),
+                analysis: failure_analysis(&labels[i], r, context),
+            };
+            if !continue_on_error {
+                return Err(GenesisError::TransactionFailed {
+                    index: failure.index,
+                    label: failure.label,
+                    analysis: failure.analysis,
+                });
+            }
+            error!("Detailed analysis: {}", failure.analysis);
+            failures.push(failure);
+            continue;
+        }
+        info!("Detailed analysis: {}", analyze_txn_result(r, context));
+    }
+    if !failures.is_empty() {
+        return Err(GenesisError::TransactionsFailed(failures));
+    }
+    Ok(())
+}
+
+/// Paths of the files written to the output directory
+#[derive(Debug, Clone)]
+pub struct GenesisFiles {
+    pub gas_report: PathBuf,
+    /// `None` when the split files were turned off with [`GenesisBuilder::with_split_files`]
+    pub bundle_state: Option<PathBuf>,
+    pub genesis_accounts: Option<PathBuf>,
+    pub manifest: PathBuf,
+    pub genesis_contracts: Option<PathBuf>,
+    /// Single-document genesis, when requested with [`GenesisBuilder::with_combined_output`]
+    pub combined: Option<PathBuf>,
+    /// Logs emitted by the genesis transactions, when requested with [`GenesisBuilder::with_log_dump`]
+    pub logs: Option<PathBuf>,
+    /// Labeled contract storage, when layouts were given with [`GenesisBuilder::with_storage_layouts`]
+    pub storage_labeled: Option<PathBuf>,
+    /// Reth chain spec, when requested with [`GenesisBuilder::with_export`]
+    pub reth_chainspec: Option<PathBuf>,
+    pub supply_report: PathBuf,
+}
+
+/// Result of a successful [`GenesisBuilder::build`]
+pub struct GenesisArtifacts {
+    /// Database holding the deployed contracts before the genesis transactions ran
+    pub db: InMemoryDB,
+    /// State changes made by the genesis transactions
+    pub bundle_state: BundleState,
+    pub manifest: GenesisManifest,
+    /// Gas used by each genesis transaction
+    pub gas_report: GasReport,
+    /// Wei allocated to the genesis accounts
+    pub supply_report: SupplyReport,
+    /// Number of contracts whose runtime bytecode was placed in the genesis state
+    pub contracts_deployed: usize,
+    /// Accounts of the genesis, as written to `genesis_accounts.json`
+    pub genesis_state: HashMap<Address, PlainAccount>,
+    /// `None` when writing was turned off with [`GenesisBuilder::with_write_files`]
+    pub files: Option<GenesisFiles>,
+}
+
+/// Programmatic entry point for generating a genesis
+///
+/// ```ignore
+/// let artifacts = GenesisBuilder::new("out")
+///     .with_config(config)
+///     .with_jwks_file("jwks.json")
+///     .with_chain_id(1337)
+///     .with_output_dir("genesis")
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenesisBuilder {
+    byte_code_dir: String,
+    config: Option<GenesisConfig>,
+    jwks_file: Option<String>,
+    oidc_file: Option<String>,
+    extra_txns_file: Option<String>,
+    output_dir: Option<PathBuf>,
+    combined_output: Option<PathBuf>,
+    log_dump: Option<PathBuf>,
+    split_files: bool,
+    keep_system_caller: bool,
+    block: BlockContext,
+    deploy: DeployOptions,
+    continue_on_error: bool,
+    base_state: Option<BundleState>,
+    write_files: bool,
+    gas_limits: GasLimits,
+    tx_time_budget: Duration,
+    output_names: OutputNames,
+    storage_layouts: Option<BTreeMap<String, SlotLabels>>,
+    export: Option<ExportFormat>,
+    keep_empty_accounts: bool,
+    supply_report_top: usize,
+    context: Arc<RunContext>,
+}
+
+impl GenesisBuilder {
+    /// Start a genesis from the `<Contract>.hex` files in `byte_code_dir`
+    pub fn new(byte_code_dir: impl Into<String>) -> Self {
+        Self {
+            byte_code_dir: byte_code_dir.into(),
+            config: None,
+            jwks_file: None,
+            oidc_file: None,
+            extra_txns_file: None,
+            output_dir: None,
+            combined_output: None,
+            log_dump: None,
+            split_files: true,
+            keep_system_caller: false,
+            block: BlockContext::default(),
+            deploy: DeployOptions::default(),
+            continue_on_error: false,
+            base_state: None,
+            write_files: true,
+            gas_limits: GasLimits::default(),
+            tx_time_budget: DEFAULT_TX_TIME_BUDGET,
+            output_names: OutputNames::default(),
+            storage_layouts: None,
+            export: None,
+            keep_empty_accounts: false,
+            supply_report_top: DEFAULT_SUPPLY_REPORT_TOP,
+            context: Arc::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: GenesisConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// JWKs file, or a directory of `*.json` files holding one provider each
+    pub fn with_jwks_file(mut self, jwks_file: impl Into<String>) -> Self {
+        self.jwks_file = Some(jwks_file.into());
+        self
+    }
+
+    /// OIDC providers file, or a directory of `*.json` files holding one provider each
+    pub fn with_oidc_file(mut self, oidc_file: impl Into<String>) -> Self {
+        self.oidc_file = Some(oidc_file.into());
+        self
+    }
+
+    /// Append the system calls listed in `extra_txns_file` after the standard genesis transactions
+    pub fn with_extra_txns_file(mut self, extra_txns_file: impl Into<String>) -> Self {
+        self.extra_txns_file = Some(extra_txns_file.into());
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.block.chain_id = chain_id;
+        self
+    }
+
+    /// Directory the output files are written to, created if missing
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(output_dir.into());
+        self
+    }
+
+    /// Also write accounts, contracts and metadata as one JSON document at `path`
+    pub fn with_combined_output(mut self, path: impl Into<PathBuf>) -> Self {
+        self.combined_output = Some(path.into());
+        self
+    }
+
+    /// Also write every log the genesis transactions emitted to `path`, even if one of them fails
+    pub fn with_log_dump(mut self, path: impl Into<PathBuf>) -> Self {
+        self.log_dump = Some(path.into());
+        self
+    }
+
+    /// Names of the files written to the output directory
+    pub fn with_output_names(mut self, output_names: OutputNames) -> Self {
+        self.output_names = output_names;
+        self
+    }
+
+    /// Also write `genesis_storage_labeled.json`, decoding each contract's storage with its layout
+    pub fn with_storage_layouts(mut self, layouts: BTreeMap<String, SlotLabels>) -> Self {
+        self.storage_layouts = Some(layouts);
+        self
+    }
+
+    /// Also write the genesis in another client's format, such as `reth_chainspec.json`
+    pub fn with_export(mut self, export: ExportFormat) -> Self {
+        self.export = Some(export);
+        self
+    }
+
+    /// Whether to keep accounts with no code, balance, nonce or storage in an
+    /// exported `alloc` (off by default)
+    pub fn with_keep_empty_accounts(mut self, keep_empty_accounts: bool) -> Self {
+        self.keep_empty_accounts = keep_empty_accounts;
+        self
+    }
+
+    /// Number of largest balances listed in `supply_report.json`
+    pub fn with_supply_report_top(mut self, top: usize) -> Self {
+        self.supply_report_top = top;
+        self
+    }
+
+    /// Whether to write `bundle_state.json`, `genesis_accounts.json` and
+    /// `genesis_contracts.json` (on by default)
+    pub fn with_split_files(mut self, split_files: bool) -> Self {
+        self.split_files = split_files;
+        self
+    }
+
+    /// Whether to keep the system caller's final state in `bundle_state.json`
+    /// (off by default); it never becomes a genesis account
+    pub fn with_keep_system_caller(mut self, keep_system_caller: bool) -> Self {
+        self.keep_system_caller = keep_system_caller;
+        self
+    }
+
+    /// Replace the whole block context, including the chain id
+    pub fn with_block(mut self, block: BlockContext) -> Self {
+        self.block = block;
+        self
+    }
+
+    pub fn with_deploy_options(mut self, deploy: DeployOptions) -> Self {
+        self.deploy = deploy;
+        self
+    }
+
+    /// Gas caps the genesis transactions are measured against or run with
+    pub fn with_gas_limits(mut self, gas_limits: GasLimits) -> Self {
+        self.gas_limits = gas_limits;
+        self
+    }
+
+    /// Wall-clock time each genesis transaction may run before the build is aborted
+    pub fn with_tx_time_budget(mut self, tx_time_budget: Duration) -> Self {
+        self.tx_time_budget = tx_time_budget;
+        self
+    }
+
+    /// Whether to write any output file (on by default)
+    ///
+    /// With writing off the genesis is built and returned in memory only, the
+    /// output directory is optional and the files are just logged.
+    pub fn with_write_files(mut self, write_files: bool) -> Self {
+        self.write_files = write_files;
+        self
+    }
+
+    /// Run the genesis transactions on top of a previously generated bundle state
+    ///
+    /// Accounts and storage slots of the base state that the transactions do
+    /// not touch are carried into the new genesis unchanged. A base state from
+    /// a full genesis has already run `Genesis.initialize`, which reverts when
+    /// sent again, so turn [`DeployOptions::initialize`] off to layer onto it.
+    pub fn with_base_state(mut self, base_state: BundleState) -> Self {
+        self.base_state = Some(base_state);
+        self
+    }
+
+    /// Run with the contract registry and other state of `context`, shared
+    /// with the caller for verifying the genesis and listing its warnings afterwards
+    pub fn with_context(mut self, context: Arc<RunContext>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Check every genesis transaction and report all failures instead of
+    /// stopping at the first one
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Deploy the contracts, run the genesis transactions and write the output files
+    ///
+    /// The configuration is validated first, and every problem found is
+    /// reported in one [`GenesisError::Config`].
+    pub fn build(self) -> Result<GenesisArtifacts, GenesisError> {
+        let config = self.config.ok_or(GenesisError::Missing("config"))?;
+        let context = &*self.context;
+        let parsed = parse_config(&config)
+            .map_err(|errors| GenesisError::Config(format_config_errors(&errors)))?;
+        if let Some(warning) = check_total_voting_power(
+            &parsed.init_param.voting_powers,
+            config.max_total_voting_power_bits,
+            config.decimals,
+        ) {
+            context
+                .diagnostics
+                .emit(DiagnosticCode::VotingPowerBound, warning);
+        }
+        let output = match self.output_dir {
+            Some(path) => OutputDir {
+                path,
+                write: self.write_files,
+            },
+            None if !self.write_files => OutputDir {
+                path: PathBuf::from("."),
+                write: false,
+            },
+            None => return Err(GenesisError::Missing("output directory")),
+        };
+        if output.write {
+            std::fs::create_dir_all(&output.path).map_err(|source| GenesisError::Io {
+                path: output.path.clone(),
+                source,
+            })?;
+        } else {
+            info!("Writing is disabled; the genesis is built in memory only");
+        }
+        let prefunded_accounts = parsed.prefunded_accounts;
+
+        info!("=== Starting Genesis deployment and initialization ===");
+
+        context.progress.start_phase(
+            "deploying contracts",
+            Some(context.registry.contracts().len() as u64),
+        );
+        let (db, runtime_bytecodes) = deploy_bsc_style(&self.byte_code_dir, &self.deploy, context)
+            .map_err(GenesisError::Deploy)?;
+        context.progress.complete_phase();
+
+        info!("Genesis block context: {:?}", self.block);
+        let env = prepare_env(&self.block);
+
+        // Read once, so the manifest hashes exactly the inputs the transactions are built from
+        let jwks = self
+            .jwks_file
+            .as_deref()
+            .map(|path| read_jwks_input(path, context))
+            .transpose()
+            .map_err(GenesisError::Config)?;
+        let oidc_providers = self
+            .oidc_file
+            .as_deref()
+            .map(|path| read_oidc_input(path, context))
+            .transpose()
+            .map_err(GenesisError::Config)?;
+
+        let extra_transactions = self
+            .extra_txns_file
+            .as_deref()
+            .map(|path| read_extra_transactions(path, context))
+            .transpose()
+            .map_err(GenesisError::Config)?
+            .unwrap_or_default();
+        let (labels, mut txs) = build_genesis_transactions(
+            &config,
+            jwks.as_ref(),
+            oidc_providers.as_ref(),
+            &extra_transactions,
+            &self.deploy,
+            context,
+        )
+        .map_err(GenesisError::Config)?;
+        self.gas_limits.apply(&mut txs);
+
+        if let Some(base_state) = &self.base_state {
+            info!(
+                "Layering genesis transactions onto a base state of {} accounts",
+                base_state.state.len()
+            );
+        }
+        context
+            .progress
+            .start_phase("executing transactions", Some(txs.len() as u64));
+        let (result, mut bundle_state) = execute_revm_sequential_timed(
+            db.clone(),
+            SpecId::LATEST,
+            env.clone(),
+            &txs,
+            self.base_state,
+            self.tx_time_budget,
+            context,
+        )
+        .map_err(|e| match e {
+            EVMError::Custom(message) => GenesisError::Execution(message),
+            e => GenesisError::Execution(format!(
+                "{:?}",
+                e.map_db_err(|_| "Database error".to_string())
+            )),
+        })?;
+        info!("=== Genesis initialization successful ===");
+        debug!("the bundle state is {:?}", bundle_state);
+        let returned_bundle_state = bundle_state.clone();
+
+        for (i, gas_used, cap) in self.gas_limits.exceeded(&txs, &result) {
+            context.diagnostics.emit(
+                DiagnosticCode::GasCapExceeded,
+                format!(
+                    "transaction {} ({}) used {} gas, over its cap of {}",
+                    i + 1,
+                    labels[i],
+                    gas_used,
+                    cap
+                ),
+            );
+        }
+        if let Some(path) = self.log_dump.as_ref().filter(|_| !output.write) {
+            info!("Not writing {}: writing is disabled", path.display());
+        } else if let Some(path) = &self.log_dump {
+            let event_log = EventLog::new(&labels, &result);
+            event_log.write(path).map_err(|source| GenesisError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            info!(
+                "{} genesis logs written to {}",
+                event_log.logs.len(),
+                path.display()
+            );
+        }
+        check_transaction_results(&labels, &result, self.continue_on_error, context)?;
+        info!(
+            "=== All {} transactions completed successfully ===",
+            result.len()
+        );
+
+        let gas_report = GasReport::new(&labels, &txs, &result);
+        info!("Total genesis gas used: {}", gas_report.total_gas_used);
+        if let Some(block_gas_limit) = self
+            .block
+            .gas_limit
+            .filter(|limit| gas_report.total_gas_used > *limit)
+        {
+            context.diagnostics.emit(
+                DiagnosticCode::BlockGasLimitExceeded,
+                format!(
+                    "genesis transactions used {} gas in total, more than the block gas limit of {}",
+                    gas_report.total_gas_used, block_gas_limit
+                ),
+            );
+        }
+        let gas_report_path = output.write_json(&self.output_names.gas_report, &gas_report)?;
+
+        // Add deployed contracts to the final state, with the balances they were deployed with,
+        // since the bundle state below only holds the accounts a transaction touched
+        let mut genesis_state = HashMap::new();
+
+        let contracts_deployed = runtime_bytecodes.len();
+        for (contract_name, contract_address, runtime_bytecode) in runtime_bytecodes {
+            genesis_state.insert(
+                contract_address,
+                PlainAccount {
+                    info: self.deploy.contract_account_info(
+                        runtime_bytecode,
+                        self.deploy.contract_balance(contract_name),
+                    ),
+                    storage: Default::default(),
+                },
+            );
+
+            info!(
+                "Added {} to genesis state at {:?}",
+                contract_name, contract_address
+            );
+        }
+
+        // The system caller only sends the genesis transactions and is funded
+        // for them alone, so it is not an account of the genesis itself
+        if !self.keep_system_caller {
+            bundle_state.state.remove(&self.deploy.system_caller);
+        }
+        // write bundle state into one json file named bundle_state.json
+        let bundle_state_path = self
+            .split_files
+            .then(|| output.write_json(&self.output_names.bundle_state, &bundle_state))
+            .transpose()?;
+        bundle_state.state.remove(&self.deploy.system_caller);
+
+        info!(
+            "bundle state size is {:?}, contracts size {:?}",
+            bundle_state.state.len(),
+            context.registry.contracts().len()
+        );
+        // Merge in every account the genesis transactions changed, which also
+        // holds the base state's accounts when layering onto one
+        for (address, account) in bundle_state.state.into_iter() {
+            debug!(
+                "Address: {}, account: {:?}",
+                context.registry.display_address(address),
+                account
+            );
+            if let Some(info) = account.info {
+                let storage = account
+                    .storage
+                    .into_iter()
+                    .map(|(k, v)| (k, v.present_value()))
+                    .collect();
+
+                // If this address already exists in genesis_state, merge the storage
+                if let Some(existing) = genesis_state.get_mut(&address) {
+                    existing.storage.extend(storage);
+                    existing.info = info;
+                } else {
+                    genesis_state.insert(address, PlainAccount { info, storage });
+                }
+            }
+        }
+
+        for (address, balance) in prefunded_accounts {
+            if genesis_state.contains_key(&address) {
+                return Err(GenesisError::AccountCollision(address));
+            }
+            genesis_state.insert(
+                address,
+                PlainAccount {
+                    info: AccountInfo {
+                        balance,
+                        ..AccountInfo::default()
+                    },
+                    storage: Default::default(),
+                },
+            );
+            info!("Prefunded {} with {} wei", address, balance);
+        }
+
+        let supply_report =
+            SupplyReport::new(&genesis_state, self.supply_report_top, &context.registry);
+        info!(
+            "Genesis total supply: {} wei across {} funded accounts",
+            supply_report.total_supply, supply_report.funded_accounts
+        );
+        let supply_report_path =
+            output.write_json(&self.output_names.supply_report, &supply_report)?;
+
+        let genesis_accounts_path = self
+            .split_files
+            .then(|| {
+                output.write_json_with(&self.output_names.genesis_accounts, |writer| {
+                    write_genesis_accounts(writer, &genesis_state)
+                })
+            })
+            .transpose()?;
+
+        let mut manifest = GenesisManifest::from_env(&env, SpecId::LATEST);
+        manifest.state_root = compute_state_root(&genesis_state);
+        info!("Genesis state root: {}", manifest.state_root);
+        manifest.config_hash = config_hash(
+            &config,
+            jwks.as_ref().map(|jwks| jwks.content.as_bytes()),
+            oidc_providers
+                .as_ref()
+                .map(|providers| providers.content.as_bytes()),
+        );
+        info!("Genesis config hash: {}", manifest.config_hash);
+        manifest.system_caller = self.deploy.system_caller;
+        let manifest_path = output.write_json(&self.output_names.manifest, &manifest)?;
+
+        // Create contracts JSON with bytecode
+        let contracts_json: HashMap<_, _> = genesis_state
+            .iter()
+            .filter_map(|(addr, account)| {
+                account
+                    .info
+                    .code
+                    .as_ref()
+                    .map(|code| (*addr, code.bytecode()))
+            })
+            .collect();
+        let genesis_contracts_path = self
+            .split_files
+            .then(|| output.write_json(&self.output_names.genesis_contracts, &contracts_json))
+            .transpose()?;
+        let storage_labeled_path = self
+            .storage_layouts
+            .as_ref()
+            .map(|layouts| {
+                output.write_json(
+                    &self.output_names.storage_labeled,
+                    &label_genesis_storage(&genesis_state, layouts, &context.registry),
+                )
+            })
+            .transpose()?;
+        let reth_chainspec_path = match self.export {
+            Some(ExportFormat::Reth) => Some(output.write_json(
+                &self.output_names.reth_chainspec,
+                &RethChainSpec::new(
+                    &manifest,
+                    &genesis_state,
+                    manifest.block_gas_limit.unwrap_or(PRODUCTION_GAS_LIMIT),
+                    self.keep_empty_accounts,
+                ),
+            )?),
+            None => None,
+        };
+
+        if let Some(path) = self.combined_output.as_ref().filter(|_| !output.write) {
+            info!(
+                "Not writing {}: writing is disabled ({} accounts, {} contracts)",
+                path.display(),
+                genesis_state.len(),
+                contracts_json.len()
+            );
+        } else if let Some(path) = &self.combined_output {
+            CombinedGenesis {
+                accounts: &genesis_state,
+                contracts: &contracts_json,
+                metadata: CombinedMetadata::from(&manifest),
+            }
+            .write(path)
+            .map_err(|source| GenesisError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            info!("Combined genesis written to {}", path.display());
+        }
+
+        Ok(GenesisArtifacts {
+            db,
+            bundle_state: returned_bundle_state,
+            manifest,
+            gas_report,
+            supply_report,
+            contracts_deployed,
+            genesis_state,
+            files: output.write.then_some(GenesisFiles {
+                gas_report: gas_report_path,
+                bundle_state: bundle_state_path,
+                genesis_accounts: genesis_accounts_path,
+                manifest: manifest_path,
+                genesis_contracts: genesis_contracts_path,
+                combined: self.combined_output,
+                logs: self.log_dump,
+                storage_labeled: storage_labeled_path,
+                reth_chainspec: reth_chainspec_path,
+                supply_report: supply_report_path,
+            }),
+        })
+    }
+}
+
+/// Directory the output files go to
+struct OutputDir {
+    path: PathBuf,
+    /// When unset, each file is only logged
+    write: bool,
+}
+
+impl OutputDir {
+    fn write_json<T: Serialize>(
+        &self,
+        file_name: &str,
+        value: &T,
+    ) -> Result<PathBuf, GenesisError> {
+        self.write_json_with(file_name, |writer| {
+            serde_json::to_writer_pretty(writer, value)
+        })
+    }
+
+    /// Write `file_name` with `write`, returning its path
+    fn write_json_with(
+        &self,
+        file_name: &str,
+        write: impl FnOnce(BufWriter<File>) -> serde_json::Result<()>,
+    ) -> Result<PathBuf, GenesisError> {
+        let path = self.path.join(file_name);
+        if !self.write {
+            info!("Not writing {}: writing is disabled", path.display());
+            return Ok(path);
+        }
+        File::create(&path)
+            .and_then(|file| write(BufWriter::new(file)).map_err(std::io::Error::from))
+            .map_err(|source| GenesisError::Io {
+                path: path.clone(),
+                source,
+            })?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Contract bytecode compiled by `forge build` and `extract_bytecode.py`
+    const BYTECODE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../out");
+
+    #[test]
+    fn test_build_requires_config_and_output_dir() {
+        let err = GenesisBuilder::new("out").build().err().unwrap();
+        assert_eq!(err.to_string(), "config is required to build a genesis");
+
+        let config: GenesisConfig = serde_json::from_value(serde_json::json!({
+            "validatorAddresses": [],
+            "consensusPublicKeys": [],
+            "votingPowers": [],
+            "validatorNetworkAddresses": [],
+            "fullnodeNetworkAddresses": [],
+            "aptosAddresses": [],
+        }))
+        .unwrap();
+        let err = GenesisBuilder::new("out")
+            .with_config(config)
+            .with_chain_id(1337)
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(err, GenesisError::Missing("output directory")));
+    }
+
+    #[test]
+    fn test_invalid_config_fails_before_deployment() {
+        let mut config = crate::config_template::template_config();
+        config.voting_powers[0] = "0".to_string();
+        config.aptos_addresses[0] = "00".to_string();
+        let err = GenesisBuilder::new("missing-bytecode-dir")
+            .with_config(config)
+            .with_write_files(false)
+            .build()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Invalid config: genesis config has 2 problems:\n  \
+             votingPowers[0] is zero\n  \
+             aptosAddresses[0] is 1 bytes, expected 32"
+        );
+    }
+
+    #[test]
+    fn test_contract_balances_survive_without_a_transaction_touching_them() {
+        use revm::primitives::U256;
+
+        use crate::utils::SYSTEM_REWARD_ADDR;
+
+        let dir = tempfile::tempdir().unwrap();
+        // STOP; no genesis transaction is sent without Genesis
+        std::fs::write(dir.path().join("SystemReward.hex"), "00").unwrap();
+        let mut deploy = DeployOptions {
+            only: Some(["SystemReward".to_string()].into()),
+            ..DeployOptions::default()
+        };
+        deploy
+            .balances
+            .insert("SystemReward".to_string(), U256::from(42));
+
+        let artifacts = GenesisBuilder::new(dir.path().to_string_lossy())
+            .with_config(crate::config_template::template_config())
+            .with_deploy_options(deploy)
+            .with_write_files(false)
+            .build()
+            .unwrap();
+        assert!(artifacts.gas_report.transactions.is_empty());
+        assert_eq!(
+            artifacts.genesis_state[&SYSTEM_REWARD_ADDR].info.balance,
+            U256::from(42)
+        );
+        assert_eq!(artifacts.supply_report.total_supply, U256::from(42));
+    }
+
+    #[test]
+    fn test_continue_on_error_reports_every_failed_transaction() {
+        use revm::primitives::{Bytes, HaltReason, OutOfGasError, Output, SuccessReason};
+
+        let labels: Vec<String> = ["Genesis.initialize", "JWKManager.upsertObservedJWKs", "c"]
+            .map(String::from)
+            .to_vec();
+        let results = vec![
+            ExecutionResult::Revert {
+                gas_used: 100,
+                output: Bytes::new(),
+            },
+            ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used: 100,
+                gas_refunded: 0,
+                logs: Vec::new(),
+                output: Output::Call(Bytes::new()),
+            },
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+                gas_used: 100,
+            },
+        ];
+
+        let err = check_transaction_results(&labels, &results, false, &RunContext::default())
+            .unwrap_err();
+        assert!(
+            matches!(err, GenesisError::TransactionFailed { index: 0, .. }),
+            "{}",
+            err
+        );
+
+        let err =
+            check_transaction_results(&labels, &results, true, &RunContext::default()).unwrap_err();
+        let GenesisError::TransactionsFailed(failures) = &err else {
+            panic!("unexpected error: {}", err);
+        };
+        assert_eq!(
+            failures.iter().map(|f| f.index).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        let message = err.to_string();
+        assert!(
+            message.starts_with("2 genesis transactions failed:"),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("transaction 1 (Genesis.initialize)"),
+            "{}",
+            message
+        );
+        assert!(message.contains("transaction 3 (c)"), "{}", message);
+
+        assert!(
+            check_transaction_results(&labels[1..2], &results[1..2], true, &RunContext::default())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    #[ignore = "needs ../out from forge build and extract_bytecode.py"]
+    fn test_transactions_layer_onto_a_generated_bundle_state() {
+        use crate::{config_template::template_config, utils::VALIDATOR_MANAGER_ADDR};
+
+        let dir = tempfile::tempdir().unwrap();
+        let base_dir = dir.path().join("base");
+        let base = GenesisBuilder::new(BYTECODE_DIR)
+            .with_config(template_config())
+            .with_output_dir(&base_dir)
+            .build()
+            .unwrap();
+        let oidc_file = dir.path().join("oidc_providers.json");
+        std::fs::write(
+            &oidc_file,
+            serde_json::json!({
+                "providers": [{
+                    "name": "https://accounts.google.com",
+                    "configUrl": "https://accounts.google.com/.well-known/openid-configuration",
+                    "active": true
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let layer = |deploy: DeployOptions| {
+            let base_state = crate::inspect::read_bundle_state(
+                &base_dir.join("bundle_state.json").to_string_lossy(),
+            )
+            .unwrap();
+            GenesisBuilder::new(BYTECODE_DIR)
+                .with_config(template_config())
+                .with_oidc_file(oidc_file.to_string_lossy())
+                .with_deploy_options(deploy)
+                .with_base_state(base_state)
+                .with_write_files(false)
+                .build()
+        };
+
+        // The base genesis already ran Genesis.initialize
+        let err = layer(DeployOptions::default()).err().unwrap();
+        assert!(
+            matches!(err, GenesisError::TransactionFailed { index: 0, .. }),
+            "{}",
+            err
+        );
+
+        let layered = layer(DeployOptions {
+            initialize: false,
+            ..DeployOptions::default()
+        })
+        .unwrap();
+        assert!(
+            layered
+                .gas_report
+                .transactions
+                .iter()
+                .all(|tx| tx.label != GENESIS_INITIALIZE_LABEL)
+        );
+        let validator_manager = |genesis_state: &HashMap<Address, PlainAccount>| {
+            genesis_state[&VALIDATOR_MANAGER_ADDR].storage.clone()
+        };
+        assert!(!validator_manager(&base.genesis_state).is_empty());
+        assert_eq!(
+            validator_manager(&layered.genesis_state),
+            validator_manager(&base.genesis_state)
+        );
+    }
+
+    #[test]
+    #[ignore = "needs ../out from forge build and extract_bytecode.py"]
+    fn test_initialize_layers_onto_a_deployment_only_genesis() {
+        use crate::{config_template::template_config, utils::VALIDATOR_MANAGER_ADDR};
+
+        let dir = tempfile::tempdir().unwrap();
+        let deployed_dir = dir.path().join("deployed");
+        let deployed = GenesisBuilder::new(BYTECODE_DIR)
+            .with_config(template_config())
+            .with_deploy_options(DeployOptions {
+                initialize: false,
+                ..DeployOptions::default()
+            })
+            .with_output_dir(&deployed_dir)
+            .build()
+            .unwrap();
+        assert!(deployed.gas_report.transactions.is_empty());
+        assert!(
+            deployed.genesis_state[&VALIDATOR_MANAGER_ADDR]
+                .storage
+                .is_empty()
+        );
+
+        let base_state = crate::inspect::read_bundle_state(
+            &deployed_dir.join("bundle_state.json").to_string_lossy(),
+        )
+        .unwrap();
+        let layered = GenesisBuilder::new(BYTECODE_DIR)
+            .with_config(template_config())
+            .with_base_state(base_state)
+            .with_write_files(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            layered.gas_report.transactions[0].label,
+            GENESIS_INITIALIZE_LABEL
+        );
+
+        // Staged, the genesis ends up as if generated in one run
+        let direct = GenesisBuilder::new(BYTECODE_DIR)
+            .with_config(template_config())
+            .with_write_files(false)
+            .build()
+            .unwrap();
+        assert_eq!(layered.manifest.state_root, direct.manifest.state_root);
+    }
+}
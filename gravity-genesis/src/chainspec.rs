@@ -0,0 +1,312 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
+
+use revm::db::PlainAccount;
+use revm_primitives::{Address, B256, Bytes, U64, U256};
+use serde::Serialize;
+
+use crate::{manifest::GenesisManifest, state_root::compute_state_root};
+
+/// Genesis formats that can be exported next to the native output files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Chain spec JSON that Reth imports with `--chain`
+    Reth,
+}
+
+/// Hardforks of a Reth chain spec, all active from genesis
+///
+/// Genesis transactions are executed with the latest spec, so the chain
+/// starts with every fork up to Prague enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RethChainConfig {
+    pub chain_id: u64,
+    pub homestead_block: u64,
+    pub eip150_block: u64,
+    pub eip155_block: u64,
+    pub eip158_block: u64,
+    pub byzantium_block: u64,
+    pub constantinople_block: u64,
+    pub petersburg_block: u64,
+    pub istanbul_block: u64,
+    pub berlin_block: u64,
+    pub london_block: u64,
+    pub terminal_total_difficulty: u64,
+    pub terminal_total_difficulty_passed: bool,
+    pub shanghai_time: u64,
+    pub cancun_time: u64,
+    pub prague_time: u64,
+}
+
+impl RethChainConfig {
+    fn all_active(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            homestead_block: 0,
+            eip150_block: 0,
+            eip155_block: 0,
+            eip158_block: 0,
+            byzantium_block: 0,
+            constantinople_block: 0,
+            petersburg_block: 0,
+            istanbul_block: 0,
+            berlin_block: 0,
+            london_block: 0,
+            terminal_total_difficulty: 0,
+            terminal_total_difficulty_passed: true,
+            shanghai_time: 0,
+            cancun_time: 0,
+            prague_time: 0,
+        }
+    }
+}
+
+/// One account of the chain spec `alloc`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RethAccount {
+    pub balance: U256,
+    #[serde(skip_serializing_if = "U64::is_zero")]
+    pub nonce: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Non-zero slots only, since a zero slot is absent from the state trie
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, B256>,
+}
+
+impl From<&PlainAccount> for RethAccount {
+    fn from(account: &PlainAccount) -> Self {
+        Self {
+            balance: account.info.balance,
+            nonce: U64::from(account.info.nonce),
+            code: account
+                .info
+                .code
+                .as_ref()
+                .map(|code| code.original_bytes())
+                .filter(|code| !code.is_empty()),
+            storage: account
+                .storage
+                .iter()
+                .filter(|(_, value)| !value.is_zero())
+                .map(|(slot, value)| (B256::from(*slot), B256::from(*value)))
+                .collect(),
+        }
+    }
+}
+
+/// Whether an account is empty in the EIP-161 sense and holds no storage either
+///
+/// Nodes drop such accounts from their state, so they do not belong in an `alloc`.
+pub fn is_empty_account(account: &PlainAccount) -> bool {
+    account.info.balance.is_zero()
+        && account.info.nonce == 0
+        && account
+            .info
+            .code
+            .as_ref()
+            .is_none_or(|code| code.is_empty())
+        && account.storage.values().all(|value| value.is_zero())
+}
+
+/// Geth-style genesis with the fields Reth reads into its `ChainSpec`
+///
+/// `stateRoot` is not part of the format; it is the root computed by this
+/// tool, for checking against the genesis hash Reth logs on import. It is
+/// recomputed when empty accounts are pruned from the `alloc`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RethChainSpec {
+    pub config: RethChainConfig,
+    pub nonce: U64,
+    pub timestamp: U64,
+    pub extra_data: Bytes,
+    pub gas_limit: U64,
+    pub difficulty: U256,
+    pub mix_hash: B256,
+    pub coinbase: Address,
+    pub number: U64,
+    pub base_fee_per_gas: U64,
+    pub excess_blob_gas: U64,
+    pub blob_gas_used: U64,
+    pub state_root: B256,
+    pub alloc: BTreeMap<Address, RethAccount>,
+}
+
+impl RethChainSpec {
+    /// Chain spec of `genesis_state`, with the header taken from its manifest
+    ///
+    /// Accounts that are [empty](is_empty_account) are left out of the `alloc`
+    /// unless `keep_empty` is set.
+    pub fn new(
+        manifest: &GenesisManifest,
+        genesis_state: &HashMap<Address, PlainAccount>,
+        gas_limit: u64,
+        keep_empty: bool,
+    ) -> Self {
+        let (alloc_state, state_root) =
+            if keep_empty || !genesis_state.values().any(is_empty_account) {
+                (Cow::Borrowed(genesis_state), manifest.state_root)
+            } else {
+                let pruned: HashMap<Address, PlainAccount> = genesis_state
+                    .iter()
+                    .filter(|(_, account)| !is_empty_account(account))
+                    .map(|(address, account)| (*address, account.clone()))
+                    .collect();
+                let state_root = compute_state_root(&pruned);
+                (Cow::Owned(pruned), state_root)
+            };
+        Self {
+            config: RethChainConfig::all_active(manifest.chain_id),
+            nonce: U64::ZERO,
+            timestamp: U64::from(manifest.timestamp),
+            extra_data: Bytes::new(),
+            gas_limit: U64::from(gas_limit),
+            difficulty: U256::ZERO,
+            mix_hash: B256::ZERO,
+            coinbase: manifest.coinbase,
+            number: U64::from(manifest.block_number),
+            base_fee_per_gas: U64::from(manifest.basefee),
+            excess_blob_gas: U64::ZERO,
+            blob_gas_used: U64::ZERO,
+            state_root,
+            alloc: alloc_state
+                .iter()
+                .map(|(address, account)| (*address, RethAccount::from(account)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execute::{BlockContext, prepare_env};
+    use revm::primitives::{AccountInfo, Bytecode, SpecId};
+
+    #[test]
+    fn test_reth_chain_spec_has_header_and_alloc() {
+        let contract = Address::with_last_byte(0x10);
+        let funded = Address::with_last_byte(0x11);
+        let genesis_state = HashMap::from([
+            (
+                contract,
+                PlainAccount {
+                    info: AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[
+                        0x60, 0x00,
+                    ]))),
+                    storage: [(U256::from(1), U256::from(7)), (U256::from(2), U256::ZERO)]
+                        .into_iter()
+                        .collect(),
+                },
+            ),
+            (
+                funded,
+                PlainAccount {
+                    info: AccountInfo {
+                        balance: U256::from(1000),
+                        ..AccountInfo::default()
+                    },
+                    storage: Default::default(),
+                },
+            ),
+        ]);
+        let block = BlockContext {
+            chain_id: 1337,
+            timestamp: 1_735_689_600,
+            ..BlockContext::default()
+        };
+        let mut manifest = GenesisManifest::from_env(&prepare_env(&block), SpecId::LATEST);
+        manifest.state_root = B256::repeat_byte(0x11);
+
+        let json = serde_json::to_value(RethChainSpec::new(
+            &manifest,
+            &genesis_state,
+            30_000_000,
+            false,
+        ))
+        .unwrap();
+        assert_eq!(json["config"]["chainId"], 1337);
+        assert_eq!(json["config"]["pragueTime"], 0);
+        assert_eq!(json["timestamp"], "0x67748580");
+        assert_eq!(json["gasLimit"], "0x1c9c380");
+        assert_eq!(json["stateRoot"], serde_json::json!(manifest.state_root));
+
+        let alloc = &json["alloc"];
+        assert_eq!(alloc[contract.to_string()]["code"], "0x6000");
+        let storage = alloc[contract.to_string()]["storage"].as_object().unwrap();
+        assert_eq!(storage.len(), 1);
+        assert_eq!(
+            storage[&B256::with_last_byte(1).to_string()],
+            serde_json::json!(B256::with_last_byte(7))
+        );
+        assert_eq!(alloc[funded.to_string()]["balance"], "0x3e8");
+        assert!(alloc[funded.to_string()].get("code").is_none());
+        assert!(alloc[funded.to_string()].get("nonce").is_none());
+    }
+
+    #[test]
+    fn test_empty_accounts_are_pruned_from_alloc() {
+        let code_only = Address::with_last_byte(0x10);
+        let empty = Address::with_last_byte(0x11);
+        let zeroed_storage = Address::with_last_byte(0x12);
+        let with_nonce = Address::with_last_byte(0x13);
+        let genesis_state = HashMap::from([
+            (
+                code_only,
+                PlainAccount {
+                    info: AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[
+                        0x60, 0x00,
+                    ]))),
+                    storage: Default::default(),
+                },
+            ),
+            (
+                empty,
+                PlainAccount {
+                    info: AccountInfo::default(),
+                    storage: Default::default(),
+                },
+            ),
+            (
+                zeroed_storage,
+                PlainAccount {
+                    info: AccountInfo::default(),
+                    storage: [(U256::from(1), U256::ZERO)].into_iter().collect(),
+                },
+            ),
+            (
+                with_nonce,
+                PlainAccount {
+                    info: AccountInfo {
+                        nonce: 1,
+                        ..AccountInfo::default()
+                    },
+                    storage: Default::default(),
+                },
+            ),
+        ]);
+        let mut manifest =
+            GenesisManifest::from_env(&prepare_env(&BlockContext::default()), SpecId::LATEST);
+        manifest.state_root = compute_state_root(&genesis_state);
+
+        let pruned = RethChainSpec::new(&manifest, &genesis_state, 30_000_000, false);
+        assert_eq!(
+            pruned.alloc.keys().copied().collect::<Vec<_>>(),
+            vec![code_only, with_nonce]
+        );
+        let kept: HashMap<Address, PlainAccount> = genesis_state
+            .iter()
+            .filter(|(address, _)| pruned.alloc.contains_key(address))
+            .map(|(address, account)| (*address, account.clone()))
+            .collect();
+        assert_eq!(pruned.state_root, compute_state_root(&kept));
+
+        let full = RethChainSpec::new(&manifest, &genesis_state, 30_000_000, true);
+        assert_eq!(full.alloc.len(), 4);
+        assert_eq!(full.state_root, manifest.state_root);
+    }
+}
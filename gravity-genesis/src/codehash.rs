@@ -0,0 +1,302 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use revm_primitives::{B256, hex, keccak256};
+use walkdir::WalkDir;
+
+use crate::{linking::LibraryLinker, registry::ContractRegistry};
+
+/// Approved runtime code hashes per contract name, each labeled with the release it belongs to
+pub type CodeHashDb = BTreeMap<String, BTreeMap<String, B256>>;
+
+/// Read a code hash database of the form `{"Genesis": {"v1.0.0": "0x..", "v1.1.0": "0x.."}}`
+pub fn read_codehash_db(path: &str) -> Result<CodeHashDb, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read code hash database: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse code hash database: {}", e))
+}
+
+/// Check `code_hash` against the approved hashes of `contract_name`
+///
+/// Returns the version label of the matching entry. A contract missing from
+/// the database has no approved hashes and always fails.
+pub fn check_code_hash<'a>(
+    db: &'a CodeHashDb,
+    contract_name: &str,
+    code_hash: B256,
+) -> Result<&'a str, String> {
+    let approved = db
+        .get(contract_name)
+        .ok_or_else(|| format!("{} has no approved code hashes", contract_name))?;
+    approved
+        .iter()
+        .find(|(_, hash)| **hash == code_hash)
+        .map(|(version, _)| version.as_str())
+        .ok_or_else(|| {
+            format!(
+                "{} code hash {} is not approved (approved versions: {})",
+                contract_name,
+                code_hash,
+                approved.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
+        })
+}
+
+/// Expected runtime code hash per contract name, as in `bytecode_manifest.json`
+pub type BytecodeManifest = BTreeMap<String, B256>;
+
+/// Read a bytecode manifest of the form `{"Genesis": "0x..", "Block": "0x.."}`
+///
+/// Contracts may be left out, but every name must be in the registry so a
+/// typo cannot silently skip a check.
+pub fn read_bytecode_manifest(
+    path: &str,
+    registry: &ContractRegistry,
+) -> Result<BytecodeManifest, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read bytecode manifest: {}", e))?;
+    let manifest: BytecodeManifest = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse bytecode manifest: {}", e))?;
+    let unknown: Vec<&str> = manifest
+        .keys()
+        .filter(|name| registry.address_of(name).is_none())
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!(
+            "bytecode manifest names unknown contracts: {}",
+            unknown.join(", ")
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Check `code_hash` against the expected hash of `contract_name`, if the manifest has one
+pub fn check_bytecode_manifest(
+    manifest: &BytecodeManifest,
+    contract_name: &str,
+    code_hash: B256,
+) -> Result<(), String> {
+    match manifest.get(contract_name) {
+        Some(expected) if *expected != code_hash => Err(format!(
+            "{} bytecode hash {} does not match {} in the bytecode manifest",
+            contract_name, code_hash, expected
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Find the `Name.json` of each registered contract under `dir`
+///
+/// Foundry writes the artifact of `Name` to `Name.sol/Name.json`, which wins
+/// over any other `Name.json`. A contract with several candidates and none at
+/// that path is ambiguous and fails; contracts without one are left out.
+pub fn find_contract_json(
+    dir: &str,
+    registry: &ContractRegistry,
+) -> Result<BTreeMap<String, PathBuf>, String> {
+    let mut candidates: BTreeMap<&str, Vec<PathBuf>> = BTreeMap::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", dir, e))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(name) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        if let Some((contract_name, _)) = registry
+            .contracts()
+            .iter()
+            .find(|(contract_name, _)| contract_name == name)
+        {
+            candidates
+                .entry(contract_name.as_str())
+                .or_default()
+                .push(entry.into_path());
+        }
+    }
+
+    let mut paths = BTreeMap::new();
+    for (name, mut found) in candidates {
+        let source_dir = format!("{}.sol", name);
+        let is_foundry_artifact = |path: &Path| {
+            path.parent()
+                .and_then(Path::file_name)
+                .is_some_and(|dir| *dir == *source_dir)
+        };
+        if found.iter().any(|path| is_foundry_artifact(path)) {
+            found.retain(|path| is_foundry_artifact(path));
+        }
+        if found.len() > 1 {
+            return Err(format!(
+                "{} has {} candidate files under {}: {}",
+                name,
+                found.len(),
+                dir,
+                found
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        paths.insert(name.to_string(), found.remove(0));
+    }
+    Ok(paths)
+}
+
+/// Hash the `deployedBytecode` of each registered contract's Foundry artifact under `artifacts_dir`
+///
+/// Artifacts are located with [`find_contract_json`]. Library placeholders are
+/// linked with `linker` before hashing. Contracts without an artifact are left
+/// out of the result.
+pub fn load_artifact_code_hashes(
+    artifacts_dir: &str,
+    linker: &LibraryLinker,
+    registry: &ContractRegistry,
+) -> Result<BTreeMap<String, B256>, String> {
+    let artifact_paths = find_contract_json(artifacts_dir, registry)?;
+
+    let mut code_hashes = BTreeMap::new();
+    for (contract_name, _) in registry.contracts() {
+        let Some(path) = artifact_paths.get(contract_name) else {
+            continue;
+        };
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let artifact: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        let object = artifact["deployedBytecode"]["object"]
+            .as_str()
+            .ok_or_else(|| format!("{} has no deployedBytecode.object", path.display()))?;
+        let linked = linker.link(contract_name, object.trim_start_matches("0x"))?;
+        let code = hex::decode(&linked)
+            .map_err(|e| format!("{}: invalid deployedBytecode hex: {}", path.display(), e))?;
+        code_hashes.insert(contract_name.clone(), keccak256(code));
+    }
+    Ok(code_hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::keccak256;
+
+    #[test]
+    fn test_unapproved_code_hash_is_rejected() {
+        let v1 = keccak256([0x60, 0x01]);
+        let v2 = keccak256([0x60, 0x02]);
+        let db: CodeHashDb = serde_json::from_value(serde_json::json!({
+            "Genesis": { "v1.0.0": v1, "v1.1.0": v2 }
+        }))
+        .unwrap();
+
+        assert_eq!(check_code_hash(&db, "Genesis", v2), Ok("v1.1.0"));
+
+        let unknown = keccak256([0x60, 0x03]);
+        let err = check_code_hash(&db, "Genesis", unknown).unwrap_err();
+        assert!(err.contains("is not approved"), "{}", err);
+        assert!(err.contains("v1.0.0, v1.1.0"), "{}", err);
+
+        let err = check_code_hash(&db, "Block", v1).unwrap_err();
+        assert!(err.contains("Block has no approved code hashes"), "{}", err);
+    }
+
+    #[test]
+    fn test_bytecode_manifest_mismatch_names_both_hashes() {
+        let expected = keccak256([0x60, 0x01]);
+        let loaded = keccak256([0x60, 0x02]);
+        let manifest = BytecodeManifest::from([("Genesis".to_string(), expected)]);
+
+        assert_eq!(
+            check_bytecode_manifest(&manifest, "Genesis", expected),
+            Ok(())
+        );
+        assert_eq!(check_bytecode_manifest(&manifest, "Block", loaded), Ok(()));
+        assert_eq!(
+            check_bytecode_manifest(&manifest, "Genesis", loaded),
+            Err(format!(
+                "Genesis bytecode hash {} does not match {} in the bytecode manifest",
+                loaded, expected
+            ))
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bytecode_manifest.json");
+        std::fs::write(
+            &path,
+            serde_json::json!({ "Genesis": expected, "Genesys": loaded }).to_string(),
+        )
+        .unwrap();
+        let err = read_bytecode_manifest(path.to_str().unwrap(), &ContractRegistry::default())
+            .unwrap_err();
+        assert_eq!(err, "bytecode manifest names unknown contracts: Genesys");
+    }
+
+    #[test]
+    fn test_artifact_code_hashes_use_deployed_bytecode() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("Genesis.sol")).unwrap();
+        std::fs::write(
+            dir.path().join("Genesis.sol/Genesis.json"),
+            serde_json::json!({
+                "bytecode": { "object": "0x6080" },
+                "deployedBytecode": { "object": "0x60016002" }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let hashes = load_artifact_code_hashes(
+            dir.path().to_str().unwrap(),
+            &LibraryLinker::default(),
+            &ContractRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            hashes.get("Genesis"),
+            Some(&keccak256([0x60, 0x01, 0x60, 0x02]))
+        );
+        assert!(!hashes.contains_key("ValidatorManager"));
+    }
+    #[test]
+    fn test_foundry_artifact_wins_and_other_duplicates_fail() {
+        let registry = ContractRegistry::default();
+        let dir = tempfile::tempdir().unwrap();
+        for path in [
+            "Genesis.json",
+            "Genesis.sol/Genesis.json",
+            "a/ValidatorManager.json",
+            "b/ValidatorManager.json",
+        ] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "{}").unwrap();
+        }
+
+        let err = find_contract_json(dir.path().to_str().unwrap(), &registry).unwrap_err();
+        assert!(
+            err.starts_with("ValidatorManager has 2 candidate files under"),
+            "{}",
+            err
+        );
+
+        std::fs::remove_dir_all(dir.path().join("b")).unwrap();
+        let paths = find_contract_json(dir.path().to_str().unwrap(), &registry).unwrap();
+        assert_eq!(
+            paths["Genesis"],
+            dir.path().join("Genesis.sol/Genesis.json")
+        );
+        assert_eq!(
+            paths["ValidatorManager"],
+            dir.path().join("a/ValidatorManager.json")
+        );
+        assert_eq!(paths.len(), 2);
+    }
+}
@@ -0,0 +1,98 @@
+use std::{collections::HashMap, fs::File, io::BufWriter, path::Path};
+
+use revm::db::PlainAccount;
+use revm_primitives::{Address, B256, Bytes};
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::GenesisManifest;
+
+/// Provenance of a combined genesis document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CombinedMetadata {
+    pub chain_id: u64,
+    pub spec_id: String,
+    /// Version of the tool that generated the genesis
+    pub tool_version: String,
+    /// Genesis block timestamp in unix seconds
+    pub timestamp: u64,
+    /// [`config_hash`](crate::manifest::config_hash) of the generation inputs
+    pub config_hash: B256,
+}
+
+impl From<&GenesisManifest> for CombinedMetadata {
+    fn from(manifest: &GenesisManifest) -> Self {
+        Self {
+            chain_id: manifest.chain_id,
+            spec_id: manifest.spec_id.clone(),
+            tool_version: manifest.tool_version.clone(),
+            timestamp: manifest.timestamp,
+            config_hash: manifest.config_hash,
+        }
+    }
+}
+
+/// `genesis_accounts.json`, `genesis_contracts.json` and their metadata in one document
+#[derive(Debug, Serialize)]
+pub struct CombinedGenesis<'a> {
+    pub accounts: &'a HashMap<Address, PlainAccount>,
+    pub contracts: &'a HashMap<Address, &'a Bytes>,
+    pub metadata: CombinedMetadata,
+}
+
+impl CombinedGenesis<'_> {
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execute::{BlockContext, prepare_env};
+    use revm::primitives::{AccountInfo, Bytecode, SpecId};
+    use revm_primitives::address;
+
+    #[test]
+    fn test_combined_genesis_has_accounts_contracts_and_metadata() {
+        let contract = address!("00000000000000000000000000000000000000f0");
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let accounts = HashMap::from([(
+            contract,
+            PlainAccount {
+                info: AccountInfo {
+                    code: Some(Bytecode::new_raw(code.clone())),
+                    ..AccountInfo::default()
+                },
+                storage: Default::default(),
+            },
+        )]);
+        let contracts = HashMap::from([(contract, &code)]);
+        let block = BlockContext {
+            chain_id: 1337,
+            timestamp: 1_735_689_600,
+            ..BlockContext::default()
+        };
+        let mut manifest = GenesisManifest::from_env(&prepare_env(&block), SpecId::LATEST);
+        manifest.config_hash = B256::with_last_byte(1);
+        let combined = CombinedGenesis {
+            accounts: &accounts,
+            contracts: &contracts,
+            metadata: CombinedMetadata::from(&manifest),
+        };
+
+        let json = serde_json::to_value(&combined).unwrap();
+        let key = contract.to_string().to_lowercase();
+        assert!(json["accounts"][&key]["info"].is_object(), "{}", json);
+        assert_eq!(json["contracts"][&key], "0x6000");
+        assert_eq!(json["metadata"]["chain_id"], 1337);
+        assert_eq!(json["metadata"]["timestamp"], 1_735_689_600);
+        assert_eq!(json["metadata"]["tool_version"], env!("CARGO_PKG_VERSION"));
+        let metadata: CombinedMetadata = serde_json::from_value(json["metadata"].clone()).unwrap();
+        assert_eq!(metadata, combined.metadata);
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+/// Serialization format of an input file, chosen by its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// `.json`, `.toml`, `.yaml` or `.yml`; files without an extension are read as JSON
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            None => Ok(Self::Json),
+            Some(ext) => match ext.to_ascii_lowercase().as_str() {
+                "json" => Ok(Self::Json),
+                "toml" => Ok(Self::Toml),
+                "yaml" | "yml" => Ok(Self::Yaml),
+                _ => Err(format!(
+                    "Unsupported file extension .{} for {} (expected .json, .toml, .yaml or .yml)",
+                    ext, path
+                )),
+            },
+        }
+    }
+
+    pub fn parse<T: DeserializeOwned>(self, content: &str) -> Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            Self::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Deserialize `content` read from `path` in the format its extension names
+pub fn parse_config_file<T: DeserializeOwned>(path: &str, content: &str) -> Result<T, String> {
+    ConfigFormat::from_path(path)?.parse(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genesis::GenesisConfig;
+
+    const JSON: &str = r#"{
+        "validatorAddresses": ["0x6e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"],
+        "consensusPublicKeys": ["851d41932d866f5fabed6673898e15473e6a0adcf5033d2c93816c6b115c85ad3451e0bac61d570d5ed9f23e1e7f77c4"],
+        "votingPowers": ["2000"],
+        "validatorNetworkAddresses": ["/ip4/127.0.0.1/tcp/2024"],
+        "fullnodeNetworkAddresses": ["/ip4/127.0.0.1/tcp/2026"],
+        "aptosAddresses": ["2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"],
+        "prefundedAccounts": [{ "address": "0x000000000000000000000000000000000000beef", "balance": "1000" }]
+    }"#;
+
+    const TOML: &str = r#"
+        validatorAddresses = ["0x6e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"]
+        consensusPublicKeys = ["851d41932d866f5fabed6673898e15473e6a0adcf5033d2c93816c6b115c85ad3451e0bac61d570d5ed9f23e1e7f77c4"]
+        votingPowers = ["2000"]
+        validatorNetworkAddresses = ["/ip4/127.0.0.1/tcp/2024"]
+        fullnodeNetworkAddresses = ["/ip4/127.0.0.1/tcp/2026"]
+        aptosAddresses = ["2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"]
+
+        [[prefundedAccounts]]
+        address = "0x000000000000000000000000000000000000beef"
+        balance = "1000"
+    "#;
+
+    const YAML: &str = r#"
+validatorAddresses: ["0x6e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"]
+consensusPublicKeys:
+  - "851d41932d866f5fabed6673898e15473e6a0adcf5033d2c93816c6b115c85ad3451e0bac61d570d5ed9f23e1e7f77c4"
+votingPowers: ["2000"]
+validatorNetworkAddresses: ["/ip4/127.0.0.1/tcp/2024"]
+fullnodeNetworkAddresses: ["/ip4/127.0.0.1/tcp/2026"]
+aptosAddresses: ["2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"]
+prefundedAccounts:
+  - address: "0x000000000000000000000000000000000000beef"
+    balance: "1000"
+"#;
+
+    #[test]
+    fn test_every_format_parses_to_the_same_config() {
+        let json: GenesisConfig = parse_config_file("genesis_config.json", JSON).unwrap();
+        let toml: GenesisConfig = parse_config_file("genesis_config.toml", TOML).unwrap();
+        let yaml: GenesisConfig = parse_config_file("genesis_config.yaml", YAML).unwrap();
+        let yml: GenesisConfig = parse_config_file("genesis_config.yml", YAML).unwrap();
+        let extensionless: GenesisConfig = parse_config_file("genesis_config", JSON).unwrap();
+
+        assert_eq!(json, toml);
+        assert_eq!(json, yaml);
+        assert_eq!(json, yml);
+        assert_eq!(json, extensionless);
+        assert_eq!(json.prefunded_accounts.len(), 1);
+        assert_eq!(json.max_total_voting_power_bits, 64);
+    }
+
+    #[test]
+    fn test_unknown_extension_is_rejected() {
+        let err = ConfigFormat::from_path("genesis_config.ini").unwrap_err();
+        assert!(err.contains("Unsupported file extension .ini"), "{}", err);
+        assert_eq!(
+            ConfigFormat::from_path("dir.d/genesis.YML"),
+            Ok(ConfigFormat::Yaml)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+
+use crate::genesis::{
+    DEFAULT_DECIMALS, DEFAULT_MAX_TOTAL_VOTING_POWER_BITS, GenesisConfig, VotingPowerUnit,
+};
+
+/// File the template config is written to by `init-config`
+pub const TEMPLATE_CONFIG_FILE: &str = "genesis_config.json";
+
+/// Sibling file explaining each field of the template config
+pub const TEMPLATE_README_FILE: &str = "genesis_config.README.md";
+
+const PLACEHOLDER_NETWORK_ADDRESS: &str = "/ip4/127.0.0.1/tcp/2024/noise-ik/2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f/handshake/0";
+
+/// Each top-level key of a genesis config and how its values are written
+///
+/// JSON has no comments, so these are written next to the template instead.
+pub const CONFIG_FIELD_DOCS: [(&str, &str); 14] = [
+    (
+        "validatorAddresses",
+        "Operator address of each validator, 0x-prefixed hex. The six validator arrays are parallel: entry i of each describes validator i.",
+    ),
+    (
+        "consensusPublicKeys",
+        "BLS12-381 consensus public key of each validator, 48 bytes as hex without 0x.",
+    ),
+    (
+        "votingPowers",
+        "Voting power of each validator as a decimal string, in whole tokens (see votingPowerUnit).",
+    ),
+    (
+        "validatorNetworkAddresses",
+        "Validator network address of each validator, `/ip4/<ip>/tcp/<port>/noise-ik/<32-byte x25519 key>/handshake/0`.",
+    ),
+    (
+        "fullnodeNetworkAddresses",
+        "Fullnode network address of each validator, in the same format; may be an empty string.",
+    ),
+    (
+        "aptosAddresses",
+        "Aptos account address of each validator, 32 bytes as hex without 0x.",
+    ),
+    (
+        "maxTotalVotingPowerBits",
+        "Bit width the total voting power, in whole tokens, must fit in. Defaults to 64, the width DKG narrows voting power to.",
+    ),
+    (
+        "votingPowerUnit",
+        "Optional. `ether` (the default) multiplies votingPowers by 10^decimals; `wei` passes them through unscaled.",
+    ),
+    (
+        "decimals",
+        "Optional decimals of the native token, from 0 to 36. Defaults to 18; left out of the template.",
+    ),
+    (
+        "rawConsensusKeys",
+        "Pass consensusPublicKeys to the contracts as raw UTF-8 bytes instead of hex-decoding them. Defaults to false.",
+    ),
+    (
+        "prefundedAccounts",
+        "Accounts funded at genesis: objects with an `address` and a `balance` in wei, as a decimal or 0x-prefixed hex string.",
+    ),
+    (
+        "monikers",
+        "Optional unique validator names, parallel to validatorAddresses: 3 to 9 letters or digits starting with an uppercase letter. Leave empty for the default `VAL<index>`.",
+    ),
+    (
+        "commissions",
+        "Optional commissions, parallel to validatorAddresses: objects with `rate`, `maxRate` and `maxChangeRate` in basis points, where rate <= maxRate <= 5000 and maxChangeRate <= maxRate. Leave empty for the contract defaults.",
+    ),
+    (
+        "randomnessConfig",
+        "Optional randomness thresholds replacing the Genesis.initialize defaults. Left out of the template.",
+    ),
+];
+
+/// Config with one placeholder validator, every array keyed and the other settings at their defaults
+///
+/// It passes validation as is, so operators can generate a genesis from it
+/// before replacing the placeholder with their own validators.
+pub fn template_config() -> GenesisConfig {
+    GenesisConfig {
+        validator_addresses: vec!["0x6e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f".to_string()],
+        consensus_public_keys: vec![
+            "851d41932d866f5fabed6673898e15473e6a0adcf5033d2c93816c6b115c85ad3451e0bac61d570d5ed9f23e1e7f77c4".to_string(),
+        ],
+        voting_powers: vec!["20000".to_string()],
+        validator_network_addresses: vec![PLACEHOLDER_NETWORK_ADDRESS.to_string()],
+        fullnode_network_addresses: vec![PLACEHOLDER_NETWORK_ADDRESS.to_string()],
+        aptos_addresses: vec![
+            "2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f".to_string(),
+        ],
+        max_total_voting_power_bits: DEFAULT_MAX_TOTAL_VOTING_POWER_BITS,
+        voting_power_unit: VotingPowerUnit::default(),
+        decimals: DEFAULT_DECIMALS,
+        raw_consensus_keys: false,
+        prefunded_accounts: Vec::new(),
+        monikers: Vec::new(),
+        commissions: Vec::new(),
+        randomness_config: None,
+    }
+}
+
+/// Markdown explaining every field of [`template_config`]
+pub fn template_readme() -> String {
+    let mut readme = format!(
+        "# {}\n\nTemplate genesis config with one placeholder validator. Replace it with your validators, keeping the six validator arrays the same length.\n\n| Field | Format |\n|-------|--------|\n",
+        TEMPLATE_CONFIG_FILE
+    );
+    for (field, format) in CONFIG_FIELD_DOCS {
+        readme.push_str(&format!("| `{}` | {} |\n", field, format));
+    }
+    readme
+}
+
+/// Write the template config and its README into `output_dir`
+///
+/// Fails rather than overwrite an existing file unless `force` is set.
+pub fn write_config_template(output_dir: &Path, force: bool) -> Result<(PathBuf, PathBuf), String> {
+    let config_path = output_dir.join(TEMPLATE_CONFIG_FILE);
+    let readme_path = output_dir.join(TEMPLATE_README_FILE);
+    if let Some(existing) = [&config_path, &readme_path]
+        .into_iter()
+        .find(|path| !force && path.exists())
+    {
+        return Err(format!(
+            "{} already exists; pass --force to overwrite it",
+            existing.display()
+        ));
+    }
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create {}: {}", output_dir.display(), e))?;
+    let config = serde_json::to_string_pretty(&template_config())
+        .map_err(|e| format!("Failed to serialize the template config: {}", e))?;
+    std::fs::write(&config_path, config + "\n")
+        .map_err(|e| format!("Failed to write {}: {}", config_path.display(), e))?;
+    std::fs::write(&readme_path, template_readme())
+        .map_err(|e| format!("Failed to write {}: {}", readme_path.display(), e))?;
+    Ok((config_path, readme_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_validation::validate_config;
+
+    #[test]
+    fn test_template_config_is_valid_and_documented() {
+        let dir = tempfile::tempdir().unwrap();
+        let (config_path, readme_path) = write_config_template(dir.path(), false).unwrap();
+
+        let content = std::fs::read_to_string(&config_path).unwrap();
+        let config: GenesisConfig = serde_json::from_str(&content).unwrap();
+        assert_eq!(config, template_config());
+        assert!(validate_config(&config).is_ok());
+
+        let keys: Vec<String> =
+            serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&content)
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect();
+        let readme = std::fs::read_to_string(&readme_path).unwrap();
+        for key in &keys {
+            assert!(
+                CONFIG_FIELD_DOCS.iter().any(|(field, _)| field == key),
+                "{} is not documented",
+                key
+            );
+            assert!(readme.contains(&format!("`{}`", key)), "{}", key);
+        }
+
+        let err = write_config_template(dir.path(), false).unwrap_err();
+        assert!(
+            err.ends_with("already exists; pass --force to overwrite it"),
+            "{}",
+            err
+        );
+        assert!(write_config_template(dir.path(), true).is_ok());
+    }
+}
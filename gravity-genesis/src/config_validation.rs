@@ -0,0 +1,800 @@
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fmt,
+    hash::Hash,
+};
+
+use revm_primitives::{Address, B256, Bytes, U256, hex};
+
+use crate::{
+    genesis::{
+        CommissionConfig, GenesisConfig, GenesisInitParam, MAX_COMMISSION_RATE, MAX_DECIMALS,
+        parse_consensus_public_key,
+    },
+    network_address::parse_network_address,
+    randomness::RandomnessConfig,
+};
+
+/// One structural problem in a genesis config
+///
+/// Fields are named as in the config file and indices are 0-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// A per-validator list does not have one entry per validator
+    LengthMismatch {
+        field: &'static str,
+        len: usize,
+        validators: usize,
+    },
+    /// An entry does not parse
+    InvalidValue {
+        field: &'static str,
+        index: usize,
+        reason: String,
+    },
+    /// An entry repeats an earlier one of the same list
+    Duplicate {
+        field: &'static str,
+        index: usize,
+        first: usize,
+    },
+    /// `decimals` is above [`MAX_DECIMALS`]
+    InvalidDecimals(u32),
+    /// A voting power is zero or does not fit in a `uint256` once converted to wei
+    InvalidVotingPower { index: usize, reason: &'static str },
+    /// A validator's operator address is not the one its aptos address encodes
+    AptosAddressMismatch {
+        index: usize,
+        operator: Address,
+        aptos: Address,
+    },
+    /// A commission breaks `rate <= maxRate <= 5000` or `maxChangeRate <= maxRate`
+    InvalidCommission {
+        index: usize,
+        commission: CommissionConfig,
+    },
+    /// The randomness config does not parse or would be rejected by `RandomnessConfig`
+    InvalidRandomnessConfig(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch {
+                field,
+                len,
+                validators,
+            } => write!(
+                f,
+                "{} has {} entries but there are {} validators",
+                field, len, validators
+            ),
+            Self::InvalidValue {
+                field,
+                index,
+                reason,
+            } => write!(f, "{}[{}] {}", field, index, reason),
+            Self::Duplicate {
+                field,
+                index,
+                first,
+            } => write!(f, "{}[{}] duplicates {}[{}]", field, index, field, first),
+            Self::InvalidDecimals(decimals) => write!(
+                f,
+                "decimals is {}, expected 0 to {}",
+                decimals, MAX_DECIMALS
+            ),
+            Self::InvalidVotingPower { index, reason } => {
+                write!(f, "votingPowers[{}] {}", index, reason)
+            }
+            Self::AptosAddressMismatch {
+                index,
+                operator,
+                aptos,
+            } => write!(
+                f,
+                "validator {} operator address {} does not match its aptos address, which encodes {}",
+                index, operator, aptos
+            ),
+            Self::InvalidCommission { index, commission } => write!(
+                f,
+                "commissions[{}]: expected rate <= maxRate <= {} and maxChangeRate <= maxRate, got {}",
+                index, MAX_COMMISSION_RATE, commission
+            ),
+            Self::InvalidRandomnessConfig(reason) => write!(f, "randomnessConfig: {}", reason),
+        }
+    }
+}
+
+/// Render `errors` as one message, one problem per line
+pub fn format_config_errors(errors: &[ConfigError]) -> String {
+    format!(
+        "genesis config has {} problems:\n  {}",
+        errors.len(),
+        errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n  ")
+    )
+}
+
+/// Whether `moniker` passes `ValidatorManagerUtils.validateMoniker`: 3 to 9
+/// ASCII letters or digits, the first an uppercase letter
+fn is_valid_moniker(moniker: &str) -> bool {
+    (3..=9).contains(&moniker.len())
+        && moniker.starts_with(|c: char| c.is_ascii_uppercase())
+        && moniker.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Record a [`ConfigError::Duplicate`] for each value seen before in `values`
+///
+/// Values are compared as parsed, so spellings of the same address or key
+/// match. Entries that did not parse (`None`) are skipped, having been
+/// reported already.
+fn check_duplicates<T: Eq + Hash>(
+    field: &'static str,
+    values: impl IntoIterator<Item = Option<T>>,
+    errors: &mut Vec<ConfigError>,
+) {
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    for (index, value) in values.into_iter().enumerate() {
+        let Some(value) = value else {
+            continue;
+        };
+        match seen.entry(value) {
+            Entry::Occupied(first) => errors.push(ConfigError::Duplicate {
+                field,
+                index,
+                first: *first.get(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+    }
+}
+
+/// A genesis config converted to the values genesis is built from
+pub struct ParsedConfig {
+    /// Arguments of `Genesis.initialize`
+    pub init_param: GenesisInitParam,
+    /// Address and balance in wei of each prefunded account
+    pub prefunded_accounts: Vec<(Address, U256)>,
+}
+
+/// Run every structural check on `config` and return all the problems found
+///
+/// Nothing is executed, so this is cheap to run before generation. Errors are
+/// grouped by check, in config field order.
+pub fn validate_config(config: &GenesisConfig) -> Result<(), Vec<ConfigError>> {
+    parse_config(config).map(|_| ())
+}
+
+/// Convert `config` into the values genesis is built from, checking it on the way
+///
+/// This is the one place a config is checked: a config it accepts converts
+/// without error, and otherwise every problem found is returned, as by
+/// [`validate_config`].
+pub fn parse_config(config: &GenesisConfig) -> Result<ParsedConfig, Vec<ConfigError>> {
+    let mut errors = Vec::new();
+    let validators = config.validator_addresses.len();
+
+    for (field, len, optional) in [
+        (
+            "consensusPublicKeys",
+            config.consensus_public_keys.len(),
+            false,
+        ),
+        ("votingPowers", config.voting_powers.len(), false),
+        (
+            "validatorNetworkAddresses",
+            config.validator_network_addresses.len(),
+            false,
+        ),
+        (
+            "fullnodeNetworkAddresses",
+            config.fullnode_network_addresses.len(),
+            false,
+        ),
+        ("aptosAddresses", config.aptos_addresses.len(), false),
+        ("monikers", config.monikers.len(), true),
+        ("commissions", config.commissions.len(), true),
+    ] {
+        if len != validators && !(optional && len == 0) {
+            errors.push(ConfigError::LengthMismatch {
+                field,
+                len,
+                validators,
+            });
+        }
+    }
+
+    let mut operators: Vec<Option<Address>> = Vec::with_capacity(validators);
+    for (index, address) in config.validator_addresses.iter().enumerate() {
+        match address.parse::<Address>() {
+            Ok(operator) => operators.push(Some(operator)),
+            Err(e) => {
+                operators.push(None);
+                errors.push(ConfigError::InvalidValue {
+                    field: "validatorAddresses",
+                    index,
+                    reason: format!("{:?} is not an address: {}", address, e),
+                });
+            }
+        }
+    }
+
+    // GAptos would parse these with bls12381::PublicKey::try_from
+    let mut consensus_public_keys: Vec<Option<Bytes>> =
+        Vec::with_capacity(config.consensus_public_keys.len());
+    for (index, key) in config.consensus_public_keys.iter().enumerate() {
+        match parse_consensus_public_key(key, config.raw_consensus_keys) {
+            Ok(key) => consensus_public_keys.push(Some(key)),
+            Err(reason) => {
+                consensus_public_keys.push(None);
+                errors.push(ConfigError::InvalidValue {
+                    field: "consensusPublicKeys",
+                    index,
+                    reason,
+                });
+            }
+        }
+    }
+
+    if config.decimals > MAX_DECIMALS {
+        errors.push(ConfigError::InvalidDecimals(config.decimals));
+    }
+    let mut voting_powers: Vec<Option<U256>> = Vec::with_capacity(config.voting_powers.len());
+    for (index, power) in config.voting_powers.iter().enumerate() {
+        let wei = match power.parse::<U256>() {
+            Err(e) => {
+                errors.push(ConfigError::InvalidValue {
+                    field: "votingPowers",
+                    index,
+                    reason: format!("{:?} is not a number: {}", power, e),
+                });
+                None
+            }
+            Ok(power) if power.is_zero() => {
+                errors.push(ConfigError::InvalidVotingPower {
+                    index,
+                    reason: "is zero",
+                });
+                None
+            }
+            Ok(_) if config.decimals > MAX_DECIMALS => None,
+            Ok(power) => {
+                let wei = config.voting_power_unit.to_wei(power, config.decimals);
+                if wei.is_none() {
+                    errors.push(ConfigError::InvalidVotingPower {
+                        index,
+                        reason: "overflows a uint256 when converted to wei",
+                    });
+                }
+                wei
+            }
+        };
+        voting_powers.push(wei);
+    }
+
+    // GAptos decodes these with bcs::from_bytes and NetworkAddress::from_str
+    let mut network_addresses: [Vec<Option<Bytes>>; 2] = Default::default();
+    for ((field, addresses), encoded) in [
+        (
+            "validatorNetworkAddresses",
+            &config.validator_network_addresses,
+        ),
+        (
+            "fullnodeNetworkAddresses",
+            &config.fullnode_network_addresses,
+        ),
+    ]
+    .into_iter()
+    .zip(&mut network_addresses)
+    {
+        for (index, address) in addresses.iter().enumerate() {
+            match encode_network_address(address) {
+                Ok(bytes) => encoded.push(Some(bytes)),
+                Err(reason) => {
+                    encoded.push(None);
+                    errors.push(ConfigError::InvalidValue {
+                        field,
+                        index,
+                        reason: format!("{:?} {}", address, reason),
+                    });
+                }
+            }
+        }
+    }
+    let [validator_network_addresses, fullnode_network_addresses] = network_addresses;
+
+    let mut aptos_addresses: Vec<Option<Bytes>> = Vec::with_capacity(config.aptos_addresses.len());
+    for (index, aptos_address) in config.aptos_addresses.iter().enumerate() {
+        let bytes = match hex::decode(aptos_address) {
+            Ok(bytes) if bytes.len() == 32 => bytes,
+            Ok(bytes) => {
+                aptos_addresses.push(None);
+                errors.push(ConfigError::InvalidValue {
+                    field: "aptosAddresses",
+                    index,
+                    reason: format!("is {} bytes, expected 32", bytes.len()),
+                });
+                continue;
+            }
+            Err(e) => {
+                aptos_addresses.push(None);
+                errors.push(ConfigError::InvalidValue {
+                    field: "aptosAddresses",
+                    index,
+                    reason: format!("is not hex: {}", e),
+                });
+                continue;
+            }
+        };
+        let aptos = Address::from_word(B256::from_slice(&bytes));
+        match operators.get(index) {
+            Some(Some(operator)) if *operator != aptos => {
+                errors.push(ConfigError::AptosAddressMismatch {
+                    index,
+                    operator: *operator,
+                    aptos,
+                })
+            }
+            _ => {}
+        }
+        aptos_addresses.push(Some(bytes.into()));
+    }
+
+    for (index, moniker) in config.monikers.iter().enumerate() {
+        if !is_valid_moniker(moniker) {
+            errors.push(ConfigError::InvalidValue {
+                field: "monikers",
+                index,
+                reason: format!(
+                    "{:?} is not 3 to 9 letters or digits starting with an uppercase letter",
+                    moniker
+                ),
+            });
+        }
+    }
+
+    // The same rules as `ValidatorManagerUtils.validateRegistrationParams`
+    for (index, commission) in config.commissions.iter().enumerate() {
+        if commission.max_rate > MAX_COMMISSION_RATE
+            || commission.rate > commission.max_rate
+            || commission.max_change_rate > commission.max_rate
+        {
+            errors.push(ConfigError::InvalidCommission {
+                index,
+                commission: *commission,
+            });
+        }
+    }
+
+    check_duplicates("validatorAddresses", operators.iter().copied(), &mut errors);
+    check_duplicates(
+        "aptosAddresses",
+        aptos_addresses.iter().map(Option::as_ref),
+        &mut errors,
+    );
+    // Two validators sharing a consensus key would break consensus safety
+    check_duplicates(
+        "consensusPublicKeys",
+        consensus_public_keys.iter().map(Option::as_ref),
+        &mut errors,
+    );
+
+    // `ValidatorManager` keeps monikers unique, so registering a taken one fails later
+    check_duplicates("monikers", config.monikers.iter().map(Some), &mut errors);
+
+    let mut prefunded_accounts = Vec::with_capacity(config.prefunded_accounts.len());
+    let mut prefunded_addresses = Vec::with_capacity(config.prefunded_accounts.len());
+    for (index, account) in config.prefunded_accounts.iter().enumerate() {
+        let address = account
+            .address
+            .parse::<Address>()
+            .map_err(|e| format!("invalid address {:?}: {}", account.address, e));
+        let balance = account
+            .balance
+            .parse::<U256>()
+            .map_err(|e| format!("invalid balance {:?}: {}", account.balance, e));
+        prefunded_addresses.push(address.as_ref().ok().copied());
+        match (address, balance) {
+            (Ok(address), Ok(balance)) => prefunded_accounts.push((address, balance)),
+            (address, balance) => {
+                for reason in [address.err(), balance.err()].into_iter().flatten() {
+                    errors.push(ConfigError::InvalidValue {
+                        field: "prefundedAccounts",
+                        index,
+                        reason,
+                    });
+                }
+            }
+        }
+    }
+    check_duplicates("prefundedAccounts", prefunded_addresses, &mut errors);
+
+    if let Some(randomness_config) = &config.randomness_config {
+        if let Err(reason) = randomness_config.to_data() {
+            errors.push(ConfigError::InvalidRandomnessConfig(reason));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    // Without errors every entry parsed, so flattening drops nothing
+    Ok(ParsedConfig {
+        init_param: GenesisInitParam {
+            validator_addresses: operators.into_iter().flatten().collect(),
+            consensus_public_keys: consensus_public_keys.into_iter().flatten().collect(),
+            voting_powers: voting_powers.into_iter().flatten().collect(),
+            validator_network_addresses: validator_network_addresses
+                .into_iter()
+                .flatten()
+                .collect(),
+            fullnode_network_addresses: fullnode_network_addresses.into_iter().flatten().collect(),
+            aptos_addresses: aptos_addresses.into_iter().flatten().collect(),
+            monikers: config.monikers.clone(),
+            commissions: config.commissions.clone(),
+        },
+        prefunded_accounts,
+    })
+}
+
+/// Check a network address against the Aptos `NetworkAddress` format and BCS-encode it
+///
+/// The empty string stands for "no address" and maps to empty bytes.
+fn encode_network_address(address: &str) -> Result<Bytes, String> {
+    if address.is_empty() {
+        return Ok(Bytes::new());
+    }
+    parse_network_address(address)?;
+    bcs::to_bytes(address)
+        .map(Bytes::from)
+        .map_err(|e| format!("cannot be BCS-encoded: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        genesis::{PrefundedAccount, VotingPowerUnit, decode_network_address},
+        randomness::RandomnessVariant,
+    };
+
+    const KEY: &str = "2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f";
+
+    fn valid_config() -> GenesisConfig {
+        serde_json::from_value(serde_json::json!({
+            "validatorAddresses": [
+                "0x1111111111111111111111111111111111111111",
+                "0x2222222222222222222222222222222222222222"
+            ],
+            "consensusPublicKeys": ["aa".repeat(48), "bb".repeat(48)],
+            "votingPowers": ["1", "2"],
+            "validatorNetworkAddresses": [format!("/ip4/127.0.0.1/tcp/2024/noise-ik/{KEY}/handshake/0"), ""],
+            "fullnodeNetworkAddresses": ["", ""],
+            "aptosAddresses": [
+                "0000000000000000000000001111111111111111111111111111111111111111",
+                "0000000000000000000000002222222222222222222222222222222222222222"
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_config_passes() {
+        assert_eq!(validate_config(&valid_config()), Ok(()));
+
+        // Wei are not scaled, so the largest uint256 does not overflow
+        let mut config = valid_config();
+        config.voting_power_unit = VotingPowerUnit::Wei;
+        config.voting_powers[0] = U256::MAX.to_string();
+        assert_eq!(validate_config(&config), Ok(()));
+    }
+
+    #[test]
+    fn test_decimals_are_bounded() {
+        let mut config = valid_config();
+        config.decimals = 0;
+        assert_eq!(validate_config(&config), Ok(()));
+        config.decimals = 36;
+        assert_eq!(validate_config(&config), Ok(()));
+
+        config.decimals = 37;
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors, vec![ConfigError::InvalidDecimals(37)]);
+        assert_eq!(errors[0].to_string(), "decimals is 37, expected 0 to 36");
+    }
+
+    #[test]
+    fn test_every_problem_is_reported() {
+        let mut config = valid_config();
+        config.validator_addresses[1] = "0x11".to_string();
+        config.consensus_public_keys[1] = "aa".repeat(48);
+        config.voting_powers = vec!["0".to_string(), U256::MAX.to_string()];
+        config.validator_network_addresses[1] = "/ip4/127.0.0.1/tcp/2024".to_string();
+        config.fullnode_network_addresses.pop();
+        config.aptos_addresses = vec![
+            "0000000000000000000000003333333333333333333333333333333333333333".to_string(),
+            "00".to_string(),
+        ];
+        config.monikers = vec!["Solo".to_string()];
+        config.commissions = vec![
+            CommissionConfig {
+                rate: 10,
+                max_rate: 5,
+                max_change_rate: 1,
+            };
+            2
+        ];
+        config.prefunded_accounts = vec![
+            PrefundedAccount {
+                address: "0x4444444444444444444444444444444444444444".to_string(),
+                balance: "ten".to_string(),
+            },
+            PrefundedAccount {
+                address: "0x4444444444444444444444444444444444444444".to_string(),
+                balance: "1".to_string(),
+            },
+        ];
+        config.randomness_config = Some(RandomnessConfig {
+            variant: RandomnessVariant::V1,
+            secrecy_threshold: "2".to_string(),
+            reconstruction_threshold: "1".to_string(),
+            fast_path_secrecy_threshold: None,
+        });
+
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(
+            errors[..2],
+            [
+                ConfigError::LengthMismatch {
+                    field: "fullnodeNetworkAddresses",
+                    len: 1,
+                    validators: 2,
+                },
+                ConfigError::LengthMismatch {
+                    field: "monikers",
+                    len: 1,
+                    validators: 2,
+                },
+            ]
+        );
+        assert!(errors.contains(&ConfigError::AptosAddressMismatch {
+            index: 0,
+            operator: Address::repeat_byte(0x11),
+            aptos: Address::repeat_byte(0x33),
+        }));
+        assert!(errors.contains(&ConfigError::Duplicate {
+            field: "consensusPublicKeys",
+            index: 1,
+            first: 0,
+        }));
+
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        let expected = [
+            "fullnodeNetworkAddresses has 1 entries but there are 2 validators",
+            "monikers has 1 entries but there are 2 validators",
+            "validatorAddresses[1] \"0x11\" is not an address",
+            "votingPowers[0] is zero",
+            "votingPowers[1] overflows a uint256 when converted to wei",
+            "validatorNetworkAddresses[1] \"/ip4/127.0.0.1/tcp/2024\" is missing",
+            "validator 0 operator address 0x1111111111111111111111111111111111111111 does not match",
+            "aptosAddresses[1] is 1 bytes, expected 32",
+            "commissions[0]: expected rate <= maxRate <= 5000 and maxChangeRate <= maxRate, got rate 10 maxRate 5",
+            "commissions[1]: expected rate <= maxRate <= 5000 and maxChangeRate <= maxRate",
+            "consensusPublicKeys[1] duplicates consensusPublicKeys[0]",
+            "prefundedAccounts[0] invalid balance \"ten\"",
+            "prefundedAccounts[1] duplicates prefundedAccounts[0]",
+            "randomnessConfig: reconstructionThreshold 1 must be above secrecyThreshold 2",
+        ];
+        assert_eq!(messages.len(), expected.len(), "{:#?}", messages);
+        for (message, prefix) in messages.iter().zip(expected) {
+            assert!(
+                message.starts_with(prefix),
+                "{:?} should start with {:?}",
+                message,
+                prefix
+            );
+        }
+    }
+
+    #[test]
+    fn test_operator_address_mismatches_are_all_reported() {
+        let mut config = valid_config();
+        config.aptos_addresses.swap(0, 1);
+        assert_eq!(
+            validate_config(&config),
+            Err(vec![
+                ConfigError::AptosAddressMismatch {
+                    index: 0,
+                    operator: Address::repeat_byte(0x11),
+                    aptos: Address::repeat_byte(0x22),
+                },
+                ConfigError::AptosAddressMismatch {
+                    index: 1,
+                    operator: Address::repeat_byte(0x22),
+                    aptos: Address::repeat_byte(0x11),
+                },
+            ])
+        );
+
+        config.aptos_addresses.truncate(1);
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(
+            errors[0],
+            ConfigError::LengthMismatch {
+                field: "aptosAddresses",
+                len: 1,
+                validators: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_consensus_public_key_validation() {
+        let key_error = |config: &GenesisConfig| {
+            validate_config(config)
+                .unwrap_err()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        let mut config = valid_config();
+        config.consensus_public_keys = vec![format!("0x{}", "aa".repeat(48)), "bb".repeat(96)];
+        let parsed = parse_config(&config).unwrap().init_param;
+        assert_eq!(parsed.consensus_public_keys[0].len(), 48);
+        assert_eq!(parsed.consensus_public_keys[1].len(), 96);
+
+        config.consensus_public_keys = vec!["aa".repeat(48), "cc".repeat(32)];
+        assert_eq!(
+            key_error(&config),
+            ["consensusPublicKeys[1] is 32 bytes, expected 48 or 96"]
+        );
+        config.consensus_public_keys[0] = "zz".to_string();
+        assert!(
+            key_error(&config)[0].starts_with("consensusPublicKeys[0] is not hex"),
+            "{:?}",
+            key_error(&config)
+        );
+
+        // Raw keys keep the UTF-8 bytes of the string
+        config.raw_consensus_keys = true;
+        let parsed = parse_config(&config).unwrap().init_param;
+        assert_eq!(
+            parsed.consensus_public_keys[1].as_ref(),
+            "cc".repeat(32).as_bytes()
+        );
+
+        for raw in [false, true] {
+            config.raw_consensus_keys = raw;
+            config.consensus_public_keys = vec!["aa".repeat(48), String::new()];
+            assert_eq!(key_error(&config), ["consensusPublicKeys[1] is empty"]);
+        }
+    }
+
+    #[test]
+    fn test_network_addresses_are_bcs_encoded() {
+        let mut config = valid_config();
+        let parsed = parse_config(&config).unwrap().init_param;
+        assert_eq!(
+            parsed.validator_network_addresses[0],
+            Bytes::from(bcs::to_bytes(&config.validator_network_addresses[0]).unwrap())
+        );
+        assert!(parsed.validator_network_addresses[1].is_empty());
+        for (address, bytes) in config
+            .validator_network_addresses
+            .iter()
+            .zip(&parsed.validator_network_addresses)
+        {
+            assert_eq!(decode_network_address(bytes).as_ref(), Ok(address));
+        }
+
+        config.fullnode_network_addresses[1] = "/ip4/127.0.0.1/tcp/2024".to_string();
+        assert_eq!(
+            validate_config(&config).unwrap_err()[0].to_string(),
+            "fullnodeNetworkAddresses[1] \"/ip4/127.0.0.1/tcp/2024\" is missing the /noise-ik public key and /handshake version"
+        );
+    }
+
+    #[test]
+    fn test_prefunded_accounts_are_parsed() {
+        let mut config = valid_config();
+        assert!(parse_config(&config).unwrap().prefunded_accounts.is_empty());
+
+        let account = |address: &str, balance: &str| PrefundedAccount {
+            address: address.to_string(),
+            balance: balance.to_string(),
+        };
+        config.prefunded_accounts = vec![
+            account("0x3333333333333333333333333333333333333333", "1000"),
+            account("0x4444444444444444444444444444444444444444", "0x10"),
+        ];
+        assert_eq!(
+            parse_config(&config).unwrap().prefunded_accounts,
+            [
+                (Address::repeat_byte(0x33), U256::from(1000)),
+                (Address::repeat_byte(0x44), U256::from(16)),
+            ]
+        );
+
+        config.prefunded_accounts.push(account("0x1234", "1"));
+        let errors = validate_config(&config).unwrap_err();
+        assert!(
+            errors[0]
+                .to_string()
+                .starts_with("prefundedAccounts[2] invalid address"),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_duplicate_validators_are_rejected() {
+        let mut config = valid_config();
+        config.validator_addresses[1] = config.validator_addresses[0].clone();
+        config.aptos_addresses[1] = format!("0x{}", config.aptos_addresses[0]);
+        config.consensus_public_keys[1] = config.consensus_public_keys[0].clone();
+        let duplicate = |field| ConfigError::Duplicate {
+            field,
+            index: 1,
+            first: 0,
+        };
+        assert_eq!(
+            validate_config(&config),
+            Err(vec![
+                duplicate("validatorAddresses"),
+                duplicate("aptosAddresses"),
+                duplicate("consensusPublicKeys"),
+            ])
+        );
+
+        // Keys are compared as decoded, so case and 0x do not hide a duplicate
+        let mut config = valid_config();
+        config.consensus_public_keys[1] = format!("0x{}", "AA".repeat(48));
+        assert_eq!(
+            validate_config(&config),
+            Err(vec![duplicate("consensusPublicKeys")])
+        );
+        // whereas raw keys are their UTF-8 bytes, which differ
+        config.raw_consensus_keys = true;
+        assert_eq!(validate_config(&config), Ok(()));
+    }
+
+    #[test]
+    fn test_monikers_follow_the_contract_rules() {
+        let mut config = valid_config();
+        config.monikers = vec!["Alpha".to_string(), "VAL1".to_string()];
+        assert_eq!(validate_config(&config), Ok(()));
+
+        let invalid = |moniker: &str| {
+            let mut config = valid_config();
+            config.monikers = vec!["Alpha".to_string(), moniker.to_string()];
+            validate_config(&config)
+                .unwrap_err()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        for moniker in [
+            "alpha",
+            "East-0",
+            "Al",
+            "Abcdefghij",
+            "Beta Node",
+            "Ünicode",
+        ] {
+            assert_eq!(
+                invalid(moniker),
+                [format!(
+                    "monikers[1] {:?} is not 3 to 9 letters or digits starting with an uppercase letter",
+                    moniker
+                )]
+            );
+        }
+        assert_eq!(invalid("Alpha"), ["monikers[1] duplicates monikers[0]"]);
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::OnceLock;
+
+use crate::{
+    diagnostics::Diagnostics, error_signatures::ErrorSignatures, progress::Progress,
+    registry::ContractRegistry,
+};
+
+/// State of one run, shared by the steps that build and check a genesis
+///
+/// It is passed along instead of being kept in globals, so that two runs in
+/// one process, such as tests, do not see each other's contracts.
+#[derive(Debug, Default)]
+pub struct RunContext {
+    /// Contracts deployed at genesis and the addresses they are placed at
+    pub registry: ContractRegistry,
+    /// Custom errors loaded with `--abi-dir`, used to decode reverts
+    pub error_signatures: ErrorSignatures,
+    /// Warnings emitted so far, listed at the end of the run
+    pub diagnostics: Diagnostics,
+    /// Progress bar, left disabled unless the caller enables it
+    pub progress: Progress,
+    /// Standard input, kept once read for inputs given as `-`
+    pub(crate) stdin: OnceLock<String>,
+}
+
+impl RunContext {
+    pub fn new(registry: ContractRegistry) -> Self {
+        Self {
+            registry,
+            ..Self::default()
+        }
+    }
+
+    /// Decode reverts with the custom errors in `signatures`
+    pub fn with_error_signatures(mut self, signatures: ErrorSignatures) -> Self {
+        self.error_signatures = signatures;
+        self
+    }
+}
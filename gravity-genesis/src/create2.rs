@@ -0,0 +1,161 @@
+use std::{collections::BTreeMap, fs::File, io::BufWriter};
+
+use revm_primitives::{Address, B256, address, hex, keccak256};
+
+use crate::{
+    registry::ContractRegistry,
+    utils::{CONTRACTS, read_hex_from_file},
+};
+
+/// Deterministic deployment proxy present at the same address on most EVM chains
+pub const DEFAULT_CREATE2_DEPLOYER: Address = address!("4e59b44847b379578588920ca78fbf26c0b4956c");
+
+/// `keccak256(0xff ++ deployer ++ salt ++ keccak256(init_code))[12..]`, as specified by EIP-1014
+pub fn create2_address(deployer: Address, salt: B256, init_code: &[u8]) -> Address {
+    let preimage = [
+        &[0xff][..],
+        deployer.as_slice(),
+        salt.as_slice(),
+        keccak256(init_code).as_slice(),
+    ]
+    .concat();
+    Address::from_slice(&keccak256(preimage)[12..])
+}
+
+/// Move every contract in `registry` to the address `deployer` would CREATE2 it at
+///
+/// System contracts stay where they are: System.sol hard-codes their
+/// addresses, so the contracts calling each other would miss a relocated
+/// peer. The init code is the contract's `.hex` file as compiled. Library
+/// placeholders depend on the addresses being computed, so bytecode that
+/// still holds them is rejected.
+pub fn create2_registry(
+    registry: &ContractRegistry,
+    byte_code_dir: &str,
+    deployer: Address,
+    salt: B256,
+) -> Result<ContractRegistry, String> {
+    let mut entries = BTreeMap::new();
+    for (contract_name, address) in registry.contracts() {
+        if CONTRACTS.iter().any(|(name, _)| name == contract_name) {
+            entries.insert(contract_name.clone(), *address);
+            continue;
+        }
+        let hex_path = format!("{}/{}.hex", byte_code_dir, contract_name);
+        let init_code = hex::decode(read_hex_from_file(&hex_path)?).map_err(|e| {
+            format!(
+                "{}: init code is not valid hex ({}); unlinked libraries are not supported with CREATE2 deployment",
+                contract_name, e
+            )
+        })?;
+        entries.insert(
+            contract_name.clone(),
+            create2_address(deployer, salt, &init_code),
+        );
+    }
+    ContractRegistry::from_entries(entries)
+}
+
+/// Write the computed address map as `create2_addresses.json`, usable as a `--registry` file
+pub fn write_create2_addresses(
+    contracts: &[(String, Address)],
+    output_dir: &str,
+) -> std::io::Result<()> {
+    let addresses: BTreeMap<&str, Address> = contracts
+        .iter()
+        .map(|(name, address)| (name.as_str(), *address))
+        .collect();
+    let file = File::create(format!("{output_dir}/create2_addresses.json"))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &addresses)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::b256;
+
+    #[test]
+    fn test_create2_address_matches_eip1014_vectors() {
+        assert_eq!(
+            create2_address(Address::ZERO, B256::ZERO, &[0x00]),
+            address!("4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38")
+        );
+        assert_eq!(
+            create2_address(
+                address!("00000000000000000000000000000000deadbeef"),
+                b256!("00000000000000000000000000000000000000000000000000000000cafebabe"),
+                &hex::decode("deadbeef").unwrap(),
+            ),
+            address!("60f3f640a8508fC6a86d45DF051962668E1e8AC7")
+        );
+    }
+
+    #[test]
+    fn test_create2_registry_derives_addresses_from_init_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ContractRegistry::from_entries(
+            [
+                "Genesis",
+                "ValidatorManager",
+                "EpochManager",
+                "JWKManager",
+                "Faucet",
+                "Multicall",
+            ]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                std::fs::write(
+                    dir.path().join(format!("{name}.hex")),
+                    format!("60{:02x}\n", i),
+                )
+                .unwrap();
+                (name.to_string(), Address::with_last_byte(i as u8))
+            })
+            .collect(),
+        )
+        .unwrap();
+
+        let salt = B256::with_last_byte(1);
+        let relocated = create2_registry(
+            &registry,
+            dir.path().to_str().unwrap(),
+            DEFAULT_CREATE2_DEPLOYER,
+            salt,
+        )
+        .unwrap();
+        // System.sol hard-codes the system contract addresses, so they stay put
+        assert_eq!(
+            relocated.address_of("Genesis"),
+            Some(Address::with_last_byte(0))
+        );
+        assert_eq!(
+            relocated.address_of("JWKManager"),
+            Some(Address::with_last_byte(3))
+        );
+        assert_eq!(
+            relocated.address_of("Faucet"),
+            Some(create2_address(
+                DEFAULT_CREATE2_DEPLOYER,
+                salt,
+                &[0x60, 0x04]
+            ))
+        );
+        assert_eq!(
+            relocated.address_of("Multicall"),
+            Some(create2_address(
+                DEFAULT_CREATE2_DEPLOYER,
+                salt,
+                &[0x60, 0x05]
+            ))
+        );
+
+        write_create2_addresses(relocated.contracts(), dir.path().to_str().unwrap()).unwrap();
+        let written: BTreeMap<String, Address> = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join("create2_addresses.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(ContractRegistry::from_entries(written).unwrap(), relocated);
+    }
+}
@@ -0,0 +1,118 @@
+use std::{fmt, sync::Mutex};
+
+use tracing::warn;
+
+/// Stable code of each kind of warning, so CI can grep for or deny them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticCode {
+    /// Bytecode that looks like constructor code is deployed as runtime code
+    BytecodeHeuristic,
+    /// JWK providers are not sorted by issuer, so `JWKManager` stores them in another order
+    JwksReordered,
+    /// A balance override names a contract that is not in the registry
+    UnknownContractBalance,
+    /// The total voting power exceeds `maxTotalVotingPowerBits`
+    VotingPowerBound,
+    /// A file in the ABI directory is not valid JSON
+    InvalidAbiFile,
+    /// A verification view call wrote state
+    ViewCallWroteState,
+    /// The `getObservedJWKs` result could not be decoded
+    UndecodableJwks,
+    /// An active OIDC provider on chain is missing from the expected providers
+    UnexpectedOidcProvider,
+    /// An OIDC provider's config URL is not its OpenID discovery document
+    NonStandardOidcConfigUrl,
+    /// A genesis transaction used more gas than its configured cap
+    GasCapExceeded,
+    /// An optional JWKs or OIDC providers file does not exist, so its transactions are skipped
+    MissingOptionalInput,
+    /// Contracts without a `.hex` file are left out of a partial deployment
+    MissingContractArtifact,
+    /// The genesis transactions together used more gas than the block gas limit
+    BlockGasLimitExceeded,
+    /// A contract selected for a partial deployment calls into one that is not selected
+    UnselectedDependency,
+}
+
+impl DiagnosticCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BytecodeHeuristic => "GEN-W001",
+            Self::JwksReordered => "GEN-W002",
+            Self::UnknownContractBalance => "GEN-W003",
+            Self::VotingPowerBound => "GEN-W004",
+            Self::InvalidAbiFile => "GEN-W005",
+            Self::ViewCallWroteState => "GEN-W006",
+            Self::UndecodableJwks => "GEN-W007",
+            Self::UnexpectedOidcProvider => "GEN-W008",
+            Self::NonStandardOidcConfigUrl => "GEN-W009",
+            Self::GasCapExceeded => "GEN-W010",
+            Self::MissingOptionalInput => "GEN-W011",
+            Self::MissingContractArtifact => "GEN-W012",
+            Self::BlockGasLimitExceeded => "GEN-W013",
+            Self::UnselectedDependency => "GEN-W014",
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A warning emitted during the run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+/// Warnings emitted during one run, kept for the end-of-run summary
+#[derive(Debug, Default)]
+pub struct Diagnostics(Mutex<Vec<Diagnostic>>);
+
+impl Diagnostics {
+    /// Log a warning and record it for the end-of-run summary
+    pub fn emit(&self, code: DiagnosticCode, message: impl Into<String>) {
+        let diagnostic = Diagnostic {
+            code,
+            message: message.into(),
+        };
+        warn!(code = code.as_str(), "⚠ {}", diagnostic);
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(diagnostic);
+    }
+
+    /// Every diagnostic emitted so far, in order
+    pub fn emitted(&self) -> Vec<Diagnostic> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_are_recorded_in_order_with_their_code() {
+        let diagnostics = Diagnostics::default();
+        diagnostics.emit(DiagnosticCode::JwksReordered, "providers out of order");
+        diagnostics.emit(DiagnosticCode::BytecodeHeuristic, "constructor bytecode");
+
+        let recorded = diagnostics.emitted();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].to_string(), "[GEN-W002] providers out of order");
+        assert_eq!(recorded[1].code.as_str(), "GEN-W001");
+        assert!(Diagnostics::default().emitted().is_empty());
+    }
+}
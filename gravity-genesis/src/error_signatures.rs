@@ -0,0 +1,139 @@
+use std::{collections::HashMap, fmt};
+
+use alloy_json_abi::JsonAbi;
+use tracing::info;
+use walkdir::WalkDir;
+
+use crate::diagnostics::{DiagnosticCode, Diagnostics};
+
+/// Name and canonical parameter types of a Solidity custom error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorSignature {
+    pub name: String,
+    pub param_types: Vec<String>,
+}
+
+impl fmt::Display for ErrorSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name, self.param_types.join(","))
+    }
+}
+
+pub type ErrorSignatures = HashMap<[u8; 4], ErrorSignature>;
+
+/// Collect the custom errors declared in an ABI, keyed by selector
+pub fn error_signatures_from_abi(abi: &JsonAbi) -> ErrorSignatures {
+    abi.errors()
+        .map(|error| {
+            let signature = ErrorSignature {
+                name: error.name.clone(),
+                param_types: error
+                    .inputs
+                    .iter()
+                    .map(|param| param.selector_type().into_owned())
+                    .collect(),
+            };
+            (error.selector().0, signature)
+        })
+        .collect()
+}
+
+/// Build the selector table from every ABI JSON file under `abi_dir`
+///
+/// Accepts Foundry artifacts, whose ABI is under the `abi` key, as well as
+/// bare ABI arrays. Files that are not ABIs are skipped.
+pub fn load_error_signatures(
+    abi_dir: &str,
+    diagnostics: &Diagnostics,
+) -> Result<ErrorSignatures, String> {
+    let mut signatures = ErrorSignatures::new();
+    for entry in WalkDir::new(abi_dir) {
+        let entry = entry.map_err(|e| format!("Failed to read ABI directory: {}", e))?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&content) else {
+            diagnostics.emit(
+                DiagnosticCode::InvalidAbiFile,
+                format!("Skipping {}: not valid JSON", path.display()),
+            );
+            continue;
+        };
+        if let Some(abi) = value.get_mut("abi") {
+            value = abi.take();
+        }
+        let Ok(abi) = serde_json::from_value::<JsonAbi>(value) else {
+            continue;
+        };
+        signatures.extend(error_signatures_from_abi(&abi));
+    }
+    info!(
+        "Loaded {} error signatures from {}",
+        signatures.len(),
+        abi_dir
+    );
+    Ok(signatures)
+}
+
+/// Resolve a revert selector using an ABI selector table
+pub fn lookup_error_signature<'a>(
+    signatures: &'a ErrorSignatures,
+    selector: &[u8],
+) -> Option<&'a ErrorSignature> {
+    let selector: [u8; 4] = selector.try_into().ok()?;
+    signatures.get(&selector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_signatures_from_foundry_artifact() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("Staking.sol")).unwrap();
+        let artifact = serde_json::json!({
+            "abi": [
+                {
+                    "type": "error",
+                    "name": "StakeTooLow",
+                    "inputs": [
+                        { "name": "minimum", "type": "uint256", "internalType": "uint256" },
+                        {
+                            "name": "info",
+                            "type": "tuple",
+                            "internalType": "struct Info",
+                            "components": [
+                                { "name": "owner", "type": "address", "internalType": "address" },
+                                { "name": "tags", "type": "bytes[]", "internalType": "bytes[]" }
+                            ]
+                        }
+                    ]
+                },
+                { "type": "function", "name": "stake", "inputs": [], "outputs": [], "stateMutability": "payable" }
+            ],
+            "bytecode": { "object": "0x" }
+        });
+        std::fs::write(
+            dir.path().join("Staking.sol/Staking.json"),
+            serde_json::to_string(&artifact).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.json"), "{\"not\": \"an abi\"}").unwrap();
+
+        let signatures =
+            load_error_signatures(dir.path().to_str().unwrap(), &Diagnostics::default()).unwrap();
+        assert_eq!(signatures.len(), 1);
+        let signature = signatures.values().next().unwrap();
+        assert_eq!(
+            signature.to_string(),
+            "StakeTooLow(uint256,(address,bytes[]))"
+        );
+        let hash = alloy_primitives::keccak256(signature.to_string());
+        let selector: [u8; 4] = hash[..4].try_into().unwrap();
+        assert!(signatures.contains_key(&selector));
+    }
+}
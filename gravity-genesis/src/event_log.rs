@@ -0,0 +1,109 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use revm_primitives::{Address, B256, Bytes, ExecutionResult};
+use serde::Serialize;
+
+use crate::utils::decode_debug_log;
+
+/// One log emitted by a genesis transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventLogEntry {
+    /// 1-based index of the transaction that emitted the log
+    pub transaction: usize,
+    pub label: String,
+    /// Contract that emitted the log
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+    /// Known events decoded, e.g. `Log(message: "initialized", value: 1)`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<String>,
+}
+
+/// Every log emitted during genesis execution, in emission order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EventLog {
+    pub logs: Vec<EventLogEntry>,
+}
+
+impl EventLog {
+    /// Collect the logs of each transaction's result, labeled like the gas report
+    ///
+    /// Reverted and halted transactions emit no logs.
+    pub fn new(labels: &[String], results: &[ExecutionResult]) -> Self {
+        let logs = labels
+            .iter()
+            .zip(results)
+            .enumerate()
+            .flat_map(|(i, (label, result))| {
+                result.logs().iter().map(move |log| EventLogEntry {
+                    transaction: i + 1,
+                    label: label.clone(),
+                    address: log.address,
+                    topics: log.topics().to_vec(),
+                    data: log.data.data.clone(),
+                    decoded: decode_debug_log(log),
+                })
+            })
+            .collect();
+        Self { logs }
+    }
+
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::IDebugLog;
+    use alloy_sol_types::SolEvent;
+    use revm_primitives::{Output, SuccessReason, U256};
+
+    #[test]
+    fn test_logs_are_collected_and_known_events_decoded() {
+        let emitter = Address::with_last_byte(0x20);
+        let known = alloy_primitives::Log {
+            address: emitter,
+            data: IDebugLog::Log_1 {
+                message: "initialized".to_string(),
+                value: U256::from(1),
+            }
+            .encode_log_data(),
+        };
+        let unknown = alloy_primitives::Log::new_unchecked(
+            emitter,
+            vec![B256::repeat_byte(0xaa)],
+            Bytes::from_static(&[0x01]),
+        );
+        let results = vec![
+            ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used: 100,
+                gas_refunded: 0,
+                logs: vec![known, unknown],
+                output: Output::Call(Bytes::new()),
+            },
+            ExecutionResult::Revert {
+                gas_used: 100,
+                output: Bytes::new(),
+            },
+        ];
+        let labels = ["Genesis.initialize", "JWKManager.upsertObservedJWKs"].map(String::from);
+
+        let event_log = EventLog::new(&labels, &results);
+        assert_eq!(event_log.logs.len(), 2);
+        assert_eq!(event_log.logs[0].transaction, 1);
+        assert_eq!(event_log.logs[0].address, emitter);
+        assert_eq!(
+            event_log.logs[0].decoded.as_deref(),
+            Some("Log(message: \"initialized\", value: 1)")
+        );
+        assert_eq!(event_log.logs[1].topics, vec![B256::repeat_byte(0xaa)]);
+        assert_eq!(event_log.logs[1].data, Bytes::from_static(&[0x01]));
+        assert_eq!(event_log.logs[1].decoded, None);
+    }
+}
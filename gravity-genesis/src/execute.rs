@@ -1,54 +1,323 @@
 use crate::{
+    balances::{ContractBalances, default_contract_balances},
+    builder::{GenesisBuilder, GenesisError},
+    codehash::{BytecodeManifest, CodeHashDb, check_bytecode_manifest, check_code_hash},
+    context::RunContext,
+    diagnostics::DiagnosticCode,
+    extra_txns::ExtraTransaction,
     genesis::{GenesisConfig, call_genesis_initialize},
-    jwks::{upsert_observed_jwks, upsert_oidc_providers},
+    jwks::{InputDocument, revoke_jwks, upsert_observed_jwks, upsert_oidc_providers},
+    linking::LibraryLinker,
+    randomness::randomness_transactions,
+    registry::{self, ContractRegistry},
     utils::{
-        CONTRACTS, GENESIS_ADDR, SYSTEM_ACCOUNT_INFO, SYSTEM_CALLER, analyze_txn_result,
-        execute_revm_sequential, read_hex_from_file,
+        DEFAULT_CONTRACT_NONCE, DEFAULT_SYSTEM_CALLER_BALANCE, PRODUCTION_GAS_LIMIT, SYSTEM_CALLER,
+        read_hex_from_file, system_account_info,
     },
 };
 
 use alloy_chains::NamedChain;
 
+use rayon::prelude::*;
 use revm::{
     InMemoryDB,
     db::{BundleState, PlainAccount},
-    primitives::{AccountInfo, Env, SpecId, U256},
+    primitives::{AccountInfo, Address, Env, U256},
 };
-use revm_primitives::{Bytecode, Bytes, TxEnv, hex};
-use std::{collections::HashMap, fs::File, io::BufWriter};
-use tracing::{debug, error, info, warn};
+use revm_primitives::{Bytecode, Bytes, MAX_CODE_SIZE, TxEnv, hex, keccak256};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs::File,
+    io::BufReader,
+    path::Path,
+};
+use tracing::{error, info};
+
+/// Where contracts are placed in the genesis state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DeployMode {
+    /// At the addresses in the contract registry
+    #[default]
+    Fixed,
+    /// At the CREATE2 address of each contract's init code from a known deployer and salt
+    Create2,
+}
+
+/// Options controlling how contract bytecode is placed into the genesis state
+#[derive(Debug, Clone)]
+pub struct DeployOptions {
+    pub balances: ContractBalances,
+    /// Approved code hashes every contract must match, if given
+    pub codehash_db: Option<CodeHashDb>,
+    /// Expected code hashes of the contracts it lists, if given
+    pub bytecode_manifest: Option<BytecodeManifest>,
+    /// Largest runtime bytecode accepted, in bytes
+    pub max_code_size: usize,
+    /// Leave out contracts whose `.hex` file does not exist instead of failing
+    pub allow_missing: bool,
+    /// Deploy only these contracts, every registered one when `None`
+    pub only: Option<BTreeSet<String>>,
+    /// Resolves library placeholders left in the bytecode by solc
+    pub linker: LibraryLinker,
+    /// Sender of the genesis transactions, funded for their execution and removed afterwards
+    pub system_caller: Address,
+    pub system_caller_balance: U256,
+    /// Nonce of every deployed contract account
+    pub contract_nonce: u64,
+    /// Whether the genesis transactions start with `Genesis.initialize`; off to
+    /// deploy the contracts only, or to layer onto a base state that already ran it
+    pub initialize: bool,
+}
+
+impl Default for DeployOptions {
+    fn default() -> Self {
+        Self {
+            balances: default_contract_balances(),
+            codehash_db: None,
+            bytecode_manifest: None,
+            max_code_size: MAX_CODE_SIZE,
+            allow_missing: false,
+            only: None,
+            linker: LibraryLinker::from_contracts(ContractRegistry::default().contracts(), None),
+            system_caller: SYSTEM_CALLER,
+            system_caller_balance: DEFAULT_SYSTEM_CALLER_BALANCE,
+            contract_nonce: DEFAULT_CONTRACT_NONCE,
+            initialize: true,
+        }
+    }
+}
+
+impl DeployOptions {
+    /// Whether `contract_name` is deployed under [`DeployOptions::only`]
+    pub fn deploys(&self, contract_name: &str) -> bool {
+        self.only
+            .as_ref()
+            .is_none_or(|only| only.contains(contract_name))
+    }
+
+    /// Balance `contract_name` is deployed with, zero unless set in [`DeployOptions::balances`]
+    pub fn contract_balance(&self, contract_name: &str) -> U256 {
+        self.balances
+            .get(contract_name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Account a contract is placed into the genesis state with
+    pub(crate) fn contract_account_info(
+        &self,
+        runtime_bytecode: Vec<u8>,
+        balance: U256,
+    ) -> AccountInfo {
+        AccountInfo {
+            code: Some(Bytecode::new_raw(Bytes::from(runtime_bytecode))),
+            balance,
+            nonce: self.contract_nonce,
+            ..AccountInfo::default()
+        }
+    }
+}
+
+/// Check a partial deployment: fail on names that are not registered
+/// contracts, warn about known dependencies left out
+fn check_contract_selection(only: &BTreeSet<String>, context: &RunContext) -> Result<(), String> {
+    let unknown: Vec<&str> = only
+        .iter()
+        .map(String::as_str)
+        .filter(|name| context.registry.address_of(name).is_none())
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!(
+            "contract selection names unknown contracts: {}",
+            unknown.join(", ")
+        ));
+    }
+    for (contract, missing) in context.registry.missing_dependencies(only) {
+        context.diagnostics.emit(
+            DiagnosticCode::UnselectedDependency,
+            format!(
+                "{} depends on {}, which are not selected for deployment",
+                contract,
+                missing.join(", ")
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// Fail if two contracts, or a contract and the system caller, share an address
+///
+/// Inserting both into the database would silently keep only the last one.
+fn check_address_collisions(
+    contracts: &[(&str, Address, Vec<u8>)],
+    system_caller: Address,
+) -> Result<(), String> {
+    let mut owners: BTreeMap<Address, &str> =
+        BTreeMap::from([(system_caller, "the system caller")]);
+    let mut collisions = Vec::new();
+    for (contract_name, address, _) in contracts {
+        if let Some(owner) = owners.insert(*address, contract_name) {
+            collisions.push(format!(
+                "{} and {} share address {}",
+                owner, contract_name, address
+            ));
+        }
+    }
+    if collisions.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "contract address collision(s): {}",
+        collisions.join("; ")
+    ))
+}
+
+/// Reject runtime bytecode above `max_code_size`
+///
+/// Genesis places code directly in the state, so nothing else enforces the
+/// EIP-170 limit a node applies when the contract is later called or redeployed.
+pub fn check_code_size(
+    contract_name: &str,
+    code_size: usize,
+    max_code_size: usize,
+) -> Result<(), String> {
+    if code_size > max_code_size {
+        return Err(format!(
+            "{} runtime bytecode is {} bytes, exceeding the {}-byte code size limit",
+            contract_name, code_size, max_code_size
+        ));
+    }
+    Ok(())
+}
+
+/// Read every contract's `.hex` file, link its library placeholders and extract its runtime bytecode
+///
+/// Files are read and processed concurrently; the result is in [`registry::deployment_order`].
+/// Only the contracts selected by `options.only` are loaded. Contracts without a `.hex` file are
+/// all named in one error, or left out with a warning when `options.allow_missing` is set. Fails
+/// listing every contract with a placeholder the linker cannot resolve.
+pub(crate) fn load_runtime_bytecodes<'a>(
+    byte_code_dir: &str,
+    options: &DeployOptions,
+    context: &'a RunContext,
+) -> Result<Vec<(&'a str, Address, Vec<u8>)>, String> {
+    if let Some(only) = &options.only {
+        check_contract_selection(only, context)?;
+    }
+    let hex_path = |contract_name: &str| format!("{}/{}.hex", byte_code_dir, contract_name);
+    let selected: Vec<_> = registry::deployment_order(context.registry.contracts())?
+        .into_iter()
+        .filter(|(contract_name, _)| options.deploys(contract_name))
+        .collect();
+    let total = selected.len();
+    let (present, missing): (Vec<_>, Vec<_>) = selected
+        .into_iter()
+        .partition(|(contract_name, _)| Path::new(&hex_path(contract_name)).exists());
+    if !missing.is_empty() {
+        let message = format!(
+            "{} of {} contract artifacts missing: {}",
+            missing.len(),
+            total,
+            missing
+                .iter()
+                .map(|(contract_name, _)| contract_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if !options.allow_missing {
+            return Err(message);
+        }
+        context.diagnostics.emit(
+            DiagnosticCode::MissingContractArtifact,
+            format!("{}; deploying without them", message),
+        );
+    }
+
+    let loaded: Vec<Result<_, String>> = present
+        .into_par_iter()
+        .map(|(contract_name, target_address)| {
+            let bytecode_hex = options.linker.link(
+                contract_name,
+                &read_hex_from_file(&hex_path(contract_name))?,
+            )?;
+
+            // For BSC style, we need to extract runtime bytecode from constructor bytecode
+            // This is a simplified approach - in reality, we'd need to execute the constructor
+            // and extract the returned bytecode
+            let runtime_bytecode = extract_runtime_bytecode(contract_name, &bytecode_hex, context)
+                .map_err(|e| format!("{}: {}", contract_name, e))?;
+            Ok((contract_name.as_str(), *target_address, runtime_bytecode))
+        })
+        .collect();
+
+    let (bytecodes, errors): (Vec<_>, Vec<_>) = loaded.into_iter().partition(Result::is_ok);
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors.into_iter().filter_map(Result::err).collect();
+        return Err(format!(
+            "{} contract(s) failed to load:\n{}",
+            errors.len(),
+            errors.join("\n")
+        ));
+    }
+    Ok(bytecodes.into_iter().filter_map(Result::ok).collect())
+}
 
 // Alternative approach: Use BSC-style direct bytecode deployment
-fn deploy_bsc_style(byte_code_dir: &str) -> InMemoryDB {
+///
+/// Every contract is checked against the code size limit and, if configured,
+/// the approved code hashes and the bytecode manifest. All violations are
+/// reported together. Returns the database with the name, address and
+/// runtime bytecode of every contract placed in it.
+pub(crate) fn deploy_bsc_style<'a>(
+    byte_code_dir: &str,
+    options: &DeployOptions,
+    context: &'a RunContext,
+) -> Result<(InMemoryDB, Vec<(&'a str, Address, Vec<u8>)>), String> {
     let mut db = InMemoryDB::default();
+    let mut violations = Vec::new();
 
     // Add system address with balance
-    db.insert_account_info(SYSTEM_CALLER, SYSTEM_ACCOUNT_INFO);
-
-    for (contract_name, target_address) in CONTRACTS {
-        let hex_path = format!("{}/{}.hex", byte_code_dir, contract_name);
-        let bytecode_hex = read_hex_from_file(&hex_path);
+    db.insert_account_info(
+        options.system_caller,
+        system_account_info(options.system_caller_balance),
+    );
 
-        // For BSC style, we need to extract runtime bytecode from constructor bytecode
-        // This is a simplified approach - in reality, we'd need to execute the constructor
-        // and extract the returned bytecode
-        let runtime_bytecode = extract_runtime_bytecode(&bytecode_hex);
+    let runtime_bytecodes = load_runtime_bytecodes(byte_code_dir, options, context)?;
+    check_address_collisions(&runtime_bytecodes, options.system_caller)?;
+
+    // InMemoryDB insertion is sequential, only the loading runs in parallel
+    for (contract_name, target_address, runtime_bytecode) in &runtime_bytecodes {
+        let (contract_name, target_address) = (*contract_name, *target_address);
+        if let Err(e) =
+            check_code_size(contract_name, runtime_bytecode.len(), options.max_code_size)
+        {
+            error!("❌ {}", e);
+            violations.push(e);
+        }
+        let code_hash = keccak256(runtime_bytecode);
+        if let Some(Err(e)) = options
+            .bytecode_manifest
+            .as_ref()
+            .map(|manifest| check_bytecode_manifest(manifest, contract_name, code_hash))
+        {
+            error!("❌ {}", e);
+            violations.push(e);
+        }
+        if let Some(codehash_db) = &options.codehash_db {
+            match check_code_hash(codehash_db, contract_name, code_hash) {
+                Ok(version) => info!("{} code hash approved as {}", contract_name, version),
+                Err(e) => {
+                    error!("❌ {}", e);
+                    violations.push(e);
+                }
+            }
+        }
 
-        // Set large balance for JWK Manager and Validator Manager
-        let balance = if contract_name == "JwkManager" || contract_name == "ValidatorManager" || contract_name == "Genesis" {
-            // Set 1 million ETH balance (1e6 * 1e18 wei)
-            U256::from(1_000_000) * U256::from(10).pow(U256::from(18))
-        } else {
-            U256::ZERO
-        };
+        let balance = options.contract_balance(contract_name);
 
         db.insert_account_info(
             target_address,
-            AccountInfo {
-                code: Some(Bytecode::new_raw(Bytes::from(runtime_bytecode))),
-                balance,
-                ..AccountInfo::default()
-            },
+            options.contract_account_info(runtime_bytecode.clone(), balance),
         );
 
         if balance > U256::ZERO {
@@ -64,14 +333,27 @@ fn deploy_bsc_style(byte_code_dir: &str) -> InMemoryDB {
         }
     }
 
-    db
+    if !violations.is_empty() {
+        return Err(format!(
+            "{} contract deployment check(s) failed:\n{}",
+            violations.len(),
+            violations.join("\n")
+        ));
+    }
+
+    Ok((db, runtime_bytecodes))
 }
 
 // Extract runtime bytecode from constructor bytecode
 // This is a simplified implementation - in reality, we'd need to execute the constructor
-fn extract_runtime_bytecode(constructor_bytecode: &str) -> Vec<u8> {
+fn extract_runtime_bytecode(
+    contract_name: &str,
+    constructor_bytecode: &str,
+    context: &RunContext,
+) -> Result<Vec<u8>, String> {
     // For now, we'll try to detect if this is constructor bytecode or runtime bytecode
-    let bytes = hex::decode(constructor_bytecode).unwrap_or_default();
+    let bytes = hex::decode(constructor_bytecode)
+        .map_err(|e| format!("bytecode is not valid hex: {}", e))?;
 
     // Simple heuristic: if the bytecode starts with typical constructor patterns,
     // we need to extract the runtime part
@@ -79,201 +361,426 @@ fn extract_runtime_bytecode(constructor_bytecode: &str) -> Vec<u8> {
         // This looks like constructor bytecode
         // For now, we'll use a simplified approach and return the original bytecode
         // In a real implementation, we'd execute the constructor and extract the returned bytecode
-        warn!("   [!] Warning: Using constructor bytecode as runtime bytecode");
-        bytes
+        context.diagnostics.emit(
+            DiagnosticCode::BytecodeHeuristic,
+            format!(
+                "{}: using constructor bytecode as runtime bytecode",
+                contract_name
+            ),
+        );
+        Ok(bytes)
     } else {
         // This looks like runtime bytecode already
-        bytes
+        Ok(bytes)
+    }
+}
+
+/// Block context the genesis transactions are executed in
+///
+/// Which genesis contracts consume each field:
+/// - `number`: `JWKManager` stamps `block.number` into the events it emits
+///   while processing JWK updates and cross-chain deposits.
+/// - `timestamp`: `Genesis` emits it in `GenesisCompleted`, `KeylessAccount`
+///   records it as the account creation time and `Timestamp.nowTime` returns
+///   it. `EpochManager` takes its start time from the `Timestamp` contract,
+///   which is still zero during `initialize`, not from the block.
+/// - `basefee`: not read by any genesis contract. System transactions are
+///   zero-priced, so the EVM rejects them unless the basefee stays 0.
+/// - `chain_id`: `ValidatorManagerUtils` binds BLS proofs of possession to
+///   `block.chainid`.
+/// - `coinbase`: not read by any genesis contract today. `System` declares
+///   `OnlyCoinbase()` for calls reserved to the block producer, so contracts
+///   gated that way only accept genesis calls sent from this address.
+/// - `gas_limit`: not read by any genesis contract. Each transaction's gas
+///   limit is capped to it, so one that needs more runs out of gas.
+#[derive(Debug, Clone)]
+pub struct BlockContext {
+    pub chain_id: u64,
+    pub number: u64,
+    /// Genesis timestamp in unix seconds, the one source of `block.timestamp`,
+    /// the manifest's `timestamp` and the chain spec header
+    pub timestamp: u64,
+    pub basefee: u64,
+    /// Beneficiary of the genesis block, `block.coinbase`
+    pub coinbase: Address,
+    /// `block.gaslimit`, unlimited when `None`
+    pub gas_limit: Option<u64>,
+}
+
+impl Default for BlockContext {
+    fn default() -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self {
+            chain_id: NamedChain::Mainnet.into(),
+            number: 0,
+            timestamp,
+            basefee: 0,
+            coinbase: Address::ZERO,
+            gas_limit: None,
+        }
     }
 }
 
-pub fn prepare_env() -> Env {
+pub fn prepare_env(block: &BlockContext) -> Env {
     let mut env = Env::default();
-    env.cfg.chain_id = NamedChain::Mainnet.into();
-    env.tx.gas_limit = 30_000_000;
+    env.cfg.chain_id = block.chain_id;
+    env.tx.gas_limit = PRODUCTION_GAS_LIMIT;
+    env.block.number = U256::from(block.number);
+    env.block.timestamp = U256::from(block.timestamp);
+    env.block.basefee = U256::from(block.basefee);
+    env.block.coinbase = block.coinbase;
+    if let Some(gas_limit) = block.gas_limit {
+        env.block.gas_limit = U256::from(gas_limit);
+    }
     env
 }
 
+/// Rebuild the genesis state from a `genesis_accounts.json` written by [`genesis_generate`]
+///
+/// Every account's info, including its bytecode, and storage is inserted into
+/// a fresh database. Contract code hashes are recomputed from the bytecode.
+pub fn load_db_from_genesis_accounts(path: &str) -> Result<InMemoryDB, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let accounts: HashMap<Address, PlainAccount> = serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+
+    let mut db = InMemoryDB::default();
+    for (address, account) in accounts {
+        db.insert_account_info(address, account.info);
+        for (slot, value) in account.storage {
+            db.insert_account_storage(address, slot, value)
+                .map_err(|e| format!("Failed to insert storage of {}: {:?}", address, e))?;
+        }
+    }
+    info!(
+        "Loaded {} genesis accounts from {}",
+        db.accounts.len(),
+        path
+    );
+    Ok(db)
+}
+
+/// Gas report label of the `Genesis.initialize` transaction
+pub const GENESIS_INITIALIZE_LABEL: &str = "Genesis.initialize";
+
 /// Transaction builder for genesis initialization
-struct GenesisTransactionBuilder {
-    transactions: Vec<TxEnv>,
+///
+/// Each transaction is paired with a human readable label used in the gas report.
+struct GenesisTransactionBuilder<'a> {
+    caller: Address,
+    context: &'a RunContext,
+    transactions: Vec<(String, TxEnv)>,
 }
 
-impl GenesisTransactionBuilder {
-    fn new(config: &GenesisConfig) -> Self {
-        let transactions = vec![call_genesis_initialize(GENESIS_ADDR, config)];
-        Self { transactions }
+impl<'a> GenesisTransactionBuilder<'a> {
+    /// Start with `Genesis.initialize`, unless it is turned off or `Genesis` is
+    /// left out of the deployment
+    fn new(
+        config: &GenesisConfig,
+        deploy: &DeployOptions,
+        context: &'a RunContext,
+    ) -> Result<Self, String> {
+        let caller = deploy.system_caller;
+        let mut transactions = Vec::new();
+        if !deploy.initialize {
+            info!("Skipping {}", GENESIS_INITIALIZE_LABEL);
+        } else if deploy.deploys("Genesis") {
+            transactions.push((
+                GENESIS_INITIALIZE_LABEL.to_string(),
+                call_genesis_initialize(caller, context.registry.genesis_addr(), config)?,
+            ));
+        } else {
+            info!(
+                "Genesis is not deployed, skipping {}",
+                GENESIS_INITIALIZE_LABEL
+            );
+        }
+        Ok(Self {
+            caller,
+            context,
+            transactions,
+        })
     }
 
-    fn with_jwks(mut self, jwks_file: Option<String>) -> Self {
-        if let Some(jwks_file) = jwks_file {
-            let jwks_tx = upsert_observed_jwks(&jwks_file).expect("Failed to upsert observed JWKs");
-            self.transactions.push(jwks_tx);
-            info!("Added JWKs transaction from file: {}", jwks_file);
+    fn with_jwks(mut self, jwks: Option<&InputDocument>) -> Result<Self, String> {
+        if let Some(jwks) = jwks {
+            let jwks_tx = upsert_observed_jwks(self.caller, self.context, jwks)
+                .map_err(|e| format!("Failed to upsert observed JWKs: {}", e))?;
+            self.transactions
+                .push(("JWKManager.upsertObservedJWKs".to_string(), jwks_tx));
+            let revoke_txs = revoke_jwks(self.caller, self.context, jwks)
+                .map_err(|e| format!("Failed to revoke JWKs: {}", e))?;
+            self.transactions.extend(revoke_txs);
+            info!("Added JWKs transaction from file: {}", jwks.path);
         }
-        self
+        Ok(self)
     }
 
-    fn with_oidc_providers(mut self, oidc_providers_file: Option<String>) -> Self {
-        if let Some(oidc_providers_file) = oidc_providers_file {
-            let oidc_txs = upsert_oidc_providers(&oidc_providers_file)
-                .expect("Failed to upsert OIDC providers");
+    fn with_oidc_providers(
+        mut self,
+        oidc_providers: Option<&InputDocument>,
+    ) -> Result<Self, String> {
+        if let Some(oidc_providers) = oidc_providers {
+            let oidc_txs = upsert_oidc_providers(self.caller, self.context, oidc_providers)
+                .map_err(|e| format!("Failed to upsert OIDC providers: {}", e))?;
             let oidc_txs_count = oidc_txs.len();
             self.transactions.extend(oidc_txs);
             info!(
                 "Added {} OIDC provider transactions from file: {}",
-                oidc_txs_count, oidc_providers_file
+                oidc_txs_count, oidc_providers.path
             );
         }
-        self
+        Ok(self)
     }
 
-    fn build(self) -> Vec<TxEnv> {
+    fn with_randomness(mut self, config: &GenesisConfig) -> Result<Self, String> {
+        if let Some(randomness_config) = &config.randomness_config {
+            let randomness_txs =
+                randomness_transactions(self.caller, &self.context.registry, randomness_config)
+                    .map_err(|e| format!("Failed to set the randomness config: {}", e))?;
+            self.transactions.extend(randomness_txs);
+            info!("Added randomness config transactions");
+        }
+        Ok(self)
+    }
+
+    fn with_extra_transactions(
+        mut self,
+        extra_transactions: &[ExtraTransaction],
+    ) -> Result<Self, String> {
+        for (i, extra) in extra_transactions.iter().enumerate() {
+            self.transactions
+                .push(extra.to_transaction(self.caller, &self.context.registry, i)?);
+        }
+        if !extra_transactions.is_empty() {
+            info!("Added {} extra transactions", extra_transactions.len());
+        }
+        Ok(self)
+    }
+
+    fn build(self) -> (Vec<String>, Vec<TxEnv>) {
         info!(
             "Built {} total genesis transactions",
             self.transactions.len()
         );
-        self.transactions
+        self.transactions.into_iter().unzip()
     }
 }
 
 /// Build genesis transactions using builder pattern
-fn build_genesis_transactions(
+pub(crate) fn build_genesis_transactions(
     config: &GenesisConfig,
-    jwks_file: Option<String>,
-    oidc_providers_file: Option<String>,
-) -> Vec<TxEnv> {
-    GenesisTransactionBuilder::new(config)
-        .with_jwks(jwks_file)
-        .with_oidc_providers(oidc_providers_file)
-        .build()
+    jwks: Option<&InputDocument>,
+    oidc_providers: Option<&InputDocument>,
+    extra_transactions: &[ExtraTransaction],
+    deploy: &DeployOptions,
+    context: &RunContext,
+) -> Result<(Vec<String>, Vec<TxEnv>), String> {
+    Ok(GenesisTransactionBuilder::new(config, deploy, context)?
+        .with_jwks(jwks)?
+        .with_oidc_providers(oidc_providers)?
+        .with_randomness(config)?
+        .with_extra_transactions(extra_transactions)?
+        .build())
 }
 
+/// Deploy the contracts, run the genesis transactions and write the output files
+///
+/// Thin wrapper over [`GenesisBuilder`] returning the database and bundle state.
 pub fn genesis_generate(
     byte_code_dir: &str,
     output_dir: &str,
     config: &GenesisConfig,
     jwks_file: Option<String>,
     oidc_providers_file: Option<String>,
-) -> (InMemoryDB, BundleState) {
-    info!("=== Starting Genesis deployment and initialization ===");
+    block: &BlockContext,
+    deploy: &DeployOptions,
+) -> Result<(InMemoryDB, BundleState), GenesisError> {
+    let mut builder = GenesisBuilder::new(byte_code_dir)
+        .with_output_dir(output_dir)
+        .with_config(config.clone())
+        .with_block(block.clone())
+        .with_deploy_options(deploy.clone());
+    if let Some(jwks_file) = jwks_file {
+        builder = builder.with_jwks_file(jwks_file);
+    }
+    if let Some(oidc_providers_file) = oidc_providers_file {
+        builder = builder.with_oidc_file(oidc_providers_file);
+    }
+    let artifacts = builder.build()?;
+    Ok((artifacts.db, artifacts.bundle_state))
+}
 
-    let db = deploy_bsc_style(byte_code_dir);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::DatabaseRef;
+    use revm_primitives::{KECCAK_EMPTY, address};
+
+    #[test]
+    fn test_missing_artifacts_are_reported_together() {
+        let context = RunContext::default();
+        let dir = tempfile::tempdir().unwrap();
+        let (first, _) = &context.registry.contracts()[0];
+        std::fs::write(dir.path().join(format!("{}.hex", first)), "0x6000").unwrap();
+        let byte_code_dir = dir.path().to_str().unwrap();
+        let total = context.registry.contracts().len();
+
+        let err =
+            load_runtime_bytecodes(byte_code_dir, &DeployOptions::default(), &context).unwrap_err();
+        assert!(
+            err.starts_with(&format!(
+                "{} of {} contract artifacts missing: ",
+                total - 1,
+                total
+            )),
+            "{}",
+            err
+        );
+        let (_, names) = err.split_once(": ").unwrap();
+        assert!(!names.split(", ").any(|name| name == first), "{}", err);
+
+        let options = DeployOptions {
+            allow_missing: true,
+            ..DeployOptions::default()
+        };
+        let loaded = load_runtime_bytecodes(byte_code_dir, &options, &context).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0, first.as_str());
 
-    let env = prepare_env();
+        let options = DeployOptions {
+            only: Some(BTreeSet::from([first.clone()])),
+            ..DeployOptions::default()
+        };
+        let loaded = load_runtime_bytecodes(byte_code_dir, &options, &context).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(!options.deploys("NotAContract"));
 
-    let txs = build_genesis_transactions(config, jwks_file, oidc_providers_file);
+        let options = DeployOptions {
+            only: Some(BTreeSet::from(["NotAContract".to_string()])),
+            ..DeployOptions::default()
+        };
+        assert_eq!(
+            load_runtime_bytecodes(byte_code_dir, &options, &context).unwrap_err(),
+            "contract selection names unknown contracts: NotAContract"
+        );
+    }
 
-    let r = execute_revm_sequential(db.clone(), SpecId::LATEST, env.clone(), &txs, None);
-    let (result, mut bundle_state) = match r {
-        Ok((result, bundle_state)) => {
-            info!("=== Genesis initialization successful ===");
-            (result, bundle_state)
-        }
-        Err(e) => {
-            panic!(
-                "Error: {}",
-                format!("{:?}", e.map_db_err(|_| "Database error".to_string()))
+    #[test]
+    fn test_deployed_contracts_get_the_contract_nonce() {
+        let context = RunContext::default();
+        let dir = tempfile::tempdir().unwrap();
+        let (name, address) = &context.registry.contracts()[0];
+        std::fs::write(dir.path().join(format!("{}.hex", name)), "0x6000").unwrap();
+        let byte_code_dir = dir.path().to_str().unwrap();
+
+        for contract_nonce in [DEFAULT_CONTRACT_NONCE, 0] {
+            let options = DeployOptions {
+                only: Some(BTreeSet::from([name.clone()])),
+                contract_nonce,
+                ..DeployOptions::default()
+            };
+            let (db, _) = deploy_bsc_style(byte_code_dir, &options, &context).unwrap();
+            assert_eq!(
+                db.basic_ref(*address).unwrap().unwrap().nonce,
+                contract_nonce
             );
         }
-    };
-    debug!("the bundle state is {:?}", bundle_state);
-    let ret = (db, bundle_state.clone());
-
-    for (i, r) in result.iter().enumerate() {
-        if !r.is_success() {
-            error!("=== Transaction {} failed ===", i + 1);
-            println!("Detailed analysis: {}", analyze_txn_result(r));
-            panic!("Genesis transaction {} failed", i + 1);
-        } else {
-            info!("Detailed analysis: {}", analyze_txn_result(r));
-        }
     }
-    info!(
-        "=== All {} transactions completed successfully ===",
-        result.len()
-    );
-
-    // Add deployed contracts to the final state
-    let mut genesis_state = HashMap::new();
 
-    for (contract_name, contract_address) in CONTRACTS {
-        let hex_path = format!("{}/{}.hex", byte_code_dir, contract_name);
-        let bytecode_hex = read_hex_from_file(&hex_path);
-        let runtime_bytecode = extract_runtime_bytecode(&bytecode_hex);
-
-        genesis_state.insert(
-            contract_address,
-            PlainAccount {
-                info: AccountInfo {
-                    code: Some(Bytecode::new_raw(Bytes::from(runtime_bytecode))),
-                    ..AccountInfo::default()
-                },
-                storage: Default::default(),
-            },
+    #[test]
+    fn test_address_collisions_name_both_contracts() {
+        let shared = address!("0000000000000000000000000000000000002013");
+        let contracts = vec![
+            ("StakeConfig", shared, Vec::new()),
+            (
+                "Delegation",
+                address!("0000000000000000000000000000000000002014"),
+                Vec::new(),
+            ),
+            ("ValidatorManager", shared, Vec::new()),
+        ];
+        assert_eq!(
+            check_address_collisions(&contracts, SYSTEM_CALLER),
+            Err(format!(
+                "contract address collision(s): StakeConfig and ValidatorManager share address {}",
+                shared
+            ))
         );
+        assert!(check_address_collisions(&contracts[..2], SYSTEM_CALLER).is_ok());
+        let err = check_address_collisions(&contracts[..1], shared).unwrap_err();
+        assert!(err.contains("the system caller and StakeConfig"), "{}", err);
+    }
 
-        info!(
-            "Added {} to genesis state at {:?}",
-            contract_name, contract_address
+    #[test]
+    fn test_code_size_limit() {
+        assert_eq!(
+            check_code_size("Genesis", MAX_CODE_SIZE, MAX_CODE_SIZE),
+            Ok(())
+        );
+        assert_eq!(
+            check_code_size("Genesis", MAX_CODE_SIZE + 1, MAX_CODE_SIZE),
+            Err(
+                "Genesis runtime bytecode is 24577 bytes, exceeding the 24576-byte code size limit"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_code_size("Genesis", MAX_CODE_SIZE + 1, 2 * MAX_CODE_SIZE),
+            Ok(())
         );
     }
 
-    // Add any state changes from the bundle_state (from the initialize transaction)
-    bundle_state.state.remove(&SYSTEM_CALLER);
-    // write bundle state into one json file named bundle_state.json
-    serde_json::to_writer_pretty(
-        BufWriter::new(File::create(format!("{output_dir}/bundle_state.json")).unwrap()),
-        &bundle_state,
-    )
-    .unwrap();
-
-    info!(
-        "bundle state size is {:?}, contracts size {:?}",
-        bundle_state.state.len(),
-        CONTRACTS.len()
-    );
-    for (address, account) in bundle_state.state.into_iter() {
-        debug!("Address: {:?}, account: {:?}", address, account);
-        if let Some(info) = account.info {
-            let storage = account
-                .storage
-                .into_iter()
-                .map(|(k, v)| (k, v.present_value()))
-                .collect();
-
-            // If this address already exists in genesis_state, merge the storage
-            if let Some(existing) = genesis_state.get_mut(&address) {
-                existing.storage.extend(storage);
-                existing.info = info;
-            } else {
-                genesis_state.insert(address, PlainAccount { info, storage });
-            }
-        }
+    #[test]
+    fn test_load_db_from_genesis_accounts_round_trip() {
+        let contract = address!("0000000000000000000000000000000000002013");
+        let funded = address!("3333333333333333333333333333333333333333");
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00, 0x54, 0x00]));
+        let genesis_state = HashMap::from([
+            (
+                contract,
+                PlainAccount {
+                    info: AccountInfo {
+                        code: Some(code.clone()),
+                        ..AccountInfo::default()
+                    },
+                    storage: [(U256::from(3), U256::from(42))].into_iter().collect(),
+                },
+            ),
+            (
+                funded,
+                PlainAccount {
+                    info: AccountInfo {
+                        balance: U256::from(1000),
+                        ..AccountInfo::default()
+                    },
+                    storage: Default::default(),
+                },
+            ),
+        ]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("genesis_accounts.json");
+        serde_json::to_writer_pretty(File::create(&path).unwrap(), &genesis_state).unwrap();
+
+        let db = load_db_from_genesis_accounts(path.to_str().unwrap()).unwrap();
+        let info = db.basic_ref(contract).unwrap().unwrap();
+        assert_eq!(info.code_hash, code.hash_slow());
+        assert_eq!(
+            db.code_by_hash_ref(info.code_hash)
+                .unwrap()
+                .original_bytes(),
+            code.original_bytes()
+        );
+        assert_eq!(
+            db.storage_ref(contract, U256::from(3)).unwrap(),
+            U256::from(42)
+        );
+        let info = db.basic_ref(funded).unwrap().unwrap();
+        assert_eq!(info.balance, U256::from(1000));
+        assert_eq!(info.code_hash, KECCAK_EMPTY);
     }
-
-    serde_json::to_writer_pretty(
-        BufWriter::new(File::create(format!("{output_dir}/genesis_accounts.json")).unwrap()),
-        &genesis_state,
-    )
-    .unwrap();
-
-    // Create contracts JSON with bytecode
-    let contracts_json: HashMap<_, _> = genesis_state
-        .iter()
-        .filter_map(|(addr, account)| {
-            account
-                .info
-                .code
-                .as_ref()
-                .map(|code| (*addr, code.bytecode()))
-        })
-        .collect();
-
-    serde_json::to_writer_pretty(
-        BufWriter::new(File::create(format!("{output_dir}/genesis_contracts.json")).unwrap()),
-        &contracts_json,
-    )
-    .unwrap();
-    ret
 }
@@ -0,0 +1,212 @@
+use revm_primitives::{Address, Bytes, TxEnv, hex, keccak256};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    config_format::parse_config_file,
+    context::RunContext,
+    registry::ContractRegistry,
+    utils::{new_system_call_txn, read_input_file},
+};
+
+/// One system call appended to the genesis transactions by `--extra-txns`
+///
+/// The calldata is either `selector` followed by the ABI-encoded `args`, or
+/// the raw `calldata`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExtraTransaction {
+    /// Registered contract the call is sent to, e.g. `StakeConfig`
+    pub contract: String,
+    /// 4-byte selector as hex, or the function signature it is hashed from,
+    /// e.g. `setMinimumStake(uint256)`
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// ABI-encoded arguments as hex, appended to `selector`
+    #[serde(default)]
+    pub args: Option<String>,
+    /// Complete calldata as hex, instead of `selector` and `args`
+    #[serde(default)]
+    pub calldata: Option<String>,
+    /// Name in the gas report; `<contract>.<signature>` or `<contract>.extra[<index>]` by default
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtraTransactionsFile {
+    transactions: Vec<ExtraTransaction>,
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value).map_err(|e| format!("invalid {} {:?}: {}", field, value, e))
+}
+
+/// `selector` as 4 bytes, hashing it first when it is a function signature
+fn parse_selector(selector: &str) -> Result<[u8; 4], String> {
+    if selector.contains('(') {
+        let hash = keccak256(selector.as_bytes());
+        return Ok([hash[0], hash[1], hash[2], hash[3]]);
+    }
+    decode_hex("selector", selector)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| {
+            format!(
+                "selector {:?} is {} bytes, expected 4",
+                selector,
+                bytes.len()
+            )
+        })
+}
+
+impl ExtraTransaction {
+    fn calldata(&self) -> Result<Bytes, String> {
+        match (&self.selector, &self.args, &self.calldata) {
+            (None, None, Some(calldata)) => Ok(decode_hex("calldata", calldata)?.into()),
+            (Some(selector), args, None) => {
+                let mut calldata = parse_selector(selector)?.to_vec();
+                if let Some(args) = args {
+                    calldata.extend(decode_hex("args", args)?);
+                }
+                Ok(calldata.into())
+            }
+            (None, Some(_), _) => Err("args are given without a selector".to_string()),
+            (Some(_), _, Some(_)) => {
+                Err("give either selector and args or calldata, not both".to_string())
+            }
+            (None, None, None) => Err("needs a selector or calldata".to_string()),
+        }
+    }
+
+    fn default_label(&self, index: usize) -> String {
+        match &self.selector {
+            Some(selector) if selector.contains('(') => format!("{}.{}", self.contract, selector),
+            _ => format!("{}.extra[{}]", self.contract, index),
+        }
+    }
+
+    /// The labeled system call of this entry, the `index`-th of its file
+    pub fn to_transaction(
+        &self,
+        caller: Address,
+        registry: &ContractRegistry,
+        index: usize,
+    ) -> Result<(String, TxEnv), String> {
+        let context = |e: String| format!("extra transaction {} ({}): {}", index, self.contract, e);
+        let target = registry
+            .address_of(&self.contract)
+            .ok_or_else(|| context("not a registered contract".to_string()))?;
+        let calldata = self.calldata().map_err(context)?;
+        let label = self
+            .label
+            .clone()
+            .unwrap_or_else(|| self.default_label(index));
+        Ok((label, new_system_call_txn(caller, target, calldata)))
+    }
+}
+
+/// Read the `{"transactions": [...]}` file given with `--extra-txns`
+pub fn read_extra_transactions(
+    path: &str,
+    context: &RunContext,
+) -> Result<Vec<ExtraTransaction>, String> {
+    let content = read_input_file(path, context)
+        .map_err(|e| format!("Failed to read extra transactions file {}: {}", path, e))?;
+    let file: ExtraTransactionsFile = parse_config_file(path, &content)
+        .map_err(|e| format!("Failed to parse extra transactions file {}: {}", path, e))?;
+    info!(
+        "Loaded {} extra transactions from {}",
+        file.transactions.len(),
+        path
+    );
+    Ok(file.transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{STAKE_CONFIG_ADDR, SYSTEM_CALLER};
+    use revm_primitives::TxKind;
+
+    fn extra(json: serde_json::Value) -> ExtraTransaction {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_extra_transactions_build_system_calls() {
+        let registry = ContractRegistry::default();
+        let signature = extra(serde_json::json!({
+            "contract": "StakeConfig",
+            "selector": "setMinimumStake(uint256)",
+            "args": format!("0x{}", "00".repeat(31) + "2a"),
+        }));
+        let (label, tx) = signature
+            .to_transaction(SYSTEM_CALLER, &registry, 0)
+            .unwrap();
+        assert_eq!(label, "StakeConfig.setMinimumStake(uint256)");
+        assert_eq!(tx.caller, SYSTEM_CALLER);
+        assert_eq!(tx.transact_to, TxKind::Call(STAKE_CONFIG_ADDR));
+        assert_eq!(tx.data[..4], keccak256("setMinimumStake(uint256)")[..4]);
+        assert_eq!(tx.data.len(), 36);
+        assert_eq!(tx.data[35], 0x2a);
+
+        let raw = extra(serde_json::json!({
+            "contract": "StakeConfig",
+            "calldata": "0x12345678",
+            "label": "raw setter",
+        }));
+        let (label, tx) = raw.to_transaction(SYSTEM_CALLER, &registry, 1).unwrap();
+        assert_eq!(label, "raw setter");
+        assert_eq!(tx.data, Bytes::from_static(&[0x12, 0x34, 0x56, 0x78]));
+
+        let selector =
+            extra(serde_json::json!({"contract": "StakeConfig", "selector": "0x12345678"}));
+        assert_eq!(
+            selector
+                .to_transaction(SYSTEM_CALLER, &registry, 2)
+                .unwrap()
+                .0,
+            "StakeConfig.extra[2]"
+        );
+    }
+
+    #[test]
+    fn test_invalid_extra_transactions_are_rejected() {
+        let cases = [
+            (
+                serde_json::json!({"contract": "Nope", "calldata": "0x00"}),
+                "extra transaction 0 (Nope): not a registered contract",
+            ),
+            (
+                serde_json::json!({"contract": "StakeConfig"}),
+                "extra transaction 0 (StakeConfig): needs a selector or calldata",
+            ),
+            (
+                serde_json::json!({"contract": "StakeConfig", "selector": "0x1234", "calldata": "0x00"}),
+                "extra transaction 0 (StakeConfig): give either selector and args or calldata, not both",
+            ),
+            (
+                serde_json::json!({"contract": "StakeConfig", "selector": "0x1234"}),
+                "extra transaction 0 (StakeConfig): selector \"0x1234\" is 2 bytes, expected 4",
+            ),
+            (
+                serde_json::json!({"contract": "StakeConfig", "args": "0x00"}),
+                "extra transaction 0 (StakeConfig): args are given without a selector",
+            ),
+        ];
+        for (json, expected) in cases {
+            assert_eq!(
+                extra(json)
+                    .to_transaction(SYSTEM_CALLER, &ContractRegistry::default(), 0)
+                    .unwrap_err(),
+                expected
+            );
+        }
+        assert!(
+            serde_json::from_value::<ExtraTransaction>(
+                serde_json::json!({"contract": "StakeConfig", "value": 1})
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fs::File, io::BufWriter};
+
+use revm_primitives::{Address, ExecutionResult, TxEnv, TxKind, hex};
+use serde::{Deserialize, Serialize};
+
+/// Gas used by one genesis transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasReportEntry {
+    pub label: String,
+    /// Called contract, `None` for contract creations
+    pub target: Option<Address>,
+    /// 4-byte function selector as 0x-prefixed hex, `None` without calldata
+    pub selector: Option<String>,
+    pub gas_used: u64,
+}
+
+/// Gas used by every genesis transaction, written as `gas_report.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasReport {
+    pub transactions: Vec<GasReportEntry>,
+    pub total_gas_used: u64,
+}
+
+impl GasReport {
+    /// Pair each transaction with its label and execution result
+    pub fn new(labels: &[String], txs: &[TxEnv], results: &[ExecutionResult]) -> Self {
+        let transactions: Vec<GasReportEntry> = labels
+            .iter()
+            .zip(txs)
+            .zip(results)
+            .map(|((label, tx), result)| GasReportEntry {
+                label: label.clone(),
+                target: match tx.transact_to {
+                    TxKind::Call(address) => Some(address),
+                    TxKind::Create => None,
+                },
+                selector: tx
+                    .data
+                    .get(0..4)
+                    .map(|selector| format!("0x{}", hex::encode(selector))),
+                gas_used: result.gas_used(),
+            })
+            .collect();
+        let total_gas_used = transactions.iter().map(|entry| entry.gas_used).sum();
+        Self {
+            transactions,
+            total_gas_used,
+        }
+    }
+
+    pub fn write(&self, output_dir: &str) -> std::io::Result<()> {
+        let file = File::create(format!("{output_dir}/gas_report.json"))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::{Bytes, Output, SuccessReason, address};
+
+    fn success(gas_used: u64) -> ExecutionResult {
+        ExecutionResult::Success {
+            reason: SuccessReason::Stop,
+            gas_used,
+            gas_refunded: 0,
+            logs: vec![],
+            output: Output::Call(Bytes::new()),
+        }
+    }
+
+    #[test]
+    fn test_gas_report_entries_and_total() {
+        let target = address!("0000000000000000000000000000000000002008");
+        let txs = vec![
+            TxEnv {
+                transact_to: TxKind::Call(target),
+                data: Bytes::from(vec![0x12, 0x34, 0x56, 0x78, 0x00]),
+                ..Default::default()
+            },
+            TxEnv {
+                transact_to: TxKind::Create,
+                ..Default::default()
+            },
+        ];
+        let labels = vec!["Genesis.initialize".to_string(), "create".to_string()];
+        let report = GasReport::new(&labels, &txs, &[success(21_000), success(500)]);
+
+        assert_eq!(report.total_gas_used, 21_500);
+        assert_eq!(report.transactions.len(), 2);
+        assert_eq!(report.transactions[0].label, "Genesis.initialize");
+        assert_eq!(report.transactions[0].target, Some(target));
+        assert_eq!(
+            report.transactions[0].selector.as_deref(),
+            Some("0x12345678")
+        );
+        assert_eq!(report.transactions[1].target, None);
+        assert_eq!(report.transactions[1].selector, None);
+    }
+}
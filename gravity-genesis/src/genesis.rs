@@ -1,15 +1,24 @@
 use alloy_sol_macro::sol;
-use alloy_sol_types::SolCall;
-use revm_primitives::{Address, Bytes, ExecutionResult, FixedBytes, TxEnv, U256, hex};
+use alloy_sol_types::{SolCall, SolInterface};
+use revm::{DatabaseRef, db::BundleState};
+use revm_primitives::{Address, Bytes, ExecutionResult, TxEnv, U256, hex};
 use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt};
 use tracing::{error, info};
 
 use crate::{
-    post_genesis::handle_execution_result,
-    utils::{EPOCH_MANAGER_ADDR, VALIDATOR_MANAGER_ADDR, new_system_call_txn},
+    config_validation::{format_config_errors, parse_config, validate_config},
+    context::RunContext,
+    execute::BlockContext,
+    post_genesis::{
+        VerificationReport, VerifyMode, execute_view_call, handle_execution_result,
+        successful_output,
+    },
+    randomness::RandomnessConfig,
+    utils::new_system_call_txn,
 };
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct GenesisConfig {
     #[serde(rename = "validatorAddresses")]
     pub validator_addresses: Vec<String>,
@@ -23,6 +32,146 @@ pub struct GenesisConfig {
     pub fullnode_network_addresses: Vec<String>,
     #[serde(rename = "aptosAddresses")]
     pub aptos_addresses: Vec<String>,
+    /// Bit width the total voting power, in whole tokens, must fit in
+    #[serde(
+        rename = "maxTotalVotingPowerBits",
+        default = "default_max_total_voting_power_bits"
+    )]
+    pub max_total_voting_power_bits: u32,
+    /// Unit of `votingPowers`; whole tokens unless set to `wei`
+    #[serde(
+        rename = "votingPowerUnit",
+        default,
+        skip_serializing_if = "VotingPowerUnit::is_ether"
+    )]
+    pub voting_power_unit: VotingPowerUnit,
+    /// Decimals of the native token: a whole token is 10^decimals wei
+    #[serde(
+        default = "default_decimals",
+        skip_serializing_if = "is_default_decimals"
+    )]
+    pub decimals: u32,
+    /// Pass consensus public keys to the contracts as their raw UTF-8 bytes
+    /// instead of hex-decoding them
+    #[serde(rename = "rawConsensusKeys", default)]
+    pub raw_consensus_keys: bool,
+    /// Externally-owned accounts funded at genesis
+    #[serde(rename = "prefundedAccounts", default)]
+    pub prefunded_accounts: Vec<PrefundedAccount>,
+    /// Validator names, parallel to `validatorAddresses`; empty for the default `VAL<index>`
+    #[serde(default)]
+    pub monikers: Vec<String>,
+    /// Validator commissions, parallel to `validatorAddresses`; empty for [`DEFAULT_COMMISSION`]
+    #[serde(default)]
+    pub commissions: Vec<CommissionConfig>,
+    /// Randomness thresholds replacing the `Genesis.initialize` defaults
+    #[serde(
+        rename = "randomnessConfig",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub randomness_config: Option<RandomnessConfig>,
+}
+
+/// Unit the `votingPowers` of a config are given in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VotingPowerUnit {
+    /// Whole tokens, multiplied by 10^decimals before they are passed to the contracts
+    #[default]
+    Ether,
+    /// Wei, passed to the contracts as is
+    Wei,
+}
+
+/// Wei per whole token of an 18-decimal token
+pub const WEI_PER_TOKEN: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// Decimals of the native token unless a config sets `decimals`
+pub const DEFAULT_DECIMALS: u32 = 18;
+
+/// Largest `decimals` a config may set
+pub const MAX_DECIMALS: u32 = 36;
+
+fn default_decimals() -> u32 {
+    DEFAULT_DECIMALS
+}
+
+fn is_default_decimals(decimals: &u32) -> bool {
+    *decimals == DEFAULT_DECIMALS
+}
+
+/// Wei per whole token of a token with `decimals` decimals, `None` if it overflows a `uint256`
+pub fn wei_per_token(decimals: u32) -> Option<U256> {
+    U256::from(10).checked_pow(U256::from(decimals))
+}
+
+impl VotingPowerUnit {
+    fn is_ether(&self) -> bool {
+        *self == Self::Ether
+    }
+
+    /// `power` in wei for a token with `decimals` decimals, `None` if it overflows a `uint256`
+    pub fn to_wei(self, power: U256, decimals: u32) -> Option<U256> {
+        match self {
+            Self::Ether => power.checked_mul(wei_per_token(decimals)?),
+            Self::Wei => Some(power),
+        }
+    }
+}
+
+impl fmt::Display for VotingPowerUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ether => write!(f, "ether"),
+            Self::Wei => write!(f, "wei"),
+        }
+    }
+}
+
+/// Commission terms of a validator, in basis points (10000 is 100%)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CommissionConfig {
+    pub rate: u64,
+    #[serde(rename = "maxRate")]
+    pub max_rate: u64,
+    #[serde(rename = "maxChangeRate")]
+    pub max_change_rate: u64,
+}
+
+impl fmt::Display for CommissionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rate {} maxRate {} maxChangeRate {}",
+            self.rate, self.max_rate, self.max_change_rate
+        )
+    }
+}
+
+/// Commission `ValidatorManager` gives genesis validators without one: 0%, at most 50%, changing by at most 5%
+pub const DEFAULT_COMMISSION: CommissionConfig = CommissionConfig {
+    rate: 0,
+    max_rate: 5000,
+    max_change_rate: 500,
+};
+
+/// Largest commission `maxRate`, 50% in basis points, as `StakeConfig.MAX_COMMISSION_RATE`
+pub const MAX_COMMISSION_RATE: u64 = 5_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PrefundedAccount {
+    pub address: String,
+    /// Balance in wei, as a decimal or 0x-prefixed hex string
+    pub balance: String,
+}
+
+/// `DKG` narrows each validator's voting power, in whole tokens, to `uint64`,
+/// so by default the total must fit in 64 bits
+pub const DEFAULT_MAX_TOTAL_VOTING_POWER_BITS: u32 = 64;
+
+fn default_max_total_voting_power_bits() -> u32 {
+    DEFAULT_MAX_TOTAL_VOTING_POWER_BITS
 }
 
 pub struct GenesisInitParam {
@@ -32,123 +181,242 @@ pub struct GenesisInitParam {
     pub validator_network_addresses: Vec<Bytes>,
     pub fullnode_network_addresses: Vec<Bytes>,
     pub aptos_addresses: Vec<Bytes>,
+    /// As configured, empty for the contract defaults
+    pub monikers: Vec<String>,
+    /// As configured, empty for the contract defaults
+    pub commissions: Vec<CommissionConfig>,
 }
 
-fn bytes_to_fixed32(bytes: &Bytes) -> Result<FixedBytes<32>, &'static str> {
-    if bytes.len() != 32 {
-        return Err("bytes length is not 32");
-    }
+/// Check that the summed voting power fits in `max_bits` bits
+///
+/// `voting_powers` are in wei, the bound applies to whole tokens of a token
+/// with `decimals` decimals. Returns a warning message when the total exceeds
+/// the bound.
+pub fn check_total_voting_power(
+    voting_powers: &[U256],
+    max_bits: u32,
+    decimals: u32,
+) -> Option<String> {
+    let total_wei = voting_powers
+        .iter()
+        .fold(U256::ZERO, |total, power| total.saturating_add(*power));
+    let total = total_wei / wei_per_token(decimals).unwrap_or(U256::MAX);
+    let max = if max_bits >= 256 {
+        U256::MAX
+    } else {
+        (U256::from(1) << max_bits as usize) - U256::from(1)
+    };
+    (total > max).then(|| {
+        format!(
+            "total voting power {} exceeds the {}-bit bound {}",
+            total, max_bits, max
+        )
+    })
+}
 
-    let fixed = FixedBytes::<32>::try_from(bytes.as_ref())
-        .map_err(|_| "failed to convert to FixedBytes<32>")?;
+/// Byte lengths accepted for a consensus public key: a compressed or an uncompressed BLS12-381 G1 point
+pub const BLS_PUBLIC_KEY_LENGTHS: [usize; 2] = [48, 96];
 
-    Ok(fixed)
+/// Convert one configured consensus public key to the bytes passed to `Genesis.initialize`
+///
+/// The key is hex-decoded, with an optional 0x prefix, and must be 48 or 96
+/// bytes long. With `raw` the key string is passed through as UTF-8 bytes.
+/// An empty key is rejected either way, as it would register a validator
+/// without a consensus key.
+pub fn parse_consensus_public_key(key: &str, raw: bool) -> Result<Bytes, String> {
+    if key.is_empty() {
+        return Err("is empty".to_string());
+    }
+    if raw {
+        return Ok(key.as_bytes().to_vec().into());
+    }
+    let bytes = hex::decode(key).map_err(|e| format!("is not hex: {}", e))?;
+    if !BLS_PUBLIC_KEY_LENGTHS.contains(&bytes.len()) {
+        return Err(format!("is {} bytes, expected 48 or 96", bytes.len()));
+    }
+    Ok(bytes.into())
 }
 
-pub fn parse_genesis_config(config: &GenesisConfig) -> GenesisInitParam {
-    // Convert string addresses to Address type
-    let validator_addresses: Vec<Address> = config
-        .validator_addresses
-        .iter()
-        .map(|addr| addr.parse::<Address>().expect("Invalid validator address"))
-        .collect();
-    info!("validator addresses: {:?}", validator_addresses);
+/// Decode a BCS-encoded network address back into its `/ip4/...` string form
+///
+/// Empty bytes decode to the empty string, as written for an empty configured address.
+pub fn decode_network_address(bytes: &[u8]) -> Result<String, String> {
+    if bytes.is_empty() {
+        return Ok(String::new());
+    }
+    bcs::from_bytes(bytes).map_err(|e| format!("not a BCS-encoded network address: {}", e))
+}
 
-    // Convert consensus public keys from hex strings to bytes
-    let consensus_public_keys: Vec<Bytes> = config
-        .consensus_public_keys
-        .iter()
-        .map(|key| {
-            // GApots would use the following code
-            // let public_key = bls12381::PublicKey::try_from(
-            //     hex::decode(node_config.consensus_public_key.as_bytes()).unwrap().as_slice(),
-            // )
-            key.as_bytes().to_vec().into()
-            // bcs::to_bytes(&key_str).unwrap().into()
-        })
-        .collect();
+/// Render a BCS-encoded network address for logs and comparisons
+///
+/// Bytes that do not decode are shown as hex so they remain comparable.
+pub fn display_network_address(bytes: &[u8]) -> String {
+    decode_network_address(bytes).unwrap_or_else(|_| format!("0x{}", hex::encode(bytes)))
+}
 
-    let voting_powers: Vec<U256> = config
-        .voting_powers
-        .iter()
-        .map(|power| {
-            let power_ether = power.parse::<U256>().expect("Invalid voting power");
-            // Convert from ether to wei (1 ether = 10^18 wei)
-            power_ether * U256::from(10).pow(U256::from(18))
-        })
-        .collect();
+/// Combine config fragments, in order, into one config
+///
+/// Validator vectors and prefunded accounts are concatenated. Monikers and
+/// commissions left out of a fragment are filled with the defaults
+/// `ValidatorManager` would assign, so fragments may mix both styles. The
+/// scalar settings must agree: `rawConsensusKeys`, `votingPowerUnit` and `decimals` everywhere, and
+/// `maxTotalVotingPowerBits` wherever it differs from the default.
+pub fn merge_genesis_configs(
+    fragments: Vec<(String, GenesisConfig)>,
+) -> Result<GenesisConfig, String> {
+    let Some((_, first)) = fragments.first() else {
+        return Err("no genesis config given".to_string());
+    };
+    let default_bits = default_max_total_voting_power_bits();
+    let with_monikers = fragments.iter().any(|(_, f)| !f.monikers.is_empty());
+    let with_commissions = fragments.iter().any(|(_, f)| !f.commissions.is_empty());
+    let mut merged = GenesisConfig {
+        validator_addresses: Vec::new(),
+        consensus_public_keys: Vec::new(),
+        voting_powers: Vec::new(),
+        validator_network_addresses: Vec::new(),
+        fullnode_network_addresses: Vec::new(),
+        aptos_addresses: Vec::new(),
+        max_total_voting_power_bits: default_bits,
+        voting_power_unit: first.voting_power_unit,
+        decimals: first.decimals,
+        raw_consensus_keys: first.raw_consensus_keys,
+        prefunded_accounts: Vec::new(),
+        monikers: Vec::new(),
+        commissions: Vec::new(),
+        randomness_config: None,
+    };
 
-    // Convert validator network addresses from hex strings to bytes
-    let validator_network_addresses: Vec<Bytes> = config
-        .validator_network_addresses
-        .iter()
-        .map(|addr| {
-            if addr.is_empty() {
-                Bytes::new()
-            } else {
-                // GAptos would use the following code
-                // let address_string: String = bcs::from_bytes(&address_bytes).unwrap();
-                // println!("address_string: {:?}", address_string);
-                // let validator_network_address: NetworkAddress =
-                //     NetworkAddress::from_str(&address_string).unwrap();
-                bcs::to_bytes(&addr).unwrap().into()
+    for (name, mut fragment) in fragments {
+        if fragment.raw_consensus_keys != merged.raw_consensus_keys {
+            return Err(format!(
+                "{}: rawConsensusKeys is {} but an earlier fragment has {}",
+                name, fragment.raw_consensus_keys, merged.raw_consensus_keys
+            ));
+        }
+        if fragment.voting_power_unit != merged.voting_power_unit {
+            return Err(format!(
+                "{}: votingPowerUnit is {} but an earlier fragment has {}",
+                name, fragment.voting_power_unit, merged.voting_power_unit
+            ));
+        }
+        if fragment.decimals != merged.decimals {
+            return Err(format!(
+                "{}: decimals is {} but an earlier fragment has {}",
+                name, fragment.decimals, merged.decimals
+            ));
+        }
+        if fragment.max_total_voting_power_bits != default_bits {
+            if merged.max_total_voting_power_bits != default_bits
+                && merged.max_total_voting_power_bits != fragment.max_total_voting_power_bits
+            {
+                return Err(format!(
+                    "{}: maxTotalVotingPowerBits is {} but an earlier fragment has {}",
+                    name, fragment.max_total_voting_power_bits, merged.max_total_voting_power_bits
+                ));
             }
-        })
-        .collect();
-
-    // Convert fullnode network addresses from hex strings to bytes
-    let fullnode_network_addresses: Vec<Bytes> = config
-        .fullnode_network_addresses
-        .iter()
-        .map(|addr| {
-            if addr.is_empty() {
-                Bytes::new()
-            } else {
-                bcs::to_bytes(&addr).unwrap().into()
+            merged.max_total_voting_power_bits = fragment.max_total_voting_power_bits;
+        }
+        if let Some(randomness_config) = fragment.randomness_config.take() {
+            if merged
+                .randomness_config
+                .as_ref()
+                .is_some_and(|merged| *merged != randomness_config)
+            {
+                return Err(format!(
+                    "{}: randomnessConfig differs from the one of an earlier fragment",
+                    name
+                ));
             }
-        })
-        .collect();
-
-    let aptos_addresses: Vec<Bytes> = config
-        .aptos_addresses
-        .iter()
-        .map(|addr| {
-            let bytes: [u8; 32] = hex::decode(addr).unwrap().try_into().unwrap();
-            bytes.into()
-        })
-        .collect();
-
-    let address_from_aptos_address: Vec<Address> = aptos_addresses
-        .iter()
-        .map(|addr| {
-            let address = Address::from_word(bytes_to_fixed32(addr).unwrap());
-            address
-        })
-        .collect();
+            merged.randomness_config = Some(randomness_config);
+        }
+        validate_config(&fragment)
+            .map_err(|errors| format!("{}: {}", name, format_config_errors(&errors)))?;
 
-    for i in 0..validator_addresses.len() {
-        if validator_addresses[i] != address_from_aptos_address[i] {
-            panic!(
-                "❌ Validator address mismatch! Expected: {:?}, Actual: {:?}",
-                validator_addresses[i], address_from_aptos_address[i]
-            );
+        let offset = merged.validator_addresses.len();
+        let validator_count = fragment.validator_addresses.len();
+        if with_monikers && fragment.monikers.is_empty() {
+            fragment.monikers = (offset..offset + validator_count)
+                .map(|i| format!("VAL{}", i))
+                .collect();
         }
+        if with_commissions && fragment.commissions.is_empty() {
+            fragment.commissions = vec![DEFAULT_COMMISSION; validator_count];
+        }
+
+        merged
+            .validator_addresses
+            .append(&mut fragment.validator_addresses);
+        merged
+            .consensus_public_keys
+            .append(&mut fragment.consensus_public_keys);
+        merged.voting_powers.append(&mut fragment.voting_powers);
+        merged
+            .validator_network_addresses
+            .append(&mut fragment.validator_network_addresses);
+        merged
+            .fullnode_network_addresses
+            .append(&mut fragment.fullnode_network_addresses);
+        merged.aptos_addresses.append(&mut fragment.aptos_addresses);
+        merged
+            .prefunded_accounts
+            .append(&mut fragment.prefunded_accounts);
+        merged.monikers.append(&mut fragment.monikers);
+        merged.commissions.append(&mut fragment.commissions);
     }
+    Ok(merged)
+}
 
-    GenesisInitParam {
-        validator_addresses,
-        consensus_public_keys,
-        voting_powers,
-        validator_network_addresses,
-        fullnode_network_addresses,
-        aptos_addresses,
+/// Moniker `ValidatorManager` assigns validator `i`
+pub fn validator_moniker(config: &GenesisConfig, i: usize) -> String {
+    config
+        .monikers
+        .get(i)
+        .cloned()
+        .unwrap_or_else(|| format!("VAL{}", i))
+}
+
+/// Commission `ValidatorManager` assigns validator `i`
+pub fn validator_commission(config: &GenesisConfig, i: usize) -> CommissionConfig {
+    config
+        .commissions
+        .get(i)
+        .copied()
+        .unwrap_or(DEFAULT_COMMISSION)
+}
+
+/// Convert `config` into the arguments of `Genesis.initialize`
+///
+/// Checking is left to [`parse_config`], so every problem of an invalid
+/// config is reported.
+pub fn parse_genesis_config(config: &GenesisConfig) -> Result<GenesisInitParam, String> {
+    let param = parse_config(config)
+        .map_err(|errors| format_config_errors(&errors))?
+        .init_param;
+    info!("validator addresses: {:?}", param.validator_addresses);
+    Ok(param)
+}
+
+/// Name of a validator status as declared in `IValidatorManager`
+pub fn validator_status_name(status: IValidatorManager::ValidatorStatus) -> &'static str {
+    match status {
+        IValidatorManager::ValidatorStatus::PENDING_ACTIVE => "PENDING_ACTIVE",
+        IValidatorManager::ValidatorStatus::ACTIVE => "ACTIVE",
+        IValidatorManager::ValidatorStatus::PENDING_INACTIVE => "PENDING_INACTIVE",
+        IValidatorManager::ValidatorStatus::INACTIVE => "INACTIVE",
+        _ => "unknown",
     }
 }
 
+/// Compare the returned active validators against the genesis config
+///
+/// Every compared field is recorded in the returned report. In
+/// [`VerifyMode::FailFast`] recording stops at the first mismatch.
 pub fn validate_genesis_data_consistency(
     config: &GenesisConfig,
     active_validators: &[IValidatorManager::ValidatorInfo],
-) {
+    mode: VerifyMode,
+) -> VerificationReport {
     info!("=== Validating Genesis Initial Data Consistency with ValidatorSet Return Data ===");
 
     let GenesisInitParam {
@@ -158,145 +426,123 @@ pub fn validate_genesis_data_consistency(
         validator_network_addresses,
         fullnode_network_addresses,
         aptos_addresses,
-    } = parse_genesis_config(config);
-    let expected_count = validator_addresses.len();
-    let actual_count = active_validators.len();
-
-    info!("Expected validator count: {}", expected_count);
-    info!("Actual validator count: {}", actual_count);
+        ..
+    } = match parse_genesis_config(config) {
+        Ok(param) => param,
+        Err(e) => {
+            let mut report = VerificationReport::new(mode);
+            report.record("genesis config", "valid", e, false);
+            return report;
+        }
+    };
+    let mut report = VerificationReport::new(mode);
+    let mut stake_credit_owners: HashMap<Address, usize> = HashMap::new();
 
-    if expected_count != actual_count {
-        error!(
-            "❌ Validator count mismatch! Expected: {}, Actual: {}",
-            expected_count, actual_count
-        );
-        return;
+    if !report.check_eq(
+        "validator count",
+        validator_addresses.len(),
+        active_validators.len(),
+    ) {
+        return report;
     }
 
-    let mut all_match = true;
-
     for (i, validator) in active_validators.iter().enumerate() {
         info!("--- Validating Validator {} ---", i + 1);
+        info!("Status: {}", validator_status_name(validator.status));
 
-        // Validate operator address
-        let expected_operator = validator_addresses[i];
-        let actual_operator = validator.operator;
-
-        if expected_operator == actual_operator {
-            info!("✅ Operator address matches: {:?}", actual_operator);
-        } else {
-            error!(
-                "❌ Operator address mismatch! Expected: {:?}, Actual: {:?}",
-                expected_operator, actual_operator
-            );
-            all_match = false;
-        }
-
-        let expected_aptos_address = aptos_addresses[i].clone();
-        let actual_aptos_address = validator.aptosAddress.to_vec();
-        if expected_aptos_address == actual_aptos_address {
-            info!(
-                "✅ Aptos address matches: 0x{}",
-                hex::encode(&actual_aptos_address)
-            );
-        } else {
-            error!("❌ Aptos address mismatch!");
-            error!("Expected: 0x{}", hex::encode(&expected_aptos_address));
-            error!("Actual: 0x{}", hex::encode(&actual_aptos_address));
-            all_match = false;
-        }
-
-        // Validate consensus public key
-        let expected_consensus_key = consensus_public_keys[i].clone();
-        let actual_consensus_key = validator.consensusPublicKey.to_vec();
-
-        if expected_consensus_key == actual_consensus_key {
-            info!(
-                "✅ Consensus public key matches (length: {} bytes)",
-                expected_consensus_key.len()
-            );
-        } else {
-            error!("❌ Consensus public key mismatch!");
-            error!("Expected: 0x{}", hex::encode(&expected_consensus_key));
-            error!("Actual: 0x{}", hex::encode(&actual_consensus_key));
-            all_match = false;
-        }
-
-        // Validate voting power
-        let expected_voting_power = voting_powers[i];
-        let actual_voting_power = validator.votingPower;
-
-        if expected_voting_power == actual_voting_power {
-            info!("✅ Voting power matches: {}", actual_voting_power);
-        } else {
-            error!(
-                "❌ Voting power mismatch! Expected: {}, Actual: {}",
-                expected_voting_power, actual_voting_power
-            );
-            all_match = false;
-        }
-
-        // Validate validator network addresses
-        let expected_validator_network_addr = validator_network_addresses[i].clone();
-        let actual_validator_network_addr = validator.validatorNetworkAddresses.to_vec();
-
-        if expected_validator_network_addr == actual_validator_network_addr {
-            info!(
-                "✅ Validator network addresses match (length: {} bytes)",
-                expected_validator_network_addr.len()
-            );
-        } else {
-            error!("❌ Validator network addresses mismatch!");
-            error!(
-                "Expected: {:?}",
-                String::from_utf8_lossy(&expected_validator_network_addr)
-            );
-            error!(
-                "Actual: {:?}",
-                String::from_utf8_lossy(&actual_validator_network_addr)
-            );
-            all_match = false;
-        }
-
-        // Validate fullnode network addresses
-        let expected_fullnode_network_addr = fullnode_network_addresses[i].clone();
-        let actual_fullnode_network_addr = validator.fullnodeNetworkAddresses.to_vec();
+        // Every genesis validator joins the active set directly
+        report.check_eq(
+            format!("validator {} status", i),
+            validator_status_name(IValidatorManager::ValidatorStatus::ACTIVE),
+            validator_status_name(validator.status),
+        );
 
-        if expected_fullnode_network_addr == actual_fullnode_network_addr {
-            info!(
-                "✅ Fullnode network addresses match (length: {} bytes)",
-                expected_fullnode_network_addr.len()
-            );
+        report.check_eq(
+            format!("validator {} operator address", i),
+            validator_addresses[i],
+            validator.operator,
+        );
+        report.check_eq(
+            format!("validator {} aptos address", i),
+            &aptos_addresses[i],
+            &validator.aptosAddress,
+        );
+        report.check_eq(
+            format!("validator {} consensus public key", i),
+            &consensus_public_keys[i],
+            &validator.consensusPublicKey,
+        );
+        report.check_eq(
+            format!("validator {} voting power", i),
+            voting_powers[i],
+            validator.votingPower,
+        );
+        report.check_eq(
+            format!("validator {} validator network addresses", i),
+            display_network_address(&validator_network_addresses[i]),
+            display_network_address(&validator.validatorNetworkAddresses),
+        );
+        report.check_eq(
+            format!("validator {} fullnode network addresses", i),
+            display_network_address(&fullnode_network_addresses[i]),
+            display_network_address(&validator.fullnodeNetworkAddresses),
+        );
+        report.check_eq(
+            format!("validator {} moniker", i),
+            validator_moniker(config, i),
+            validator.moniker.clone(),
+        );
+        report.check_eq(
+            format!("validator {} commission", i),
+            validator_commission(config, i),
+            CommissionConfig {
+                rate: validator.commission.rate,
+                max_rate: validator.commission.maxRate,
+                max_change_rate: validator.commission.maxChangeRate,
+            },
+        );
+        // ValidatorManager deploys a StakeCredit proxy per validator; a zero or
+        // shared address means a deployment failed or two validators collided
+        let stake_credit = validator.stakeCreditAddress;
+        let problem = if stake_credit.is_zero() {
+            Some("zero address".to_string())
         } else {
-            error!("❌ Fullnode network addresses mismatch!");
-            error!(
-                "Expected: {:?}",
-                String::from_utf8_lossy(&expected_fullnode_network_addr)
-            );
-            error!(
-                "Actual: {:?}",
-                String::from_utf8_lossy(&actual_fullnode_network_addr)
-            );
-            all_match = false;
-        }
+            stake_credit_owners
+                .insert(stake_credit, i)
+                .map(|first| format!("shared with validator {}", first))
+        };
+        report.record(
+            format!("validator {} stake credit address", i),
+            "non-zero and unique",
+            match &problem {
+                Some(problem) => format!("{} ({})", stake_credit, problem),
+                None => stake_credit.to_string(),
+            },
+            problem.is_none(),
+        );
 
         info!(""); // Empty line separator
     }
 
-    if all_match {
+    if report.passed() {
         info!(
             "🎉 All validator data validation passed! Genesis initialization data is completely consistent with ValidatorSet return data."
         );
     } else {
         error!("⚠️  Data inconsistency found, please check the error messages above.");
     }
+    report
 }
 
-pub fn call_genesis_initialize(genesis_address: Address, config: &GenesisConfig) -> TxEnv {
-    let param = parse_genesis_config(config);
+pub fn call_genesis_initialize(
+    caller: Address,
+    genesis_address: Address,
+    config: &GenesisConfig,
+) -> Result<TxEnv, String> {
+    let param = parse_genesis_config(config)?;
 
     info!("=== Genesis Initialize Parameters ===");
-    info!("Genesis address: {:?}", genesis_address);
+    info!("Genesis address: {}", genesis_address);
     info!("Validator addresses: {:?}", param.validator_addresses);
     info!(
         "Consensus public keys count: {}",
@@ -312,16 +558,26 @@ pub fn call_genesis_initialize(genesis_address: Address, config: &GenesisConfig)
         param.fullnode_network_addresses.len()
     );
     info!("Aptos addresses count: {}", param.aptos_addresses.len());
+    info!("Monikers: {:?}", param.monikers);
+    info!("Commissions: {:?}", param.commissions);
 
     sol! {
         contract Genesis {
+            struct Commission {
+                uint64 rate;
+                uint64 maxRate;
+                uint64 maxChangeRate;
+            }
+
             function initialize(
                 address[] calldata validatorAddresses,
                 bytes[] calldata consensusPublicKeys,
                 uint256[] calldata votingPowers,
                 bytes[] calldata validatorNetworkAddresses,
                 bytes[] calldata fullnodeNetworkAddresses,
-                bytes[] calldata aptosAddresses
+                bytes[] calldata aptosAddresses,
+                string[] calldata monikers,
+                Commission[] calldata commissions
             ) external;
         }
     }
@@ -333,14 +589,109 @@ pub fn call_genesis_initialize(genesis_address: Address, config: &GenesisConfig)
         validatorNetworkAddresses: param.validator_network_addresses,
         fullnodeNetworkAddresses: param.fullnode_network_addresses,
         aptosAddresses: param.aptos_addresses,
+        monikers: param.monikers,
+        commissions: param
+            .commissions
+            .iter()
+            .map(|commission| Genesis::Commission {
+                rate: commission.rate,
+                maxRate: commission.max_rate,
+                maxChangeRate: commission.max_change_rate,
+            })
+            .collect(),
     }
     .abi_encode();
 
     info!("Call data length: {}", call_data.len());
     info!("Call data: 0x{}", hex::encode(&call_data));
 
-    let txn = new_system_call_txn(genesis_address, call_data.into());
-    txn
+    let txn = new_system_call_txn(caller, genesis_address, call_data.into());
+    Ok(txn)
+}
+
+sol! {
+    /// Errors `Genesis.initialize` most often reverts with, from `Genesis`,
+    /// `ValidatorManager` and `System`
+    interface IGenesisInitializeErrors {
+        error GenesisAlreadyCompleted();
+        error InvalidInitialValidators();
+        error ArrayLengthMismatch();
+        error AlreadyInitialized();
+        error InvalidVotingPower(uint256 votingPower);
+        error InvalidCommission();
+        error InvalidMoniker(string moniker);
+        error DuplicateMoniker(string moniker);
+        error OnlySystemCaller(address errorAddress);
+    }
+}
+
+/// Explain what a `Genesis.initialize` revert usually means and how to fix it
+///
+/// Returns `None` for revert data that is not one of the common genesis failures.
+pub fn genesis_initialize_hint(output: &[u8]) -> Option<String> {
+    use IGenesisInitializeErrors::IGenesisInitializeErrorsErrors as Errors;
+
+    let hint = match Errors::abi_decode(output, true).ok()? {
+        Errors::GenesisAlreadyCompleted(_) => {
+            "Genesis contract already initialized: the genesis transactions ran twice, \
+             e.g. on top of a --base-state that already contains a genesis"
+                .to_string()
+        }
+        Errors::InvalidInitialValidators(_) => {
+            "no initial validators: consensusPublicKeys is empty".to_string()
+        }
+        Errors::ArrayLengthMismatch(_) => {
+            "validator arrays length mismatch on-chain: validatorAddresses, consensusPublicKeys, \
+             votingPowers and the network addresses must all have one entry per validator, \
+             and monikers and commissions must be empty or have one too"
+                .to_string()
+        }
+        Errors::AlreadyInitialized(_) => {
+            "a system contract was already initialized: the genesis transactions ran twice, \
+             or the base state already holds an initialized contract"
+                .to_string()
+        }
+        Errors::InvalidVotingPower(e) => {
+            format!(
+                "a validator has invalid voting power {}: every voting power must be non-zero",
+                e.votingPower
+            )
+        }
+        Errors::InvalidCommission(_) => {
+            "invalid commission: rate and maxChangeRate must not exceed maxRate, \
+             and maxRate must not exceed 5000"
+                .to_string()
+        }
+        Errors::InvalidMoniker(e) => {
+            format!(
+                "invalid moniker {:?}: monikers are 3 to 9 letters or digits starting with \
+                 an uppercase letter",
+                e.moniker
+            )
+        }
+        Errors::DuplicateMoniker(e) => {
+            format!("moniker {:?} is used by more than one validator", e.moniker)
+        }
+        Errors::OnlySystemCaller(e) => {
+            format!(
+                "unauthorized caller {}: initialize must be sent by the SYSTEM_CALLER the \
+                 contracts were compiled with, check --system-caller",
+                e.errorAddress
+            )
+        }
+    };
+    Some(hint)
+}
+
+/// Name of the error refusing a second initialization that `output` holds, if it is one
+pub fn already_initialized_error(output: &[u8]) -> Option<&'static str> {
+    use IGenesisInitializeErrors::IGenesisInitializeErrorsErrors as Errors;
+
+    match Errors::abi_decode(output, true).ok()? {
+        Errors::GenesisAlreadyCompleted(_) => Some("GenesisAlreadyCompleted"),
+        Errors::AlreadyInitialized(_) => Some("AlreadyInitialized"),
+        _ => None,
+    }
 }
 
 sol! {
@@ -395,39 +746,602 @@ sol! {
         function getCurrentEpochInfo() external view returns (uint256 epoch, uint256 lastTransitionTime, uint256 duration);
     }
 }
-pub fn call_get_validator_set() -> TxEnv {
+pub fn call_get_validator_set(caller: Address, validator_manager: Address) -> TxEnv {
     let call_data = IValidatorManager::getValidatorSetCall {}.abi_encode();
-    new_system_call_txn(VALIDATOR_MANAGER_ADDR, call_data.into())
+    new_system_call_txn(caller, validator_manager, call_data.into())
 }
 
-pub fn call_get_current_epoch_info() -> TxEnv {
+pub fn call_get_current_epoch_info(caller: Address, epoch_manager: Address) -> TxEnv {
     let call_data = IEpochManager::getCurrentEpochInfoCall {}.abi_encode();
-    new_system_call_txn(EPOCH_MANAGER_ADDR, call_data.into())
+    new_system_call_txn(caller, epoch_manager, call_data.into())
+}
+
+/// Epoch `Genesis.initialize` leaves the chain in, as it triggers the first transition
+pub const GENESIS_EPOCH: u64 = 1;
+
+/// Epoch duration set by `EpochManager.initialize`, in microseconds
+pub const DEFAULT_EPOCH_INTERVAL_MICROS: u64 = 7_200_000_000;
+
+/// Epoch information returned by `EpochManager.getCurrentEpochInfo`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct EpochInfo {
+    pub epoch: U256,
+    /// Unix timestamp in seconds of the last epoch transition, by the `Timestamp` contract
+    pub last_transition_time: U256,
+    /// Epoch duration in microseconds
+    pub duration: U256,
+}
+
+impl EpochInfo {
+    /// The epoch info a generated genesis should report
+    ///
+    /// The first transition is triggered before any block has advanced the
+    /// `Timestamp` contract, so its time is `last_transition_time`, the
+    /// contract's clock at genesis.
+    pub fn at_genesis(last_transition_time: u64, interval_micros: u64) -> Self {
+        Self {
+            epoch: U256::from(GENESIS_EPOCH),
+            last_transition_time: U256::from(last_transition_time),
+            duration: U256::from(interval_micros),
+        }
+    }
+}
+
+/// Decode the return data of `ValidatorManager.getValidatorSet`
+pub fn decode_validator_set(output: &[u8]) -> Result<IValidatorManager::ValidatorSet, String> {
+    IValidatorManager::getValidatorSetCall::abi_decode_returns(output, false)
+        .map(|returns| returns._0)
+        .map_err(|e| format!("Failed to decode getValidatorSet output: {}", e))
+}
+
+/// Decode the return data of `EpochManager.getCurrentEpochInfo`
+pub fn decode_epoch_info(output: &[u8]) -> Result<EpochInfo, String> {
+    IEpochManager::getCurrentEpochInfoCall::abi_decode_returns(output, false)
+        .map(|returns| EpochInfo {
+            epoch: returns.epoch,
+            last_transition_time: returns.lastTransitionTime,
+            duration: returns.duration,
+        })
+        .map_err(|e| format!("Failed to decode getCurrentEpochInfo output: {}", e))
+}
+
+/// Call `ValidatorManager.getValidatorSet` on top of the genesis state
+pub fn query_validator_set(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    context: &RunContext,
+) -> Result<IValidatorManager::ValidatorSet, String> {
+    let result = execute_view_call(
+        db,
+        bundle_state,
+        block,
+        call_get_validator_set(caller, context.registry.validator_manager_addr()),
+        "getValidatorSet",
+        context,
+    )?;
+    decode_validator_set(&successful_output(&result, "getValidatorSet", context)?)
 }
 
-pub fn print_validator_set_result(result: &ExecutionResult, config: &GenesisConfig) {
+/// Call `EpochManager.getCurrentEpochInfo` on top of the genesis state
+pub fn query_epoch_info(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    context: &RunContext,
+) -> Result<EpochInfo, String> {
+    let result = execute_view_call(
+        db,
+        bundle_state,
+        block,
+        call_get_current_epoch_info(caller, context.registry.epoch_manager_addr()),
+        "getCurrentEpochInfo",
+        context,
+    )?;
+    decode_epoch_info(&successful_output(&result, "getCurrentEpochInfo", context)?)
+}
+
+pub fn print_validator_set_result(
+    result: &ExecutionResult,
+    config: &GenesisConfig,
+    mode: VerifyMode,
+) -> VerificationReport {
     handle_execution_result(result, "getValidatorSet", |output_bytes| {
-        let solidity_validator_set =
-            IValidatorManager::getValidatorSetCall::abi_decode_returns(output_bytes, false)
-                .unwrap();
+        let validator_set = match decode_validator_set(output_bytes) {
+            Ok(validator_set) => validator_set,
+            Err(e) => {
+                error!("{}", e);
+                return VerificationReport::call_failed(mode, "getValidatorSet");
+            }
+        };
 
-        let active_validators = &solidity_validator_set._0.activeValidators;
+        let active_validators = &validator_set.activeValidators;
         info!("Active validators count: {}", active_validators.len());
+        for (i, validator) in active_validators.iter().enumerate() {
+            info!(
+                "Validator {} {}: validator network address {:?}, fullnode network address {:?}",
+                i,
+                validator.operator,
+                display_network_address(&validator.validatorNetworkAddresses),
+                display_network_address(&validator.fullnodeNetworkAddresses)
+            );
+        }
 
         // Validate consistency between initial data and returned data
-        validate_genesis_data_consistency(config, active_validators);
-    });
+        validate_genesis_data_consistency(config, active_validators, mode)
+    })
+    .unwrap_or_else(|| VerificationReport::call_failed(mode, "getValidatorSet"))
+}
+
+pub fn print_current_epoch_info_result(
+    result: &ExecutionResult,
+    expected: &EpochInfo,
+    mode: VerifyMode,
+) -> VerificationReport {
+    handle_execution_result(
+        result,
+        "getCurrentEpochInfo",
+        |output_bytes| match decode_epoch_info(output_bytes) {
+            Ok(epoch_info) => {
+                info!(
+                    "Current epoch {}, last transition at {}s, duration {}us",
+                    epoch_info.epoch, epoch_info.last_transition_time, epoch_info.duration
+                );
+                let mut report = VerificationReport::new(mode);
+                report.check_eq("epoch", expected.epoch, epoch_info.epoch);
+                report.check_eq(
+                    "epoch last transition time (s)",
+                    expected.last_transition_time,
+                    epoch_info.last_transition_time,
+                );
+                report.check_eq(
+                    "epoch duration (us)",
+                    expected.duration,
+                    epoch_info.duration,
+                );
+                report
+            }
+            Err(e) => {
+                error!("{}", e);
+                VerificationReport::call_failed(mode, "getCurrentEpochInfo")
+            }
+        },
+    )
+    .unwrap_or_else(|| VerificationReport::call_failed(mode, "getCurrentEpochInfo"))
 }
 
-pub fn print_current_epoch_info_result(result: &ExecutionResult) {
-    handle_execution_result(result, "getCurrentEpochInfo", |output_bytes| {
-        let solidity_current_epoch_info =
-            IEpochManager::getCurrentEpochInfoCall::abi_decode_returns(output_bytes, false)
-                .unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_validation::ConfigError;
 
-        info!(
-            "Current epoch info: {:?}",
-            solidity_current_epoch_info.epoch
+    #[test]
+    fn test_genesis_initialize_hints() {
+        use IGenesisInitializeErrors::*;
+        use alloy_sol_types::SolError;
+
+        let hint = |output: Vec<u8>| genesis_initialize_hint(&output).unwrap();
+        assert!(
+            hint(ArrayLengthMismatch {}.abi_encode())
+                .starts_with("validator arrays length mismatch on-chain")
+        );
+        assert!(
+            hint(GenesisAlreadyCompleted {}.abi_encode())
+                .starts_with("Genesis contract already initialized")
+        );
+        assert_eq!(
+            already_initialized_error(&GenesisAlreadyCompleted {}.abi_encode()),
+            Some("GenesisAlreadyCompleted")
+        );
+        assert_eq!(
+            already_initialized_error(&ArrayLengthMismatch {}.abi_encode()),
+            None
         );
-    });
+        assert!(
+            hint(
+                OnlySystemCaller {
+                    errorAddress: Address::with_last_byte(1)
+                }
+                .abi_encode()
+            )
+            .contains(&Address::with_last_byte(1).to_string())
+        );
+        assert!(
+            hint(
+                InvalidMoniker {
+                    moniker: "east-0".to_string()
+                }
+                .abi_encode()
+            )
+            .starts_with("invalid moniker \"east-0\"")
+        );
+        assert_eq!(genesis_initialize_hint(&[]), None);
+        assert_eq!(genesis_initialize_hint(&[0xde, 0xad, 0xbe, 0xef]), None);
+    }
+
+    fn test_config() -> GenesisConfig {
+        serde_json::from_value(serde_json::json!({
+            "validatorAddresses": [
+                "0x1111111111111111111111111111111111111111",
+                "0x2222222222222222222222222222222222222222"
+            ],
+            "consensusPublicKeys": ["aa".repeat(48), "bb".repeat(48)],
+            "votingPowers": ["1", "2"],
+            "validatorNetworkAddresses": ["", ""],
+            "fullnodeNetworkAddresses": ["", ""],
+            "aptosAddresses": [
+                "0000000000000000000000001111111111111111111111111111111111111111",
+                "0000000000000000000000002222222222222222222222222222222222222222"
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_invalid_config_is_an_error() {
+        let mut config = test_config();
+        config.voting_powers[1] = "two".to_string();
+        let err = parse_genesis_config(&config).err().unwrap();
+        assert!(
+            err.starts_with(
+                "genesis config has 1 problems:\n  votingPowers[1] \"two\" is not a number"
+            ),
+            "{}",
+            err
+        );
+
+        let mut config = test_config();
+        config.aptos_addresses.swap(0, 1);
+        let err = parse_genesis_config(&config).err().unwrap();
+        assert!(
+            err.starts_with("genesis config has 2 problems:\n  validator 0 operator address"),
+            "{}",
+            err
+        );
+        assert!(
+            call_genesis_initialize(crate::utils::SYSTEM_CALLER, Address::ZERO, &config).is_err()
+        );
+    }
+
+    #[test]
+    fn test_total_voting_power_bound() {
+        let param = parse_genesis_config(&test_config()).unwrap();
+        assert_eq!(
+            check_total_voting_power(
+                &param.voting_powers,
+                DEFAULT_MAX_TOTAL_VOTING_POWER_BITS,
+                DEFAULT_DECIMALS
+            ),
+            None
+        );
+
+        let mut config = test_config();
+        config.voting_powers = vec![u64::MAX.to_string(), "1".to_string()];
+        let param = parse_genesis_config(&config).unwrap();
+        let warning = check_total_voting_power(
+            &param.voting_powers,
+            config.max_total_voting_power_bits,
+            config.decimals,
+        )
+        .expect("total above u64::MAX should warn");
+        assert!(warning.contains("18446744073709551616"), "{}", warning);
+        assert!(warning.contains("64-bit"), "{}", warning);
+
+        // A wider bound accepts the same total
+        assert_eq!(
+            check_total_voting_power(&param.voting_powers, 128, DEFAULT_DECIMALS),
+            None
+        );
+    }
+
+    #[test]
+    fn test_voting_power_units() {
+        let param = parse_genesis_config(&test_config()).unwrap();
+        assert_eq!(
+            param.voting_powers,
+            vec![WEI_PER_TOKEN, WEI_PER_TOKEN * U256::from(2)]
+        );
+
+        let mut json = serde_json::to_value(test_config()).unwrap();
+        json["votingPowerUnit"] = "wei".into();
+        json["votingPowers"] = serde_json::json!(["1500000000000000000", "7"]);
+        let config: GenesisConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.voting_power_unit, VotingPowerUnit::Wei);
+        let param = parse_genesis_config(&config).unwrap();
+        assert_eq!(
+            param.voting_powers,
+            vec![U256::from(1_500_000_000_000_000_000u64), U256::from(7)]
+        );
+
+        // Whole tokens of a 6-decimal token are scaled by 10^6
+        let mut config = test_config();
+        config.decimals = 6;
+        let param = parse_genesis_config(&config).unwrap();
+        assert_eq!(
+            param.voting_powers,
+            vec![U256::from(1_000_000), U256::from(2_000_000)]
+        );
+        assert_eq!(
+            check_total_voting_power(&param.voting_powers, 1, config.decimals),
+            Some("total voting power 3 exceeds the 1-bit bound 1".to_string())
+        );
+        assert_eq!(serde_json::to_value(&config).unwrap()["decimals"], 6);
+
+        // The default unit and decimals are left out, so existing configs keep their hash
+        let json = serde_json::to_value(test_config()).unwrap();
+        assert!(json.get("votingPowerUnit").is_none());
+        assert!(json.get("decimals").is_none());
+        assert_eq!(
+            serde_json::to_value(&config).unwrap()["votingPowerUnit"],
+            "wei"
+        );
+    }
+
+    /// Build the validator set the contracts would return for `config`
+    fn returned_validators(config: &GenesisConfig) -> Vec<IValidatorManager::ValidatorInfo> {
+        let param = parse_genesis_config(config).unwrap();
+        (0..param.validator_addresses.len())
+            .map(|i| IValidatorManager::ValidatorInfo {
+                consensusPublicKey: param.consensus_public_keys[i].clone(),
+                commission: {
+                    let commission = validator_commission(config, i);
+                    IValidatorManager::Commission {
+                        rate: commission.rate,
+                        maxRate: commission.max_rate,
+                        maxChangeRate: commission.max_change_rate,
+                    }
+                },
+                moniker: validator_moniker(config, i),
+                registered: true,
+                stakeCreditAddress: Address::with_last_byte(0x40 + i as u8),
+                status: IValidatorManager::ValidatorStatus::ACTIVE,
+                votingPower: param.voting_powers[i],
+                validatorIndex: U256::from(i),
+                updateTime: U256::ZERO,
+                operator: param.validator_addresses[i],
+                validatorNetworkAddresses: param.validator_network_addresses[i].clone(),
+                fullnodeNetworkAddresses: param.fullnode_network_addresses[i].clone(),
+                aptosAddress: param.aptos_addresses[i].clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matching_validator_set_has_no_mismatches() {
+        let config = test_config();
+        let validators = returned_validators(&config);
+        let report =
+            validate_genesis_data_consistency(&config, &validators, VerifyMode::CollectAll);
+        assert!(report.passed(), "unexpected failures: {:?}", report.checks);
+        assert_eq!(report.checks.len(), 1 + 2 * 10);
+    }
+
+    #[test]
+    fn test_validator_metadata_validation_and_comparison() {
+        let mut config = test_config();
+        assert_eq!(validate_config(&config), Ok(()));
+        assert_eq!(validator_moniker(&config, 1), "VAL1");
+        assert_eq!(validator_commission(&config, 1), DEFAULT_COMMISSION);
+
+        config.monikers = vec!["Alpha".to_string()];
+        assert_eq!(
+            validate_config(&config),
+            Err(vec![ConfigError::LengthMismatch {
+                field: "monikers",
+                len: 1,
+                validators: 2,
+            }])
+        );
+        config.monikers.push("Beta".to_string());
+
+        let commission = |rate, max_rate| CommissionConfig {
+            rate,
+            max_rate,
+            max_change_rate: 100,
+        };
+        config.commissions = vec![commission(1000, 2000), commission(3000, 2000)];
+        assert_eq!(
+            validate_config(&config),
+            Err(vec![ConfigError::InvalidCommission {
+                index: 1,
+                commission: commission(3000, 2000),
+            }])
+        );
+        config.commissions[1] = commission(0, 5_001);
+        assert!(validate_config(&config).is_err());
+        config.commissions[1] = commission(50, 50);
+        assert!(validate_config(&config).is_err());
+        config.commissions[1] = commission(5_000, 5_000);
+        assert_eq!(validate_config(&config), Ok(()));
+
+        // The configured values are what the returned validator set must hold
+        let mut validators = returned_validators(&config);
+        assert_eq!(validators[1].moniker, "Beta");
+        assert_eq!(validators[0].commission.maxRate, 2000);
+        validators[1].moniker = "VAL1".to_string();
+        let report =
+            validate_genesis_data_consistency(&config, &validators, VerifyMode::CollectAll);
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].field, "validator 1 moniker");
+    }
+
+    #[test]
+    fn test_genesis_validators_must_be_active() {
+        let config = test_config();
+        let mut validators = returned_validators(&config);
+        validators[1].status = IValidatorManager::ValidatorStatus::PENDING_ACTIVE;
+        let report =
+            validate_genesis_data_consistency(&config, &validators, VerifyMode::CollectAll);
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].field, "validator 1 status");
+        assert_eq!(failures[0].expected, "ACTIVE");
+        assert_eq!(failures[0].actual, "PENDING_ACTIVE");
+    }
+
+    #[test]
+    fn test_stake_credit_addresses_must_be_non_zero_and_unique() {
+        let config = test_config();
+        let mut validators = returned_validators(&config);
+        let shared = validators[0].stakeCreditAddress;
+        validators[1].stakeCreditAddress = shared;
+        let report =
+            validate_genesis_data_consistency(&config, &validators, VerifyMode::CollectAll);
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].field, "validator 1 stake credit address");
+        assert_eq!(
+            failures[0].actual,
+            format!("{} (shared with validator 0)", shared)
+        );
+
+        validators[1].stakeCreditAddress = Address::ZERO;
+        let report =
+            validate_genesis_data_consistency(&config, &validators, VerifyMode::CollectAll);
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].actual,
+            format!("{} (zero address)", Address::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_merge_genesis_config_fragments() {
+        let mut west = test_config();
+        west.validator_addresses = vec!["0x3333333333333333333333333333333333333333".to_string()];
+        west.consensus_public_keys = vec!["cc".repeat(48)];
+        west.voting_powers.truncate(1);
+        west.validator_network_addresses.truncate(1);
+        west.fullnode_network_addresses.truncate(1);
+        west.aptos_addresses =
+            vec!["0000000000000000000000003333333333333333333333333333333333333333".to_string()];
+        let mut east = test_config();
+        east.monikers = vec!["East0".to_string(), "East1".to_string()];
+        east.max_total_voting_power_bits = 128;
+
+        let merged = merge_genesis_configs(vec![
+            ("west.json".to_string(), west.clone()),
+            ("east.json".to_string(), east.clone()),
+        ])
+        .unwrap();
+        assert_eq!(merged.validator_addresses.len(), 3);
+        assert_eq!(merged.voting_powers, ["1", "1", "2"]);
+        assert_eq!(merged.monikers, ["VAL0", "East0", "East1"]);
+        assert!(merged.commissions.is_empty());
+        assert_eq!(merged.max_total_voting_power_bits, 128);
+        assert_eq!(validate_config(&merged), Ok(()));
+
+        east.voting_powers.pop();
+        let err = merge_genesis_configs(vec![
+            ("west.json".to_string(), west.clone()),
+            ("east.json".to_string(), east),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "east.json: genesis config has 1 problems:\n  votingPowers has 1 entries but there are 2 validators"
+        );
+
+        let mut raw = test_config();
+        raw.raw_consensus_keys = true;
+        let err = merge_genesis_configs(vec![
+            ("west.json".to_string(), west),
+            ("raw.json".to_string(), raw),
+        ])
+        .unwrap_err();
+        assert!(err.starts_with("raw.json: rawConsensusKeys"), "{}", err);
+    }
+
+    #[test]
+    fn test_view_call_results_are_decoded() {
+        let output = IEpochManager::getCurrentEpochInfoCall::abi_encode_returns(&(
+            U256::from(1),
+            U256::from(1_700_000_000),
+            U256::from(7200),
+        ));
+        assert_eq!(
+            decode_epoch_info(&output),
+            Ok(EpochInfo {
+                epoch: U256::from(1),
+                last_transition_time: U256::from(1_700_000_000),
+                duration: U256::from(7200),
+            })
+        );
+
+        let expected = EpochInfo::at_genesis(1_700_000_000, 7200);
+        let result = ExecutionResult::Success {
+            reason: revm_primitives::SuccessReason::Stop,
+            gas_used: 0,
+            gas_refunded: 0,
+            logs: Vec::new(),
+            output: revm_primitives::Output::Call(output.into()),
+        };
+        assert!(
+            print_current_epoch_info_result(&result, &expected, VerifyMode::CollectAll).passed()
+        );
+        let wrong_duration = EpochInfo {
+            duration: U256::from(3600),
+            ..expected
+        };
+        let report =
+            print_current_epoch_info_result(&result, &wrong_duration, VerifyMode::CollectAll);
+        let failures: Vec<&str> = report
+            .failures()
+            .map(|check| check.field.as_str())
+            .collect();
+        assert_eq!(failures, ["epoch duration (us)"]);
+
+        let config = test_config();
+        let output = IValidatorManager::getValidatorSetCall::abi_encode_returns(&(
+            IValidatorManager::ValidatorSet {
+                activeValidators: returned_validators(&config),
+                pendingInactive: Vec::new(),
+                pendingActive: Vec::new(),
+                totalVotingPower: U256::from(3),
+                totalJoiningPower: U256::ZERO,
+            },
+        ));
+        let validator_set = decode_validator_set(&output).unwrap();
+        assert_eq!(validator_set.activeValidators.len(), 2);
+        assert_eq!(validator_set.totalVotingPower, U256::from(3));
+
+        // Nothing is deployed, so the call succeeds with empty output that does not decode
+        let err = query_epoch_info(
+            revm::InMemoryDB::default(),
+            BundleState::default(),
+            &BlockContext::default(),
+            crate::utils::SYSTEM_CALLER,
+            &RunContext::default(),
+        )
+        .unwrap_err();
+        assert!(
+            err.starts_with("Failed to decode getCurrentEpochInfo"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_fail_fast_stops_at_first_mismatch() {
+        let config = test_config();
+        let mut validators = returned_validators(&config);
+        for validator in validators.iter_mut() {
+            validator.votingPower = U256::ZERO;
+            validator.consensusPublicKey = Bytes::new();
+        }
+
+        let fail_fast =
+            validate_genesis_data_consistency(&config, &validators, VerifyMode::FailFast);
+        let failures: Vec<_> = fail_fast.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].field, "validator 0 consensus public key");
+
+        let collect_all =
+            validate_genesis_data_consistency(&config, &validators, VerifyMode::CollectAll);
+        assert_eq!(collect_all.failures().count(), 4);
+    }
 }
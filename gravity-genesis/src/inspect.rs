@@ -0,0 +1,404 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Write},
+};
+
+use revm::db::{BundleAccount, BundleState, PlainAccount};
+use revm_primitives::{Address, B256, U256, hex};
+use serde::Deserialize;
+
+use crate::{codehash::find_contract_json, layout::AddressSlotHint, registry::ContractRegistry};
+
+/// Name, position and type of a value stored in a slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotLabel {
+    pub label: String,
+    /// Byte offset within the slot, counted from the lowest-order byte
+    pub offset: usize,
+    /// Solidity type, e.g. `address`, `uint64` or `contract IValidatorManager`
+    pub type_label: String,
+    /// Size of the value in bytes
+    pub size: usize,
+}
+
+/// Labels of the known values in each storage slot of one contract
+pub type SlotLabels = BTreeMap<U256, Vec<SlotLabel>>;
+
+#[derive(Deserialize)]
+struct StorageLayoutEntry {
+    label: String,
+    offset: usize,
+    slot: String,
+    #[serde(rename = "type")]
+    type_id: String,
+}
+
+#[derive(Deserialize)]
+struct StorageLayoutType {
+    label: String,
+    #[serde(rename = "numberOfBytes")]
+    number_of_bytes: String,
+}
+
+#[derive(Deserialize)]
+struct StorageLayout {
+    storage: Vec<StorageLayoutEntry>,
+    types: BTreeMap<String, StorageLayoutType>,
+}
+
+/// Load a `bundle_state.json` written by genesis generation
+pub fn read_bundle_state(path: &str) -> Result<BundleState, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+/// Resolve a contract name from the registry or a hex address
+pub fn resolve_address(
+    name_or_address: &str,
+    registry: &ContractRegistry,
+) -> Result<Address, String> {
+    if let Some(address) = registry.address_of(name_or_address) {
+        return Ok(address);
+    }
+    name_or_address.parse().map_err(|e| {
+        format!(
+            "{:?} is neither a registered contract nor an address: {}",
+            name_or_address, e
+        )
+    })
+}
+
+/// Label the slots named by address slot hints
+pub fn labels_from_hints(hints: &[AddressSlotHint]) -> SlotLabels {
+    let mut labels = SlotLabels::new();
+    for hint in hints {
+        labels.entry(hint.slot).or_default().push(SlotLabel {
+            label: hint.label.clone(),
+            offset: hint.offset,
+            type_label: "address".to_string(),
+            size: 20,
+        });
+    }
+    labels
+}
+
+/// Read a Foundry storage layout, either a whole artifact or its `storageLayout` object
+pub fn read_storage_layout(path: &str) -> Result<SlotLabels, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read storage layout {}: {}", path, e))?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse storage layout {}: {}", path, e))?;
+    if let Some(layout) = value.get_mut("storageLayout") {
+        value = layout.take();
+    }
+    let layout: StorageLayout = serde_json::from_value(value)
+        .map_err(|e| format!("{} is not a Foundry storage layout: {}", path, e))?;
+
+    let mut labels = SlotLabels::new();
+    for entry in layout.storage {
+        let slot = entry
+            .slot
+            .parse::<U256>()
+            .map_err(|e| format!("{}: invalid slot {:?}: {}", entry.label, entry.slot, e))?;
+        let (type_label, size) = match layout.types.get(&entry.type_id) {
+            Some(ty) => (ty.label.clone(), ty.number_of_bytes.parse().unwrap_or(32)),
+            None => (entry.type_id.clone(), 32),
+        };
+        labels.entry(slot).or_default().push(SlotLabel {
+            label: entry.label,
+            offset: entry.offset,
+            type_label,
+            size,
+        });
+    }
+    Ok(labels)
+}
+
+/// Read the storage layout of each registered contract under `layout_dir`
+///
+/// The layout of `Name` is a `Name.json` located with
+/// [`find_contract_json`], either a Foundry artifact built with
+/// `extra_output = ["storageLayout"]` or a bare `storageLayout` object.
+/// Contracts without one are left out of the result.
+pub fn read_storage_layouts(
+    layout_dir: &str,
+    registry: &ContractRegistry,
+) -> Result<BTreeMap<String, SlotLabels>, String> {
+    find_contract_json(layout_dir, registry)?
+        .into_iter()
+        .map(|(name, path)| Ok((name, read_storage_layout(&path.display().to_string())?)))
+        .collect()
+}
+
+/// Variables of each registered contract, written as `genesis_storage_labeled.json`
+pub type LabeledStorage = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Decode the genesis storage of every registered contract with its layout
+///
+/// Each variable of a contract's layout maps to its decoded value, including
+/// variables still zero at genesis. Slots no variable is laid out in map from
+/// their hex slot to the raw word; this includes the entries of mappings,
+/// which live at hashed slots.
+pub fn label_genesis_storage(
+    genesis_state: &HashMap<Address, PlainAccount>,
+    layouts: &BTreeMap<String, SlotLabels>,
+    registry: &ContractRegistry,
+) -> LabeledStorage {
+    let empty = SlotLabels::new();
+    let mut labeled = LabeledStorage::new();
+    for (contract_name, address) in registry.contracts() {
+        let Some(account) = genesis_state.get(address) else {
+            continue;
+        };
+        let labels = layouts.get(contract_name).unwrap_or(&empty);
+        let mut variables = BTreeMap::new();
+        for (slot, slot_labels) in labels {
+            let word = account.storage.get(slot).copied().unwrap_or_default();
+            for label in slot_labels {
+                if label.type_label.starts_with("mapping(") {
+                    continue;
+                }
+                variables.insert(label.label.clone(), decode_slot_value(word, label));
+            }
+        }
+        for (slot, word) in &account.storage {
+            if !labels.contains_key(slot) {
+                variables.insert(format!("{:#x}", slot), B256::from(*word).to_string());
+            }
+        }
+        labeled.insert(contract_name.clone(), variables);
+    }
+    labeled
+}
+
+/// Render the part of `word` that `label` describes according to its type
+fn decode_slot_value(word: U256, label: &SlotLabel) -> String {
+    let bytes = word.to_be_bytes::<32>();
+    let end = 32usize.saturating_sub(label.offset);
+    if label.size > end {
+        return B256::from(word).to_string();
+    }
+    let value = &bytes[end - label.size..end];
+    let type_label = label.type_label.as_str();
+    if (type_label == "address" || type_label.starts_with("contract ")) && label.size == 20 {
+        Address::from_slice(value).to_string()
+    } else if type_label == "bool" {
+        (value.iter().any(|byte| *byte != 0)).to_string()
+    } else if type_label.starts_with("uint") {
+        U256::from_be_slice(value).to_string()
+    } else {
+        format!("0x{}", hex::encode(value))
+    }
+}
+
+/// Describe the account at `address` in `bundle_state`: balance, nonce, code hash and storage
+///
+/// Slots in `labels` are decoded by type; other slots are shown as raw words.
+pub fn inspect_account(
+    bundle_state: &BundleState,
+    address: Address,
+    labels: &SlotLabels,
+    registry: &ContractRegistry,
+) -> Result<String, String> {
+    let account = bundle_state
+        .state
+        .get(&address)
+        .ok_or_else(|| format!("{} is not in the bundle state", address))?;
+
+    let mut out = String::new();
+    write_account(&mut out, address, account, labels, registry).map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+/// Write the description of [`inspect_account`] to `out`
+fn write_account(
+    out: &mut String,
+    address: Address,
+    account: &BundleAccount,
+    labels: &SlotLabels,
+    registry: &ContractRegistry,
+) -> fmt::Result {
+    writeln!(out, "Account {}", registry.display_address(address))?;
+    writeln!(out, "  status: {:?}", account.status)?;
+    match &account.info {
+        Some(info) => {
+            writeln!(out, "  balance: {} wei", info.balance)?;
+            writeln!(out, "  nonce: {}", info.nonce)?;
+            writeln!(out, "  code hash: {}", info.code_hash)?;
+        }
+        None => writeln!(out, "  account destroyed")?,
+    }
+
+    let storage: BTreeMap<_, _> = account.storage.iter().collect();
+    write!(out, "  storage: {} slot(s)", storage.len())?;
+    for (slot, value) in storage {
+        write!(
+            out,
+            "\n    slot {:#x} = {}",
+            slot,
+            B256::from(value.present_value)
+        )?;
+        if value.previous_or_original_value != value.present_value {
+            write!(
+                out,
+                " (was {})",
+                B256::from(value.previous_or_original_value)
+            )?;
+        }
+        for label in labels.get(slot).into_iter().flatten() {
+            write!(
+                out,
+                "\n      {} ({}) = {}",
+                label.label,
+                label.type_label,
+                decode_slot_value(value.present_value, label)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::GENESIS_ADDR;
+    use revm::db::AccountStatus;
+    use revm_primitives::{AccountInfo, HashMap, StorageSlot, address};
+
+    #[test]
+    fn test_inspect_account_decodes_labelled_slots() {
+        let registry = ContractRegistry::default();
+        let peer = address!("0000000000000000000000000000000000002013");
+        // Slot 0 packs `bool initialized` at byte 0 and `address validatorManager` at byte 1
+        let packed = (U256::from_be_slice(peer.as_slice()) << 8) | U256::from(1);
+        let storage: HashMap<U256, StorageSlot> = [
+            (U256::ZERO, StorageSlot::new_changed(U256::ZERO, packed)),
+            (
+                U256::from(3),
+                StorageSlot::new_changed(U256::ZERO, U256::from(42)),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let mut bundle_state = BundleState::default();
+        bundle_state.state.insert(
+            GENESIS_ADDR,
+            BundleAccount::new(
+                None,
+                Some(AccountInfo {
+                    nonce: 1,
+                    ..AccountInfo::default()
+                }),
+                storage,
+                AccountStatus::Changed,
+            ),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let layout_path = dir.path().join("Genesis.json");
+        std::fs::write(
+            &layout_path,
+            serde_json::json!({
+                "storageLayout": {
+                    "storage": [
+                        { "label": "initialized", "offset": 0, "slot": "0", "type": "t_bool" },
+                        { "label": "epoch", "offset": 0, "slot": "3", "type": "t_uint64" }
+                    ],
+                    "types": {
+                        "t_bool": { "encoding": "inplace", "label": "bool", "numberOfBytes": "1" },
+                        "t_uint64": { "encoding": "inplace", "label": "uint64", "numberOfBytes": "8" }
+                    }
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let mut labels = read_storage_layout(layout_path.to_str().unwrap()).unwrap();
+        let hints: Vec<AddressSlotHint> = serde_json::from_value(serde_json::json!([
+            { "label": "validatorManager", "slot": "0x0", "offset": 1 }
+        ]))
+        .unwrap();
+        for (slot, mut hint_labels) in labels_from_hints(&hints) {
+            labels.entry(slot).or_default().append(&mut hint_labels);
+        }
+
+        let address = resolve_address("Genesis", &registry).unwrap();
+        assert_eq!(address, GENESIS_ADDR);
+        let out = inspect_account(&bundle_state, address, &labels, &registry).unwrap();
+        assert!(
+            out.starts_with(&format!("Account Genesis ({})", GENESIS_ADDR)),
+            "{}",
+            out
+        );
+        assert!(out.contains("  nonce: 1\n"), "{}", out);
+        assert!(out.contains("  storage: 2 slot(s)"), "{}", out);
+        assert!(out.contains("      initialized (bool) = true"), "{}", out);
+        assert!(
+            out.contains(&format!("      validatorManager (address) = {}", peer)),
+            "{}",
+            out
+        );
+        assert!(out.contains("    slot 0x3 = "), "{}", out);
+        assert!(out.contains("      epoch (uint64) = 42"), "{}", out);
+
+        let err = inspect_account(&bundle_state, peer, &labels, &registry).unwrap_err();
+        assert!(err.contains("is not in the bundle state"), "{}", err);
+        assert!(resolve_address("NotAContract", &registry).is_err());
+    }
+
+    #[test]
+    fn test_label_genesis_storage_from_layout_dir() {
+        let registry = ContractRegistry::default();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("Genesis.sol")).unwrap();
+        std::fs::write(
+            dir.path().join("Genesis.sol/Genesis.json"),
+            serde_json::json!({
+                "storageLayout": {
+                    "storage": [
+                        { "label": "initialized", "offset": 0, "slot": "0", "type": "t_bool" },
+                        { "label": "epoch", "offset": 0, "slot": "3", "type": "t_uint64" },
+                        { "label": "stakes", "offset": 0, "slot": "4", "type": "t_mapping(t_address,t_uint256)" }
+                    ],
+                    "types": {
+                        "t_bool": { "label": "bool", "numberOfBytes": "1" },
+                        "t_uint64": { "label": "uint64", "numberOfBytes": "8" },
+                        "t_mapping(t_address,t_uint256)": { "label": "mapping(address => uint256)", "numberOfBytes": "32" }
+                    }
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let layouts = read_storage_layouts(dir.path().to_str().unwrap(), &registry).unwrap();
+        assert_eq!(layouts.keys().collect::<Vec<_>>(), vec!["Genesis"]);
+
+        let account = |storage: &[(u64, u64)]| PlainAccount {
+            info: AccountInfo::default(),
+            storage: storage
+                .iter()
+                .map(|(slot, value)| (U256::from(*slot), U256::from(*value)))
+                .collect(),
+        };
+        let epoch_manager = resolve_address("EpochManager", &registry).unwrap();
+        let genesis_state = std::collections::HashMap::from([
+            (GENESIS_ADDR, account(&[(0, 1), (7, 5)])),
+            (epoch_manager, account(&[(1, 2)])),
+            (Address::with_last_byte(0xaa), account(&[(0, 1)])),
+        ]);
+
+        let labeled = label_genesis_storage(&genesis_state, &layouts, &registry);
+        assert_eq!(labeled.len(), 2);
+        let genesis = &labeled["Genesis"];
+        assert_eq!(genesis["initialized"], "true");
+        assert_eq!(genesis["epoch"], "0");
+        assert!(!genesis.contains_key("stakes"));
+        assert_eq!(genesis["0x7"], B256::from(U256::from(5)).to_string());
+        assert_eq!(
+            labeled["EpochManager"]["0x1"],
+            B256::from(U256::from(2)).to_string()
+        );
+    }
+}
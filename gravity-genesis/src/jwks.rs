@@ -1,26 +1,50 @@
+use std::path::Path;
+
 use alloy_sol_macro::sol;
 use alloy_sol_types::{SolCall, SolValue};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use revm::{
+    DatabaseRef,
     db::BundleState,
     primitives::{Env, SpecId, TxEnv},
 };
-use revm_primitives::{ExecutionResult, hex};
-use serde::{Deserialize, Serialize};
-use tracing::{debug, error, info, warn};
+use revm_primitives::{Address, Bytes, ExecutionResult, hex};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tracing::{debug, error, info};
 
 use crate::{
-    post_genesis::handle_execution_result,
-    utils::{JWK_MANAGER_ADDR, execute_revm_sequential, new_system_call_txn},
+    config_format::ConfigFormat,
+    context::RunContext,
+    diagnostics::DiagnosticCode,
+    execute::BlockContext,
+    post_genesis::{
+        VerificationReport, VerifyMode, execute_view_call, handle_execution_result,
+        successful_output,
+    },
+    utils::{execute_revm_sequential, new_system_call_txn, read_input_file},
 };
 
 // JSON structures for deserialization
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
 pub struct JsonJWK {
     pub variant: u8,
-    pub data: String, // hex string
+    pub data: JsonJWKData,
+    /// Revoked key: it is still observed, and a `RemoveJWK` patch drops it from the patched JWKs
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub remove: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// `data` of a JWK in a JWKs file, told apart by whether it is a string or an object
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum JsonJWKData {
+    /// Optionally `0x`-prefixed hex of the ABI-encoded JWK
+    Hex(String),
+    /// JWK as its provider publishes it, e.g. `{"kty":"RSA","kid":"...","alg":"RS256","e":"AQAB","n":"..."}`
+    Object(serde_json::Map<String, serde_json::Value>),
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
 pub struct JsonProviderJWKs {
     pub issuer: String,
     pub version: u64,
@@ -33,7 +57,7 @@ pub struct JsonAllProvidersJWKs {
 }
 
 // JSON structures for OIDC Provider deserialization
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
 pub struct JsonOIDCProvider {
     pub name: String,
     pub configUrl: String,
@@ -53,6 +77,7 @@ sol! {
         uint64 onchain_block_number; // Onchain block number
     }
     function upsertOIDCProvider(string calldata name, string calldata configUrl) external;
+    function removeOIDCProvider(string calldata name) external;
     function getActiveProviders() external view returns (OIDCProvider[] memory);
     struct JWK {
         uint8 variant; // 0: RSA_JWK, 1: UnsupportedJWK
@@ -81,11 +106,160 @@ sol! {
         bytes data;
     }
 
+    struct UnsupportedJWK {
+        bytes id;
+        bytes payload;
+    }
+
+    enum PatchType {
+        RemoveAll,
+        RemoveIssuer,
+        RemoveJWK,
+        UpsertJWK
+    }
+
+    struct Patch {
+        PatchType patchType;
+        string issuer; // For RemoveIssuer, RemoveJWK, UpsertJWK
+        bytes jwkId; // For RemoveJWK
+        JWK jwk; // For UpsertJWK
+    }
+
     function upsertObservedJWKs(ProviderJWKs[] calldata providerJWKsArray, CrossChainParams[] calldata crossChainParamsArray) external;
     function getObservedJWKs() external view returns (AllProvidersJWKs memory);
+    function addPatch(Patch calldata patch) external;
+}
+
+/// Scheme of issuers naming an event source on a Gravity chain rather than an OIDC provider,
+/// e.g. `gravity://31337/event?address=...`
+pub const GRAVITY_ISSUER_SCHEME: &str = "gravity";
+
+/// Path an OIDC provider serves its discovery document at
+pub const OPENID_CONFIGURATION_PATH: &str = ".well-known/openid-configuration";
+
+/// Check that `url` has one of `schemes` and a host, returning the scheme
+fn check_url<'a>(url: &'a str, schemes: &[&str]) -> Result<&'a str, String> {
+    let expected = schemes
+        .iter()
+        .map(|scheme| format!("{}://", scheme))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err(format!("{:?} has no scheme, expected {}", url, expected));
+    };
+    if !schemes
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+    {
+        return Err(format!(
+            "{:?} uses {}://, expected {}",
+            url, scheme, expected
+        ));
+    }
+    if url.chars().any(char::is_whitespace) {
+        return Err(format!("{:?} contains whitespace", url));
+    }
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() {
+        return Err(format!("{:?} has no host", url));
+    }
+    Ok(scheme)
+}
+
+/// Check a JWK issuer or OIDC provider name: an https URL, or a `gravity://` event source
+pub fn validate_issuer(issuer: &str) -> Result<(), String> {
+    check_url(issuer, &["https", GRAVITY_ISSUER_SCHEME]).map(|_| ())
+}
+
+/// Check the name and config URL of an OIDC provider
+///
+/// Providers named by an https issuer need an https config URL, which should
+/// be the issuer's OpenID discovery document. Gravity event sources point at
+/// an RPC endpoint instead, which may also be plain http.
+pub fn validate_oidc_provider(name: &str, config_url: &str) -> Result<(), String> {
+    let scheme = check_url(name, &["https", GRAVITY_ISSUER_SCHEME])
+        .map_err(|e| format!("OIDC provider {:?}: invalid name: {}", name, e))?;
+    let config_url_schemes: &[&str] = if scheme.eq_ignore_ascii_case(GRAVITY_ISSUER_SCHEME) {
+        &["http", "https"]
+    } else {
+        &["https"]
+    };
+    check_url(config_url, config_url_schemes)
+        .map_err(|e| format!("OIDC provider {:?}: invalid configUrl: {}", name, e))?;
+    Ok(())
+}
+
+/// Warn when an https issuer's config URL is not its OpenID discovery document
+///
+/// Only done when building the provider transactions, not each time the
+/// providers are read, so that verification does not repeat the warning.
+fn check_oidc_config_url(provider: &OIDCProvider, context: &RunContext) {
+    let gravity_source = provider
+        .name
+        .split_once("://")
+        .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(GRAVITY_ISSUER_SCHEME));
+    if !gravity_source
+        && !provider
+            .configUrl
+            .trim_end_matches('/')
+            .ends_with(OPENID_CONFIGURATION_PATH)
+    {
+        context.diagnostics.emit(
+            DiagnosticCode::NonStandardOidcConfigUrl,
+            format!(
+                "OIDC provider {:?}: configUrl {:?} does not end in {}",
+                provider.name, provider.configUrl, OPENID_CONFIGURATION_PATH
+            ),
+        );
+    }
+}
+
+/// Byte lengths of the RSA moduli a JWK may have: 2048, 3072 and 4096-bit keys
+pub const RSA_MODULUS_SIZES: [usize; 3] = [256, 384, 512];
+
+/// Decode a base64url JWK member, which is unpadded per RFC 7515
+fn decode_base64url(name: &str, value: &str) -> Result<Vec<u8>, String> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|e| format!("{} {:?} is not base64url: {}", name, value, e))
+}
+
+/// Check that the exponent `e` and modulus `n` of an RSA JWK are base64url
+/// big-endian integers, with `n` one of the [`RSA_MODULUS_SIZES`]
+///
+/// A malformed key would be written into genesis state and could never
+/// verify a signature.
+pub fn validate_rsa_jwk(e: &str, n: &str) -> Result<(), String> {
+    if decode_base64url("e", e)?.is_empty() {
+        return Err("e is empty".to_string());
+    }
+    let modulus = decode_base64url("n", n)?;
+    if !RSA_MODULUS_SIZES.contains(&modulus.len()) {
+        return Err(format!(
+            "n is {} bytes, expected an RSA modulus of {:?} bytes",
+            modulus.len(),
+            RSA_MODULUS_SIZES
+        ));
+    }
+    Ok(())
+}
+
+/// Check an ABI-encoded RSA JWK with [`validate_rsa_jwk`]
+fn validate_rsa_jwk_data(data: &[u8]) -> Result<(), String> {
+    let rsa_jwk = RSATestJWK::abi_decode(data, true)
+        .map_err(|e| format!("data is not an ABI-encoded RSA JWK: {}", e))?;
+    validate_rsa_jwk(&rsa_jwk.e, &rsa_jwk.n)
+}
+
+/// RSA JWK with `e` and `n` checked by [`validate_rsa_jwk`]
+pub fn create_rsa_jwk(kid: &str, alg: &str, e: &str, n: &str) -> Result<JWK, String> {
+    validate_rsa_jwk(e, n)?;
+    Ok(create_test_rsa_jwk(kid, alg, e, n))
 }
 
 /// Create a test RSA JWK
+///
+/// `e` and `n` are encoded as given; use [`create_rsa_jwk`] for keys that go into a genesis.
 pub fn create_test_rsa_jwk(kid: &str, alg: &str, e: &str, n: &str) -> JWK {
     // Create RSA JWK structure
     let rsa_jwk = RSATestJWK {
@@ -115,44 +289,241 @@ pub fn create_provider_jwks(issuer: &str, version: u64, jwks: Vec<JWK>) -> Provi
 }
 
 /// Call upsertObservedJWKs function
-pub fn call_upsert_observed_jwks(provider_jwks_array: Vec<ProviderJWKs>, cross_chain_params_array: Vec<CrossChainParams>) -> TxEnv {
+pub fn call_upsert_observed_jwks(
+    caller: Address,
+    jwk_manager: Address,
+    provider_jwks_array: Vec<ProviderJWKs>,
+    cross_chain_params_array: Vec<CrossChainParams>,
+) -> TxEnv {
     let call_data = upsertObservedJWKsCall {
         providerJWKsArray: provider_jwks_array,
         crossChainParamsArray: cross_chain_params_array,
     }
     .abi_encode();
-    new_system_call_txn(JWK_MANAGER_ADDR, call_data.into())
+    new_system_call_txn(caller, jwk_manager, call_data.into())
 }
 
 /// Call getObservedJWKs function
-pub fn call_get_observed_jwks() -> TxEnv {
+pub fn call_get_observed_jwks(caller: Address, jwk_manager: Address) -> TxEnv {
     let call_data = getObservedJWKsCall {}.abi_encode();
-    new_system_call_txn(JWK_MANAGER_ADDR, call_data.into())
+    new_system_call_txn(caller, jwk_manager, call_data.into())
 }
 
 /// Call upsertOIDCProvider function
-pub fn call_upsert_oidc_provider(name: String, config_url: String) -> TxEnv {
+pub fn call_upsert_oidc_provider(
+    caller: Address,
+    jwk_manager: Address,
+    name: String,
+    config_url: String,
+) -> TxEnv {
     let call_data = upsertOIDCProviderCall {
         name,
         configUrl: config_url,
     }
     .abi_encode();
-    new_system_call_txn(JWK_MANAGER_ADDR, call_data.into())
+    new_system_call_txn(caller, jwk_manager, call_data.into())
+}
+
+/// Call removeOIDCProvider function, which marks the provider inactive
+pub fn call_remove_oidc_provider(caller: Address, jwk_manager: Address, name: String) -> TxEnv {
+    let call_data = removeOIDCProviderCall { name }.abi_encode();
+    new_system_call_txn(caller, jwk_manager, call_data.into())
+}
+
+/// Call addPatch function
+pub fn call_add_patch(caller: Address, jwk_manager: Address, patch: Patch) -> TxEnv {
+    let call_data = addPatchCall { patch }.abi_encode();
+    new_system_call_txn(caller, jwk_manager, call_data.into())
+}
+
+/// ID `JWKManager` identifies a JWK by: the `kid` of an RSA JWK, the `id` of an unsupported one
+fn jwk_id(jwk: &JWK) -> Result<Bytes, String> {
+    match jwk.variant {
+        0 => RSATestJWK::abi_decode(&jwk.data, true)
+            .map(|rsa_jwk| Bytes::from(rsa_jwk.kid.into_bytes()))
+            .map_err(|e| format!("data is not an ABI-encoded RSA JWK: {}", e)),
+        1 => UnsupportedJWK::abi_decode(&jwk.data, true)
+            .map(|unsupported| unsupported.id)
+            .map_err(|e| format!("data is not an ABI-encoded unsupported JWK: {}", e)),
+        variant => Err(format!("unknown JWK variant {}", variant)),
+    }
 }
 
 /// Call getActiveProviders function
-pub fn call_get_active_providers() -> TxEnv {
+pub fn call_get_active_providers(caller: Address, jwk_manager: Address) -> TxEnv {
     let call_data = getActiveProvidersCall {}.abi_encode();
-    new_system_call_txn(JWK_MANAGER_ADDR, call_data.into())
+    new_system_call_txn(caller, jwk_manager, call_data.into())
+}
+
+/// Read the JWKs of a JSON, TOML or YAML file, or a directory merged by [`merge_jwks_dir`]
+pub fn read_jwks_from_file(
+    jwks_file_path: &str,
+    context: &RunContext,
+) -> Result<Vec<ProviderJWKs>, String> {
+    read_jwks_file(jwks_file_path, context).map(|(provider_jwks_array, _)| provider_jwks_array)
+}
+
+/// Read the `RemoveJWK` patches revoking the JWKs marked `remove` in a JWKs file
+pub fn read_revoked_jwks(jwks_file_path: &str, context: &RunContext) -> Result<Vec<Patch>, String> {
+    read_jwks_file(jwks_file_path, context).map(|(_, revoked)| revoked)
 }
 
-pub fn read_jwks_from_file(jwks_file_path: &str) -> Result<Vec<ProviderJWKs>, String> {
-    let jwks_content = std::fs::read_to_string(jwks_file_path)
-        .map_err(|e| format!("Failed to read JWKS file: {}", e))?;
+/// Parse every `*.json` file of `dir`, in file name order
+fn read_json_dir<T: DeserializeOwned>(dir: &str) -> Result<Vec<(String, T)>, String> {
+    let read_error = |e: std::io::Error| format!("Failed to read directory {}: {}", dir, e);
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    if paths.is_empty() {
+        return Err(format!("{} holds no .json files", dir));
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let file = path.display().to_string();
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", file, e))?;
+            let value = serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse {}: {}", file, e))?;
+            Ok((file, value))
+        })
+        .collect()
+}
 
-    let jwks: JsonAllProvidersJWKs = serde_json::from_str(&jwks_content)
+/// Merge a directory of `*.json` files, each holding one provider's JWKs
+///
+/// Providers are taken in file name order and deduplicated by issuer: an
+/// issuer may only be repeated with the same version and JWKs.
+pub fn merge_jwks_dir(dir: &str) -> Result<JsonAllProvidersJWKs, String> {
+    let mut merged: Vec<(String, JsonProviderJWKs)> = Vec::new();
+    for (file, provider) in read_json_dir::<JsonProviderJWKs>(dir)? {
+        match merged
+            .iter()
+            .find(|(_, existing)| existing.issuer == provider.issuer)
+        {
+            None => merged.push((file, provider)),
+            Some((first, existing)) if existing.version != provider.version => {
+                return Err(format!(
+                    "issuer {:?} has version {} in {} but version {} in {}",
+                    provider.issuer, existing.version, first, provider.version, file
+                ));
+            }
+            Some((first, existing)) if *existing != provider => {
+                return Err(format!(
+                    "issuer {:?} has different JWKs for version {} in {} and {}",
+                    provider.issuer, provider.version, first, file
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(JsonAllProvidersJWKs {
+        entries: merged.into_iter().map(|(_, provider)| provider).collect(),
+    })
+}
+
+/// Merge a directory of `*.json` files, each holding one OIDC provider
+///
+/// Providers are taken in file name order and deduplicated by name: a name
+/// may only be repeated with the same config URL and `active` flag.
+pub fn merge_oidc_dir(dir: &str) -> Result<JsonOIDCProviders, String> {
+    let mut merged: Vec<(String, JsonOIDCProvider)> = Vec::new();
+    for (file, provider) in read_json_dir::<JsonOIDCProvider>(dir)? {
+        match merged
+            .iter()
+            .find(|(_, existing)| existing.name == provider.name)
+        {
+            None => merged.push((file, provider)),
+            Some((first, existing)) if *existing != provider => {
+                return Err(format!(
+                    "OIDC provider {:?} differs between {} and {}",
+                    provider.name, first, file
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(JsonOIDCProviders {
+        providers: merged.into_iter().map(|(_, provider)| provider).collect(),
+    })
+}
+
+/// A JWKs or OIDC providers input, read into memory once
+///
+/// The genesis transactions are built from `content`, and the manifest hashes
+/// the same bytes. A directory is held as its merged JSON document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputDocument {
+    /// File or directory the input was read from
+    pub path: String,
+    /// Format `content` is written in
+    pub format: ConfigFormat,
+    /// Content of the file, or the merged JSON document of a directory
+    pub content: String,
+}
+
+impl InputDocument {
+    fn read<T: Serialize>(
+        path: &str,
+        kind: &str,
+        merge_dir: fn(&str) -> Result<T, String>,
+        context: &RunContext,
+    ) -> Result<Self, String> {
+        if Path::new(path).is_dir() {
+            let merged = merge_dir(path)
+                .map_err(|e| format!("Failed to merge {} directory: {}", kind, e))?;
+            return Ok(Self {
+                path: path.to_string(),
+                format: ConfigFormat::Json,
+                content: serde_json::to_string(&merged).map_err(|e| e.to_string())?,
+            });
+        }
+        let content = read_input_file(path, context)
+            .map_err(|e| format!("Failed to read {} file: {}", kind, e))?;
+        let format = ConfigFormat::from_path(path)
+            .map_err(|e| format!("Failed to parse {} file: {}", kind, e))?;
+        Ok(Self {
+            path: path.to_string(),
+            format,
+            content,
+        })
+    }
+}
+
+/// Read a JWKs file, or a directory merged by [`merge_jwks_dir`]
+pub fn read_jwks_input(path: &str, context: &RunContext) -> Result<InputDocument, String> {
+    InputDocument::read(path, "JWKS", merge_jwks_dir, context)
+}
+
+/// Read an OIDC providers file, or a directory merged by [`merge_oidc_dir`]
+pub fn read_oidc_input(path: &str, context: &RunContext) -> Result<InputDocument, String> {
+    InputDocument::read(path, "OIDC provider", merge_oidc_dir, context)
+}
+
+fn read_jwks_file(
+    jwks_file_path: &str,
+    context: &RunContext,
+) -> Result<(Vec<ProviderJWKs>, Vec<Patch>), String> {
+    parse_jwks(&read_jwks_input(jwks_file_path, context)?)
+}
+
+/// Decode the JWKs of `input` and the patches revoking those marked `remove`
+fn parse_jwks(input: &InputDocument) -> Result<(Vec<ProviderJWKs>, Vec<Patch>), String> {
+    let jwks: JsonAllProvidersJWKs = input
+        .format
+        .parse(&input.content)
         .map_err(|e| format!("Failed to parse JWKS file: {}", e))?;
 
+    for provider in &jwks.entries {
+        validate_issuer(&provider.issuer)
+            .map_err(|e| format!("JWK provider {:?}: invalid issuer: {}", provider.issuer, e))?;
+    }
+
     info!("Successfully loaded JWKs from file");
     info!("Total providers: {}", jwks.entries.len());
 
@@ -162,61 +533,184 @@ pub fn read_jwks_from_file(jwks_file_path: &str) -> Result<Vec<ProviderJWKs>, St
         info!("  JWK count: {}", provider.jwks.len());
 
         for (j, jwk) in provider.jwks.iter().enumerate() {
+            let data = match &jwk.data {
+                JsonJWKData::Hex(data) => format!("data_length={}", data.len()),
+                JsonJWKData::Object(object) => format!(
+                    "kid={}",
+                    object
+                        .get("kid")
+                        .and_then(|kid| kid.as_str())
+                        .unwrap_or("?")
+                ),
+            };
             info!(
-                "    JWK {}: variant={}, data_length={}",
+                "    JWK {}: variant={}, {}{}",
                 j + 1,
                 jwk.variant,
-                jwk.data.len()
+                data,
+                if jwk.remove { ", revoked" } else { "" }
             );
         }
     }
 
     // Convert JSON structure to Solidity structure
-    let provider_jwks_array: Result<Vec<ProviderJWKs>, String> = jwks
-        .entries
-        .into_iter()
-        .map(|entry| {
-            let jwks: Result<Vec<JWK>, String> = entry
-                .jwks
-                .into_iter()
-                .map(|jwk| {
-                    // Convert hex string to bytes
-                    let data_bytes = if jwk.data.starts_with("0x") {
-                        hex::decode(&jwk.data[2..])
-                            .map_err(|e| format!("Failed to decode hex data: {}", e))
-                    } else {
-                        hex::decode(&jwk.data)
-                            .map_err(|e| format!("Failed to decode hex data: {}", e))
-                    }?;
-
-                    Ok(JWK {
-                        variant: jwk.variant,
-                        data: data_bytes.into(),
-                    })
-                })
-                .collect();
+    let mut provider_jwks_array = Vec::with_capacity(jwks.entries.len());
+    let mut revoked = Vec::new();
+    for entry in jwks.entries {
+        let mut provider_jwks = Vec::with_capacity(entry.jwks.len());
+        for (j, jwk) in entry.jwks.into_iter().enumerate() {
+            let data_bytes = match &jwk.data {
+                JsonJWKData::Hex(data) => decode_jwk_data(data).map_err(|e| {
+                    format!(
+                        "Failed to decode hex data of JWK {} of issuer {:?}: {}",
+                        j, entry.issuer, e
+                    )
+                })?,
+                JsonJWKData::Object(object) => {
+                    encode_jwk_object(jwk.variant, object).map_err(|e| {
+                        format!(
+                            "Failed to encode JWK {} of issuer {:?}: {}",
+                            j, entry.issuer, e
+                        )
+                    })?
+                }
+            };
+            if jwk.variant == 0 {
+                validate_rsa_jwk_data(&data_bytes).map_err(|e| {
+                    format!("Invalid RSA JWK {} of issuer {:?}: {}", j, entry.issuer, e)
+                })?;
+            }
+            let decoded = JWK {
+                variant: jwk.variant,
+                data: data_bytes.into(),
+            };
+            if jwk.remove {
+                let jwk_id = jwk_id(&decoded).map_err(|e| {
+                    format!(
+                        "Cannot revoke JWK {} of issuer {:?}: {}",
+                        j, entry.issuer, e
+                    )
+                })?;
+                revoked.push(Patch {
+                    patchType: PatchType::RemoveJWK,
+                    issuer: entry.issuer.clone(),
+                    jwkId: jwk_id,
+                    jwk: JWK {
+                        variant: 0,
+                        data: Bytes::new(),
+                    },
+                });
+            }
+            provider_jwks.push(decoded);
+        }
+        provider_jwks_array.push(ProviderJWKs {
+            issuer: entry.issuer,
+            version: entry.version,
+            jwks: provider_jwks,
+        });
+    }
 
-            Ok(ProviderJWKs {
-                issuer: entry.issuer,
-                version: entry.version,
-                jwks: jwks?,
-            })
-        })
-        .collect();
+    Ok((provider_jwks_array, revoked))
+}
+
+/// Longest part of an invalid hex string quoted in an error
+const MAX_QUOTED_HEX_LEN: usize = 64;
 
-    Ok(provider_jwks_array?)
+/// Decode the optionally `0x`-prefixed hex data of a JWK
+///
+/// Invalid characters are reported by their position in `data`, counting the prefix.
+fn decode_jwk_data(data: &str) -> Result<Vec<u8>, String> {
+    let (prefix_len, digits) = match data.strip_prefix("0x") {
+        Some(digits) => (2, digits),
+        None => (0, data),
+    };
+    hex::decode(digits).map_err(|e| {
+        let quoted = if data.len() > MAX_QUOTED_HEX_LEN {
+            format!(
+                "{:?}...",
+                data.chars().take(MAX_QUOTED_HEX_LEN).collect::<String>()
+            )
+        } else {
+            format!("{:?}", data)
+        };
+        match e {
+            hex::FromHexError::InvalidHexCharacter { c, index } => format!(
+                "invalid character {:?} at position {} in {}",
+                c,
+                prefix_len + index,
+                quoted
+            ),
+            hex::FromHexError::OddLength => {
+                format!("odd number of hex digits ({}) in {}", digits.len(), quoted)
+            }
+            e => format!("{} in {}", e, quoted),
+        }
+    })
 }
 
-/// Read OIDC providers from JSON file
+fn jwk_field<'a>(
+    jwk: &'a serde_json::Map<String, serde_json::Value>,
+    name: &str,
+) -> Result<&'a str, String> {
+    match jwk.get(name) {
+        Some(serde_json::Value::String(value)) => Ok(value),
+        Some(value) => Err(format!("{} is not a string: {}", name, value)),
+        None => Err(format!("missing {}", name)),
+    }
+}
+
+/// ABI-encode a JWK object the way `JWKManager` expects for `variant`
+///
+/// RSA keys (variant 0) are checked and encoded from their `kid`, `alg`, `e`
+/// and `n`, as [`create_rsa_jwk`] does. Other keys, such as EC keys (variant 1),
+/// become an [`UnsupportedJWK`] with the `kid` as id and the JSON object as payload.
+fn encode_jwk_object(
+    variant: u8,
+    jwk: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<u8>, String> {
+    let kty = jwk_field(jwk, "kty")?;
+    match (variant, kty) {
+        (0, "RSA") => Ok(create_rsa_jwk(
+            jwk_field(jwk, "kid")?,
+            jwk_field(jwk, "alg")?,
+            jwk_field(jwk, "e")?,
+            jwk_field(jwk, "n")?,
+        )?
+        .data
+        .to_vec()),
+        (0, kty) => Err(format!("variant 0 is for RSA keys, not kty {:?}", kty)),
+        (1, "RSA") => Err("RSA keys are variant 0".to_string()),
+        (1, _) => {
+            let payload = serde_json::to_vec(jwk).map_err(|e| e.to_string())?;
+            Ok(UnsupportedJWK {
+                id: jwk_field(jwk, "kid")?.as_bytes().to_vec().into(),
+                payload: payload.into(),
+            }
+            .abi_encode())
+        }
+        (variant, _) => Err(format!("unknown JWK variant {}", variant)),
+    }
+}
+
+/// Read OIDC providers from a JSON, TOML or YAML file, or a directory merged by [`merge_oidc_dir`]
 pub fn read_oidc_providers_from_file(
     provider_file_path: &str,
+    context: &RunContext,
 ) -> Result<Vec<OIDCProvider>, String> {
-    let provider_content = std::fs::read_to_string(provider_file_path)
-        .map_err(|e| format!("Failed to read OIDC provider file: {}", e))?;
+    parse_oidc_providers(&read_oidc_input(provider_file_path, context)?)
+}
 
-    let providers: JsonOIDCProviders = serde_json::from_str(&provider_content)
+/// Decode and check the OIDC providers of `input`
+fn parse_oidc_providers(input: &InputDocument) -> Result<Vec<OIDCProvider>, String> {
+    let providers: JsonOIDCProviders = input
+        .format
+        .parse(&input.content)
         .map_err(|e| format!("Failed to parse OIDC provider file: {}", e))?;
 
+    for provider in &providers.providers {
+        validate_oidc_provider(&provider.name, &provider.configUrl)?;
+    }
+
     info!("Successfully loaded OIDC providers from file");
     info!("Total providers: {}", providers.providers.len());
 
@@ -242,32 +736,49 @@ pub fn read_oidc_providers_from_file(
 }
 
 /// Upsert OIDC providers from file
-pub fn upsert_oidc_providers(provider_file_path: &str) -> Result<Vec<TxEnv>, String> {
+///
+/// Each provider gets a labelled `upsertOIDCProvider` transaction, followed by
+/// a `removeOIDCProvider` one for providers with `active: false`.
+pub fn upsert_oidc_providers(
+    caller: Address,
+    context: &RunContext,
+    providers: &InputDocument,
+) -> Result<Vec<(String, TxEnv)>, String> {
     info!(
         "=== Loading OIDC providers from file: {} ===",
-        provider_file_path
+        providers.path
     );
 
-    let oidc_providers = read_oidc_providers_from_file(provider_file_path)?;
+    let oidc_providers = parse_oidc_providers(providers)?;
+    let jwk_manager = context.registry.jwk_manager_addr();
 
     info!("Converted to Solidity structure");
     info!("OIDC providers count: {}", oidc_providers.len());
 
     // Create transactions for each provider
     let mut transactions = Vec::new();
-    for provider in oidc_providers {
-        let tx = call_upsert_oidc_provider(provider.name, provider.configUrl);
-        transactions.push(tx);
+    for (i, provider) in oidc_providers.into_iter().enumerate() {
+        check_oidc_config_url(&provider, context);
+        let tx = call_upsert_oidc_provider(
+            caller,
+            jwk_manager,
+            provider.name.clone(),
+            provider.configUrl,
+        );
+        transactions.push((format!("JWKManager.upsertOIDCProvider[{}]", i), tx));
+        if !provider.active {
+            info!("Provider {} is inactive and will be removed", provider.name);
+            let tx = call_remove_oidc_provider(caller, jwk_manager, provider.name);
+            transactions.push((format!("JWKManager.removeOIDCProvider[{}]", i), tx));
+        }
     }
 
-    info!(
-        "Created {} upsertOIDCProvider transactions",
-        transactions.len()
-    );
-    for (i, tx) in transactions.iter().enumerate() {
+    info!("Created {} OIDC provider transactions", transactions.len());
+    for (i, (label, tx)) in transactions.iter().enumerate() {
         info!(
-            "Transaction {}: data length: {} bytes",
+            "Transaction {} ({}): data length: {} bytes",
             i + 1,
+            label,
             tx.data.len()
         );
     }
@@ -277,17 +788,39 @@ pub fn upsert_oidc_providers(provider_file_path: &str) -> Result<Vec<TxEnv>, Str
     Ok(transactions)
 }
 
-pub fn upsert_observed_jwks(jwks_file_path: &str) -> Result<TxEnv, String> {
-    info!("=== Loading JWKs from file: {} ===", jwks_file_path);
-
-    let provider_jwks_array = read_jwks_from_file(jwks_file_path)?;
+pub fn upsert_observed_jwks(
+    caller: Address,
+    context: &RunContext,
+    jwks: &InputDocument,
+) -> Result<TxEnv, String> {
+    info!("=== Loading JWKs from file: {} ===", jwks.path);
+
+    let (provider_jwks_array, _) = parse_jwks(jwks)?;
+    let jwk_manager = context.registry.jwk_manager_addr();
+    if !provider_jwks_array
+        .windows(2)
+        .all(|pair| pair[0].issuer <= pair[1].issuer)
+    {
+        context.diagnostics.emit(
+            DiagnosticCode::JwksReordered,
+            format!(
+                "JWK providers in {} are not sorted by issuer; JWKManager stores them sorted, so their on-chain order differs",
+                jwks.path
+            ),
+        );
+    }
 
     info!("Converted to Solidity structure");
     info!("Provider JWKs array length: {}", provider_jwks_array.len());
 
     // Create transaction to upsert JWKs with empty crossChainParams array
     let cross_chain_params_array = Vec::<CrossChainParams>::new();
-    let upsert_tx = call_upsert_observed_jwks(provider_jwks_array, cross_chain_params_array);
+    let upsert_tx = call_upsert_observed_jwks(
+        caller,
+        jwk_manager,
+        provider_jwks_array,
+        cross_chain_params_array,
+    );
 
     info!("Created upsertObservedJWKs transaction");
     info!("Transaction data length: {} bytes", upsert_tx.data.len());
@@ -299,63 +832,286 @@ pub fn upsert_observed_jwks(jwks_file_path: &str) -> Result<TxEnv, String> {
     Ok(upsert_tx)
 }
 
-pub fn print_jwks_result(result: &ExecutionResult, jwks_file: &str) {
-    let provider_jwks_array = read_jwks_from_file(jwks_file).unwrap();
+/// Build the `addPatch` transactions revoking the JWKs marked `remove` in a JWKs file
+pub fn revoke_jwks(
+    caller: Address,
+    context: &RunContext,
+    jwks: &InputDocument,
+) -> Result<Vec<(String, TxEnv)>, String> {
+    let (_, revoked) = parse_jwks(jwks)?;
+    let jwk_manager = context.registry.jwk_manager_addr();
+    info!("Revoking {} JWKs with RemoveJWK patches", revoked.len());
+    Ok(revoked
+        .into_iter()
+        .enumerate()
+        .map(|(i, patch)| {
+            (
+                format!("JWKManager.addPatch[{}]", i),
+                call_add_patch(caller, jwk_manager, patch),
+            )
+        })
+        .collect())
+}
 
-    handle_execution_result(result, "getObservedJWKs", |output_bytes| {
-        let solidity_current_epoch_info =
-            getObservedJWKsCall::abi_decode_returns(output_bytes, false).unwrap();
-        let result_jwks = solidity_current_epoch_info._0.entries;
-
-        // Compare with provider_jwks_array
-        for (_i, provider) in result_jwks.iter().enumerate() {
-            let provider_jwks = provider_jwks_array
-                .iter()
-                .find(|p| p.issuer == provider.issuer);
-            if let Some(provider_jwks) = provider_jwks {
-                assert_eq!(provider_jwks.version, provider.version);
-                assert_eq!(provider_jwks.jwks.len(), provider.jwks.len());
-                for (j, jwk) in provider_jwks.jwks.iter().enumerate() {
-                    assert_eq!(jwk.variant, provider.jwks[j].variant);
-                    assert_eq!(jwk.data, provider.jwks[j].data);
-                }
-            }
+/// Decode the return data of `JWKManager.getObservedJWKs`
+pub fn decode_observed_jwks(output: &[u8]) -> Result<AllProvidersJWKs, String> {
+    getObservedJWKsCall::abi_decode_returns(output, false)
+        .map(|returns| returns._0)
+        .map_err(|e| format!("Failed to decode getObservedJWKs output: {}", e))
+}
+
+/// Decode the return data of `JWKManager.getActiveProviders`
+pub fn decode_active_providers(output: &[u8]) -> Result<Vec<OIDCProvider>, String> {
+    getActiveProvidersCall::abi_decode_returns(output, false)
+        .map(|returns| returns._0)
+        .map_err(|e| format!("Failed to decode getActiveProviders output: {}", e))
+}
+
+/// Call `JWKManager.getObservedJWKs` on top of the genesis state
+pub fn query_observed_jwks(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    context: &RunContext,
+) -> Result<AllProvidersJWKs, String> {
+    let result = execute_view_call(
+        db,
+        bundle_state,
+        block,
+        call_get_observed_jwks(caller, context.registry.jwk_manager_addr()),
+        "getObservedJWKs",
+        context,
+    )?;
+    decode_observed_jwks(&successful_output(&result, "getObservedJWKs", context)?)
+}
+
+/// Call `JWKManager.getActiveProviders` on top of the genesis state
+pub fn query_active_oidc_providers(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    context: &RunContext,
+) -> Result<Vec<OIDCProvider>, String> {
+    let result = execute_view_call(
+        db,
+        bundle_state,
+        block,
+        call_get_active_providers(caller, context.registry.jwk_manager_addr()),
+        "getActiveProviders",
+        context,
+    )?;
+    decode_active_providers(&successful_output(&result, "getActiveProviders", context)?)
+}
+
+/// A field of the on-chain JWK state that differs from the expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// What was compared, e.g. `https://accounts.google.com version`
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Mismatch {
+    fn of<T: PartialEq + std::fmt::Display>(
+        field: impl Into<String>,
+        expected: T,
+        actual: T,
+    ) -> Option<Self> {
+        (expected != actual).then(|| Self {
+            field: field.into(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, actual {}",
+            self.field, self.expected, self.actual
+        )
+    }
+}
+
+/// Compare observed JWKs with the expected ones
+///
+/// Only providers present on both sides are compared, key by key in order.
+pub fn compare_jwks(expected: &[ProviderJWKs], actual: &[ProviderJWKs]) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for provider in actual {
+        let Some(expected_provider) = expected.iter().find(|p| p.issuer == provider.issuer) else {
+            continue;
+        };
+        mismatches.extend(Mismatch::of(
+            format!("{} version", provider.issuer),
+            expected_provider.version,
+            provider.version,
+        ));
+        mismatches.extend(Mismatch::of(
+            format!("{} JWK count", provider.issuer),
+            expected_provider.jwks.len(),
+            provider.jwks.len(),
+        ));
+        for (j, (jwk, actual)) in expected_provider
+            .jwks
+            .iter()
+            .zip(&provider.jwks)
+            .enumerate()
+        {
+            mismatches.extend(Mismatch::of(
+                format!("{} JWK {} variant", provider.issuer, j),
+                jwk.variant,
+                actual.variant,
+            ));
+            mismatches.extend(Mismatch::of(
+                format!("{} JWK {} data", provider.issuer, j),
+                &jwk.data,
+                &actual.data,
+            ));
         }
-    });
+    }
+    mismatches
+}
+
+/// Compare the active providers on chain with the expected providers
+///
+/// Every expected provider must be listed exactly when it is active, with its
+/// config URL. Active providers that were not expected are not mismatches; see
+/// [`unexpected_oidc_providers`].
+pub fn compare_oidc_providers(expected: &[OIDCProvider], actual: &[OIDCProvider]) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for provider in actual {
+        let Some(expected_provider) = expected.iter().find(|p| p.name == provider.name) else {
+            continue;
+        };
+        mismatches.extend(Mismatch::of(
+            format!("{} config URL", provider.name),
+            &expected_provider.configUrl,
+            &provider.configUrl,
+        ));
+        mismatches.extend(Mismatch::of(
+            format!("{} active", provider.name),
+            expected_provider.active,
+            provider.active,
+        ));
+    }
+    for expected_provider in expected {
+        mismatches.extend(Mismatch::of(
+            format!("{} listed as active", expected_provider.name),
+            expected_provider.active,
+            actual.iter().any(|p| p.name == expected_provider.name),
+        ));
+    }
+    mismatches
+}
+
+/// Active providers on chain that are not among the expected providers
+pub fn unexpected_oidc_providers<'a>(
+    expected: &[OIDCProvider],
+    actual: &'a [OIDCProvider],
+) -> impl Iterator<Item = &'a OIDCProvider> {
+    actual
+        .iter()
+        .filter(|provider| !expected.iter().any(|p| p.name == provider.name))
 }
 
-pub fn print_oidc_providers_result(result: &ExecutionResult, oidc_providers_file: &str) {
-    let expected_providers = read_oidc_providers_from_file(oidc_providers_file).unwrap();
+/// Record each mismatch as a failed check, or one passed check named `field` if there are none
+fn record_mismatches(
+    mode: VerifyMode,
+    field: &str,
+    compared: usize,
+    mismatches: Vec<Mismatch>,
+) -> VerificationReport {
+    let mut report = VerificationReport::new(mode);
+    if mismatches.is_empty() {
+        report.record(
+            field,
+            format!("{} as expected", compared),
+            format!("{} as expected", compared),
+            true,
+        );
+    }
+    for mismatch in mismatches {
+        report.record(mismatch.field, mismatch.expected, mismatch.actual, false);
+    }
+    report
+}
+
+pub fn print_jwks_result(
+    result: &ExecutionResult,
+    expected: &[ProviderJWKs],
+    mode: VerifyMode,
+) -> VerificationReport {
+    handle_execution_result(result, "getObservedJWKs", |output_bytes| {
+        let observed = match decode_observed_jwks(output_bytes) {
+            Ok(observed_jwks) => observed_jwks.entries,
+            Err(e) => {
+                error!("{}", e);
+                return VerificationReport::call_failed(mode, "getObservedJWKs");
+            }
+        };
+        info!("Retrieved JWKs of {} providers", observed.len());
+        record_mismatches(
+            mode,
+            "observed JWK providers",
+            observed.len(),
+            compare_jwks(expected, &observed),
+        )
+    })
+    .unwrap_or_else(|| VerificationReport::call_failed(mode, "getObservedJWKs"))
+}
 
+/// Check the active providers on chain against `expected`, read from `source`
+pub fn print_oidc_providers_result(
+    result: &ExecutionResult,
+    expected: &[OIDCProvider],
+    source: &str,
+    mode: VerifyMode,
+    context: &RunContext,
+) -> VerificationReport {
     handle_execution_result(result, "getActiveProviders", |output_bytes| {
-        let solidity_active_providers =
-            getActiveProvidersCall::abi_decode_returns(output_bytes, false).unwrap();
-        let result_providers = solidity_active_providers._0;
+        let active = match decode_active_providers(output_bytes) {
+            Ok(active) => active,
+            Err(e) => {
+                error!("{}", e);
+                return VerificationReport::call_failed(mode, "getActiveProviders");
+            }
+        };
 
-        info!("Retrieved {} active providers", result_providers.len());
-        for (i, provider) in result_providers.iter().enumerate() {
+        info!("Retrieved {} active providers", active.len());
+        for (i, provider) in active.iter().enumerate() {
             info!("Provider {}: {}", i + 1, provider.name);
             info!("  Config URL: {}", provider.configUrl);
             info!("  Active: {}", provider.active);
-
-            let expected_provider = expected_providers.iter().find(|p| p.name == provider.name);
-            if let Some(expected) = expected_provider {
-                assert_eq!(expected.name, provider.name);
-                assert_eq!(expected.configUrl, provider.configUrl);
-                assert_eq!(expected.active, provider.active);
-                info!("  ✓ Provider verified successfully");
-            } else {
-                info!("  ⚠ Provider not found in expected data");
-            }
         }
-    });
+        for provider in unexpected_oidc_providers(expected, &active) {
+            context.diagnostics.emit(
+                DiagnosticCode::UnexpectedOidcProvider,
+                format!("Active provider {} is not in {}", provider.name, source),
+            );
+        }
+        record_mismatches(
+            mode,
+            "active OIDC providers",
+            active.len(),
+            compare_oidc_providers(expected, &active),
+        )
+    })
+    .unwrap_or_else(|| VerificationReport::call_failed(mode, "getActiveProviders"))
 }
 
 /// Execute JWK management operations
 pub fn execute_jwk_operations<DB>(
     db: DB,
     env: Env,
+    caller: Address,
     bundle_state: Option<BundleState>,
+    context: &RunContext,
 ) -> Result<(Vec<alloy_primitives::Log>, BundleState), String>
 where
     DB: revm::DatabaseRef + Clone,
@@ -363,12 +1119,13 @@ where
     info!("=== Starting JWK Management Operations ===");
 
     // Create transaction to get observed JWKs
-    let get_tx = call_get_observed_jwks();
+    let get_tx = call_get_observed_jwks(caller, context.registry.jwk_manager_addr());
 
     // Execute get transaction
     info!("Executing getObservedJWKs transaction...");
-    let get_result = execute_revm_sequential(db, SpecId::LATEST, env, &[get_tx], bundle_state)
-        .map_err(|_| "get transaction failed".to_string())?;
+    let get_result =
+        execute_revm_sequential(db, SpecId::LATEST, env, &[get_tx], bundle_state, context)
+            .map_err(|_| "get transaction failed".to_string())?;
 
     let (get_results, _) = get_result;
 
@@ -400,7 +1157,10 @@ where
                         }
                     }
                     Err(e) => {
-                        warn!("Failed to decode getObservedJWKs result: {:?}", e);
+                        context.diagnostics.emit(
+                            DiagnosticCode::UndecodableJwks,
+                            format!("Failed to decode getObservedJWKs result: {:?}", e),
+                        );
                         debug!("Raw output: {:?}", output);
                     }
                 }
@@ -432,9 +1192,10 @@ mod tests {
     use tracing::Level;
 
     use crate::{
-        execute,
+        execute::{self, BlockContext, DeployOptions},
         genesis::GenesisConfig,
-        post_genesis::{verify_jwks, verify_oidc_providers},
+        post_genesis::{VerifyMode, verify_jwks, verify_oidc_providers},
+        utils::SYSTEM_CALLER,
     };
 
     use super::*;
@@ -558,16 +1319,41 @@ mod tests {
         let jwk_file_path = config.jwk_template_abs().to_string_lossy().to_string();
         let oidc_file_path = config.oidc_provider_abs().to_string_lossy().to_string();
         
+        let block = BlockContext::default();
         let (db, bundle_state) = execute::genesis_generate(
             &config.out_dir_abs().to_string_lossy(),
             &config.final_output_dir_abs().to_string_lossy(),
             &genesis_config,
             Some(jwk_file_path.clone()),
             Some(oidc_file_path.clone()),
+            &block,
+            &DeployOptions::default(),
+        )
+        .unwrap();
+
+        let mut report = verify_jwks(
+            db.clone(),
+            bundle_state.clone(),
+            &block,
+            SYSTEM_CALLER,
+            &jwk_file_path,
+            VerifyMode::CollectAll,
+            &RunContext::default(),
+        );
+        report.merge(verify_oidc_providers(
+            db.clone(),
+            bundle_state.clone(),
+            &block,
+            SYSTEM_CALLER,
+            &oidc_file_path,
+            VerifyMode::CollectAll,
+            &RunContext::default(),
+        ));
+        assert!(
+            report.passed(),
+            "verification failures: {:?}",
+            report.checks
         );
-        
-        verify_jwks(db.clone(), bundle_state.clone(), &jwk_file_path);
-        verify_oidc_providers(db.clone(), bundle_state.clone(), &oidc_file_path);
     }
 
     #[test]
@@ -577,6 +1363,95 @@ mod tests {
         assert!(!jwk.data.is_empty());
     }
 
+    #[test]
+    fn test_jwk_manager_view_results_are_decoded() {
+        let output = getActiveProvidersCall::abi_encode_returns(&(vec![OIDCProvider {
+            name: "https://accounts.google.com".to_string(),
+            configUrl: "https://accounts.google.com/.well-known/openid-configuration".to_string(),
+            active: true,
+            onchain_block_number: 0,
+        }],));
+        let providers = decode_active_providers(&output).unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].name, "https://accounts.google.com");
+        assert!(providers[0].active);
+
+        let jwk = create_test_rsa_jwk("test-key", "RS256", "AQAB", "test-modulus");
+        let output = getObservedJWKsCall::abi_encode_returns(&(AllProvidersJWKs {
+            entries: vec![create_provider_jwks("https://test.com", 2, vec![jwk])],
+        },));
+        let observed = decode_observed_jwks(&output).unwrap();
+        assert_eq!(observed.entries[0].issuer, "https://test.com");
+        assert_eq!(observed.entries[0].version, 2);
+
+        // Nothing is deployed, so the call succeeds with empty output that does not decode
+        let err = query_active_oidc_providers(
+            revm::InMemoryDB::default(),
+            BundleState::default(),
+            &BlockContext::default(),
+            SYSTEM_CALLER,
+            &RunContext::default(),
+        )
+        .unwrap_err();
+        assert!(
+            err.starts_with("Failed to decode getActiveProviders"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_jwks_and_oidc_providers_are_compared() {
+        let jwk = |kid: &str| create_test_rsa_jwk(kid, "RS256", "AQAB", "test-modulus");
+        let expected = vec![
+            create_provider_jwks("https://a.com", 1, vec![jwk("a1"), jwk("a2")]),
+            create_provider_jwks("https://b.com", 1, vec![jwk("b1")]),
+        ];
+        assert_eq!(compare_jwks(&expected, &expected), Vec::new());
+
+        let actual = vec![
+            create_provider_jwks("https://a.com", 2, vec![jwk("a1"), jwk("a3")]),
+            create_provider_jwks("https://c.com", 1, vec![jwk("c1")]),
+        ];
+        let fields: Vec<String> = compare_jwks(&expected, &actual)
+            .into_iter()
+            .map(|mismatch| mismatch.field)
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["https://a.com version", "https://a.com JWK 1 data"]
+        );
+
+        let provider = |name: &str, active: bool| OIDCProvider {
+            name: name.to_string(),
+            configUrl: format!("{}/.well-known/openid-configuration", name),
+            active,
+            onchain_block_number: 0,
+        };
+        let expected = vec![
+            provider("https://a.com", true),
+            provider("https://b.com", false),
+        ];
+        let actual = vec![
+            provider("https://a.com", true),
+            provider("https://c.com", true),
+        ];
+        assert_eq!(compare_oidc_providers(&expected, &actual), Vec::new());
+        assert_eq!(
+            unexpected_oidc_providers(&expected, &actual)
+                .map(|provider| provider.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["https://c.com"]
+        );
+
+        let mismatches = compare_oidc_providers(&expected, &actual[1..]);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].to_string(),
+            "https://a.com listed as active: expected true, actual false"
+        );
+    }
+
     #[test]
     fn test_provider_jwks_creation() {
         let jwk = create_test_rsa_jwk("test-key", "RS256", "AQAB", "test-modulus");
@@ -612,7 +1487,9 @@ mod tests {
         assert_eq!(jwks.entries[0].jwks[0].variant, 1);
         assert_eq!(
             jwks.entries[0].jwks[0].data,
-            "0x0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20"
+            JsonJWKData::Hex(
+                "0x0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20".to_string()
+            )
         );
     }
 
@@ -620,7 +1497,7 @@ mod tests {
     fn test_upsert_observed_jwks() {
         // This test would require a real file, so we'll just test the function signature
         // In a real scenario, you would create a temporary file and test with it
-        let result = upsert_observed_jwks("nonexistent_file.json");
+        let result = read_jwks_input("nonexistent_file.json", &RunContext::default());
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Failed to read JWKS file"));
     }
@@ -655,10 +1532,419 @@ mod tests {
         assert_eq!(providers.providers[1].active, false);
     }
 
+    #[test]
+    fn test_invalid_jwk_data_names_issuer_index_and_position() {
+        assert_eq!(decode_jwk_data("0x0a0b").unwrap(), vec![0x0a, 0x0b]);
+        assert_eq!(decode_jwk_data("0a0b").unwrap(), vec![0x0a, 0x0b]);
+        assert_eq!(
+            decode_jwk_data("0x0a0g").unwrap_err(),
+            "invalid character 'g' at position 5 in \"0x0a0g\""
+        );
+        assert_eq!(
+            decode_jwk_data("0a0g").unwrap_err(),
+            "invalid character 'g' at position 3 in \"0a0g\""
+        );
+        assert!(
+            decode_jwk_data("0x0a0")
+                .unwrap_err()
+                .starts_with("odd number of hex digits (3)")
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwks.json");
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "entries": [{
+                    "issuer": "https://accounts.google.com",
+                    "version": 1,
+                    "jwks": [
+                        { "variant": 1, "data": "0x0102" },
+                        { "variant": 1, "data": "0x01z2" }
+                    ]
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let err = read_jwks_from_file(path.to_str().unwrap(), &RunContext::default()).unwrap_err();
+        assert_eq!(
+            err,
+            "Failed to decode hex data of JWK 1 of issuer \"https://accounts.google.com\": invalid character 'z' at position 3 in \"0x01z2\""
+        );
+    }
+
+    /// base64url of an RSA modulus of `len` bytes
+    fn rsa_modulus(len: usize) -> String {
+        URL_SAFE_NO_PAD.encode(vec![0xc5; len])
+    }
+
+    #[test]
+    fn test_rsa_jwk_members_are_validated() {
+        for len in RSA_MODULUS_SIZES {
+            assert_eq!(validate_rsa_jwk("AQAB", &rsa_modulus(len)), Ok(()));
+        }
+        assert_eq!(
+            validate_rsa_jwk("AQAB", &rsa_modulus(255)),
+            Err("n is 255 bytes, expected an RSA modulus of [256, 384, 512] bytes".to_string())
+        );
+        assert_eq!(
+            validate_rsa_jwk("", &rsa_modulus(256)),
+            Err("e is empty".to_string())
+        );
+        let err = validate_rsa_jwk("AQAB", "test-modulus!").unwrap_err();
+        assert!(
+            err.starts_with("n \"test-modulus!\" is not base64url"),
+            "{}",
+            err
+        );
+        let err = validate_rsa_jwk("AQAB=", &rsa_modulus(256)).unwrap_err();
+        assert!(err.starts_with("e \"AQAB=\" is not base64url"), "{}", err);
+
+        // Hex-encoded RSA JWKs in a JWKs file are checked too
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwks.json");
+        let truncated = create_test_rsa_jwk("kid", "RS256", "AQAB", &rsa_modulus(128));
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "entries": [{
+                    "issuer": "https://accounts.google.com",
+                    "version": 1,
+                    "jwks": [{ "variant": 0, "data": format!("0x{}", hex::encode(&truncated.data)) }]
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let err = read_jwks_from_file(path.to_str().unwrap(), &RunContext::default()).unwrap_err();
+        assert!(
+            err.contains(
+                "Invalid RSA JWK 0 of issuer \"https://accounts.google.com\": n is 128 bytes"
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_jwk_objects_are_encoded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwks.json");
+        let ec_jwk =
+            serde_json::json!({ "kty": "EC", "kid": "ec-kid", "crv": "P-256", "x": "x", "y": "y" });
+        let n = rsa_modulus(256);
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "entries": [{
+                    "issuer": "https://accounts.google.com",
+                    "version": 1,
+                    "jwks": [
+                        { "variant": 0, "data": { "kty": "RSA", "kid": "rsa-kid", "alg": "RS256", "e": "AQAB", "n": n, "use": "sig" }, "remove": true },
+                        { "variant": 1, "data": ec_jwk }
+                    ]
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+        let jwks = &read_jwks_from_file(path, &RunContext::default()).unwrap()[0].jwks;
+        assert_eq!(jwks[0].variant, 0);
+        assert_eq!(
+            jwks[0].data,
+            create_test_rsa_jwk("rsa-kid", "RS256", "AQAB", &n).data
+        );
+        let unsupported = UnsupportedJWK::abi_decode(&jwks[1].data, true).unwrap();
+        assert_eq!(unsupported.id, Bytes::from_static(b"ec-kid"));
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&unsupported.payload).unwrap(),
+            ec_jwk
+        );
+        assert_eq!(
+            read_revoked_jwks(path, &RunContext::default()).unwrap()[0].jwkId,
+            Bytes::from_static(b"rsa-kid")
+        );
+
+        let rsa = |fields: serde_json::Value| fields.as_object().unwrap().clone();
+        assert_eq!(
+            encode_jwk_object(
+                0,
+                &rsa(serde_json::json!({ "kty": "RSA", "kid": "k", "e": "AQAB", "n": "n" }))
+            ),
+            Err("missing alg".to_string())
+        );
+        assert_eq!(
+            encode_jwk_object(0, &rsa(ec_jwk.clone())),
+            Err("variant 0 is for RSA keys, not kty \"EC\"".to_string())
+        );
+        assert_eq!(
+            encode_jwk_object(1, &rsa(serde_json::json!({ "kty": "RSA" }))),
+            Err("RSA keys are variant 0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_issuer_and_config_urls_are_validated() {
+        assert_eq!(validate_issuer("https://accounts.google.com"), Ok(()));
+        assert_eq!(
+            validate_issuer(
+                "gravity://31337/event?address=0x5FbDB2315678afecb367f032d93F642f64180aa3"
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate_issuer("accounts.google.com"),
+            Err(
+                "\"accounts.google.com\" has no scheme, expected https:// or gravity://"
+                    .to_string()
+            )
+        );
+        assert!(
+            validate_issuer("http://accounts.google.com")
+                .unwrap_err()
+                .contains("uses http://")
+        );
+        assert!(
+            validate_issuer("https:///path")
+                .unwrap_err()
+                .contains("has no host")
+        );
+
+        assert_eq!(
+            validate_oidc_provider(
+                "https://accounts.google.com",
+                "https://accounts.google.com/.well-known/openid-configuration"
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate_oidc_provider("gravity://31337/event", "http://localhost:8545"),
+            Ok(())
+        );
+        let err =
+            validate_oidc_provider("https://accounts.google.com", "http://accounts.google.com")
+                .unwrap_err();
+        assert!(
+            err.starts_with("OIDC provider \"https://accounts.google.com\": invalid configUrl"),
+            "{}",
+            err
+        );
+        let err = validate_oidc_provider("google", "https://accounts.google.com").unwrap_err();
+        assert!(err.contains("invalid name"), "{}", err);
+
+        // The shipped provider file must keep loading
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../generate/jwks_provider.json"
+        );
+        assert_eq!(
+            read_oidc_providers_from_file(path, &RunContext::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_revoked_jwks_and_inactive_providers() {
+        let rsa_jwk = create_test_rsa_jwk("revoked-kid", "RS256", "AQAB", &rsa_modulus(384));
+        let unsupported = UnsupportedJWK {
+            id: Bytes::from_static(b"unsupported-id"),
+            payload: Bytes::from_static(b"{}"),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let jwks_path = dir.path().join("jwks.json");
+        std::fs::write(
+            &jwks_path,
+            serde_json::json!({
+                "entries": [{
+                    "issuer": "https://accounts.google.com",
+                    "version": 1,
+                    "jwks": [
+                        { "variant": 0, "data": format!("0x{}", hex::encode(&rsa_jwk.data)), "remove": true },
+                        { "variant": 1, "data": format!("0x{}", hex::encode(unsupported.abi_encode())) },
+                        { "variant": 1, "data": format!("0x{}", hex::encode(unsupported.abi_encode())), "remove": true }
+                    ]
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let jwks_path = jwks_path.to_str().unwrap();
+        assert_eq!(
+            read_jwks_from_file(jwks_path, &RunContext::default()).unwrap()[0]
+                .jwks
+                .len(),
+            3
+        );
+        let revoked = read_revoked_jwks(jwks_path, &RunContext::default()).unwrap();
+        assert_eq!(revoked.len(), 2);
+        assert_eq!(revoked[0].issuer, "https://accounts.google.com");
+        assert_eq!(revoked[0].jwkId, Bytes::from_static(b"revoked-kid"));
+        assert_eq!(revoked[1].jwkId, Bytes::from_static(b"unsupported-id"));
+        let jwks_input = read_jwks_input(jwks_path, &RunContext::default()).unwrap();
+        let labels: Vec<_> = revoke_jwks(SYSTEM_CALLER, &RunContext::default(), &jwks_input)
+            .unwrap()
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        assert_eq!(labels, ["JWKManager.addPatch[0]", "JWKManager.addPatch[1]"]);
+
+        let oidc_path = dir.path().join("oidc.json");
+        std::fs::write(
+            &oidc_path,
+            serde_json::json!({
+                "providers": [
+                    { "name": "https://a.com", "configUrl": "https://a.com/.well-known/openid-configuration", "active": false },
+                    { "name": "https://b.com", "configUrl": "https://b.com/.well-known/openid-configuration", "active": true }
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let oidc_input =
+            read_oidc_input(oidc_path.to_str().unwrap(), &RunContext::default()).unwrap();
+        let labels: Vec<_> =
+            upsert_oidc_providers(SYSTEM_CALLER, &RunContext::default(), &oidc_input)
+                .unwrap()
+                .into_iter()
+                .map(|(label, _)| label)
+                .collect();
+        assert_eq!(
+            labels,
+            [
+                "JWKManager.upsertOIDCProvider[0]",
+                "JWKManager.removeOIDCProvider[0]",
+                "JWKManager.upsertOIDCProvider[1]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_standard_config_url_is_reported_once_per_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let oidc_path = dir.path().join("oidc.json");
+        std::fs::write(
+            &oidc_path,
+            serde_json::json!({
+                "providers": [
+                    { "name": "https://a.com", "configUrl": "https://a.com/config", "active": true },
+                    { "name": "gravity://31337/event", "configUrl": "http://localhost:8545", "active": true }
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let oidc_path = oidc_path.to_str().unwrap();
+        let context = RunContext::default();
+        let oidc_input = read_oidc_input(oidc_path, &context).unwrap();
+        upsert_oidc_providers(SYSTEM_CALLER, &context, &oidc_input).unwrap();
+        read_oidc_providers_from_file(oidc_path, &context).unwrap();
+
+        let emitted = context.diagnostics.emitted();
+        assert_eq!(emitted.len(), 1, "{:?}", emitted);
+        assert_eq!(emitted[0].code, DiagnosticCode::NonStandardOidcConfigUrl);
+        assert!(emitted[0].message.contains("https://a.com/config"));
+    }
+
+    #[test]
+    fn test_jwks_and_oidc_directories_are_merged() {
+        let rsa_jwk = create_test_rsa_jwk("kid", "RS256", "AQAB", &rsa_modulus(384));
+        let provider = |issuer: &str, version: u64| {
+            serde_json::json!({
+                "issuer": issuer,
+                "version": version,
+                "jwks": [{ "variant": 0, "data": format!("0x{}", hex::encode(&rsa_jwk.data)) }]
+            })
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let jwks_dir = dir.path().join("jwks");
+        fs::create_dir(&jwks_dir).unwrap();
+        let a = provider("https://a.com", 1);
+        let b = provider("https://b.com", 2);
+        fs::write(jwks_dir.join("b.json"), b.to_string()).unwrap();
+        fs::write(jwks_dir.join("a.json"), a.to_string()).unwrap();
+        fs::write(jwks_dir.join("a-copy.json"), a.to_string()).unwrap();
+        fs::write(jwks_dir.join("notes.txt"), "not a provider").unwrap();
+        let jwks_file = dir.path().join("jwks.json");
+        fs::write(
+            &jwks_file,
+            serde_json::json!({ "entries": [a, b] }).to_string(),
+        )
+        .unwrap();
+
+        let merged =
+            read_jwks_from_file(jwks_dir.to_str().unwrap(), &RunContext::default()).unwrap();
+        let single =
+            read_jwks_from_file(jwks_file.to_str().unwrap(), &RunContext::default()).unwrap();
+        let issuers: Vec<_> = merged.iter().map(|p| p.issuer.as_str()).collect();
+        assert_eq!(issuers, ["https://a.com", "https://b.com"]);
+        assert_eq!(merged.len(), single.len());
+        assert_eq!(compare_jwks(&single, &merged), Vec::new());
+        let input = read_jwks_input(jwks_dir.to_str().unwrap(), &RunContext::default()).unwrap();
+        assert_eq!(input.format, ConfigFormat::Json);
+        assert_eq!(
+            input.content,
+            serde_json::to_string(&merge_jwks_dir(jwks_dir.to_str().unwrap()).unwrap()).unwrap()
+        );
+
+        fs::write(
+            jwks_dir.join("c.json"),
+            provider("https://a.com", 3).to_string(),
+        )
+        .unwrap();
+        let err =
+            read_jwks_from_file(jwks_dir.to_str().unwrap(), &RunContext::default()).unwrap_err();
+        assert!(
+            err.contains("issuer \"https://a.com\" has version 1 in")
+                && err.ends_with(&format!(
+                    "but version 3 in {}",
+                    jwks_dir.join("c.json").display()
+                )),
+            "{}",
+            err
+        );
+
+        let oidc_dir = dir.path().join("oidc");
+        fs::create_dir(&oidc_dir).unwrap();
+        let oidc = |name: &str, active: bool| {
+            serde_json::json!({
+                "name": name,
+                "configUrl": format!("{}/.well-known/openid-configuration", name),
+                "active": active
+            })
+            .to_string()
+        };
+        fs::write(oidc_dir.join("1.json"), oidc("https://a.com", true)).unwrap();
+        fs::write(oidc_dir.join("2.json"), oidc("https://b.com", false)).unwrap();
+        fs::write(oidc_dir.join("3.json"), oidc("https://a.com", true)).unwrap();
+        let providers =
+            read_oidc_providers_from_file(oidc_dir.to_str().unwrap(), &RunContext::default())
+                .unwrap();
+        let names: Vec<_> = providers.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["https://a.com", "https://b.com"]);
+
+        fs::write(oidc_dir.join("4.json"), oidc("https://b.com", true)).unwrap();
+        let err = read_oidc_providers_from_file(oidc_dir.to_str().unwrap(), &RunContext::default())
+            .unwrap_err();
+        assert!(
+            err.contains("OIDC provider \"https://b.com\" differs between"),
+            "{}",
+            err
+        );
+
+        let empty = dir.path().join("empty");
+        fs::create_dir(&empty).unwrap();
+        let err = read_jwks_from_file(empty.to_str().unwrap(), &RunContext::default()).unwrap_err();
+        assert!(err.ends_with("holds no .json files"), "{}", err);
+    }
+
     #[test]
     fn test_upsert_oidc_providers() {
         // This test would require a real file, so we'll just test the function signature
-        let result = upsert_oidc_providers("nonexistent_provider_file.json");
+        let result = read_oidc_input("nonexistent_provider_file.json", &RunContext::default());
         assert!(result.is_err());
         assert!(
             result
@@ -671,8 +1957,9 @@ mod tests {
 // Example usage:
 //
 // ```rust
-// use crate::jwks::upsert_observed_jwks;
+// use crate::jwks::{read_jwks_input, upsert_observed_jwks};
 //
 // // Load and process JWKs from JSON file
-// upsert_observed_jwks("path/to/jwks_template.json").expect("Failed to process JWKs");
+// let jwks = read_jwks_input("path/to/jwks_template.json", &context).expect("Failed to read JWKs");
+// upsert_observed_jwks(caller, &context, &jwks).expect("Failed to process JWKs");
 // ```
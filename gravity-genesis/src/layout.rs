@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use revm::db::BundleState;
+use revm_primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    post_genesis::{VerificationReport, VerifyMode},
+    registry::ContractRegistry,
+};
+
+/// Storage position that should hold a peer contract's address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressSlotHint {
+    /// Name of the state variable, used in reports
+    pub label: String,
+    pub slot: U256,
+    /// Byte offset of the address within the slot, counted from the lowest-order byte
+    #[serde(default)]
+    pub offset: usize,
+}
+
+/// Address slot hints keyed by contract name as listed in the contract registry
+pub type LayoutHints = BTreeMap<String, Vec<AddressSlotHint>>;
+
+pub fn read_layout_hints(path: &str, registry: &ContractRegistry) -> Result<LayoutHints, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read layout hints file: {}", e))?;
+    let hints: LayoutHints = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse layout hints JSON: {}", e))?;
+    for (contract_name, slots) in &hints {
+        if registry.address_of(contract_name).is_none() {
+            return Err(format!(
+                "Unknown contract in layout hints: {}",
+                contract_name
+            ));
+        }
+        if let Some(hint) = slots.iter().find(|hint| hint.offset > 12) {
+            return Err(format!(
+                "{}.{}: offset {} leaves no room for an address",
+                contract_name, hint.label, hint.offset
+            ));
+        }
+    }
+    Ok(hints)
+}
+
+/// Read the address stored at `hint` in the post-genesis storage of `contract`
+///
+/// Slots genesis never wrote read as zero, like they would on chain.
+fn read_address_slot(
+    bundle_state: &BundleState,
+    contract: Address,
+    hint: &AddressSlotHint,
+) -> Address {
+    let value = bundle_state
+        .state
+        .get(&contract)
+        .and_then(|account| account.storage.get(&hint.slot))
+        .map(|slot| slot.present_value)
+        .unwrap_or_default();
+    let word = value.to_be_bytes::<32>();
+    let end = 32 - hint.offset;
+    Address::from_slice(&word[end - 20..end])
+}
+
+/// Check that every hinted address slot of the system contracts holds a non-zero address
+///
+/// A zero address where a peer contract's address is expected means the
+/// contract was deployed but never wired up during genesis.
+pub fn verify_address_slots(
+    bundle_state: &BundleState,
+    hints: &LayoutHints,
+    mode: VerifyMode,
+    registry: &ContractRegistry,
+) -> VerificationReport {
+    let mut report = VerificationReport::new(mode);
+    for (contract_name, contract_address) in registry.contracts() {
+        let Some(slots) = hints.get(contract_name) else {
+            continue;
+        };
+        for hint in slots {
+            let actual = read_address_slot(bundle_state, *contract_address, hint);
+            report.record(
+                format!("{}.{} (slot {})", contract_name, hint.label, hint.slot),
+                "non-zero address",
+                actual,
+                actual != Address::ZERO,
+            );
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{GENESIS_ADDR, STAKE_CONFIG_ADDR};
+    use revm::db::{AccountStatus, BundleAccount};
+    use revm_primitives::{AccountInfo, HashMap, StorageSlot, address};
+
+    fn bundle_with_storage(contract: Address, storage: Vec<(U256, U256)>) -> BundleState {
+        let storage: HashMap<U256, StorageSlot> = storage
+            .into_iter()
+            .map(|(slot, value)| (slot, StorageSlot::new_changed(U256::ZERO, value)))
+            .collect();
+        let mut bundle_state = BundleState::default();
+        bundle_state.state.insert(
+            contract,
+            BundleAccount::new(
+                None,
+                Some(AccountInfo::default()),
+                storage,
+                AccountStatus::Changed,
+            ),
+        );
+        bundle_state
+    }
+
+    #[test]
+    fn test_zero_address_slot_is_reported() {
+        let peer = address!("0000000000000000000000000000000000002013");
+        // Slot 0 holds a packed bool at byte 0 followed by the peer address at offset 1
+        let packed = (U256::from_be_slice(peer.as_slice()) << 8) | U256::from(1);
+        let bundle_state = bundle_with_storage(GENESIS_ADDR, vec![(U256::ZERO, packed)]);
+        let hints: LayoutHints = serde_json::from_value(serde_json::json!({
+            "Genesis": [
+                { "label": "validatorManager", "slot": "0x0", "offset": 1 },
+                { "label": "stakeConfig", "slot": "0x1" }
+            ]
+        }))
+        .unwrap();
+
+        let report = verify_address_slots(
+            &bundle_state,
+            &hints,
+            VerifyMode::CollectAll,
+            &ContractRegistry::default(),
+        );
+        assert_eq!(report.checks.len(), 2);
+        assert!(report.checks[0].passed);
+        assert_eq!(report.checks[0].actual, peer.to_string());
+
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].field, "Genesis.stakeConfig (slot 1)");
+        assert_eq!(failures[0].actual, Address::ZERO.to_string());
+    }
+
+    #[test]
+    fn test_untouched_contract_reads_zero() {
+        let bundle_state = BundleState::default();
+        let hints: LayoutHints = serde_json::from_value(serde_json::json!({
+            "StakeConfig": [{ "label": "owner", "slot": "0x5" }]
+        }))
+        .unwrap();
+
+        let report = verify_address_slots(
+            &bundle_state,
+            &hints,
+            VerifyMode::CollectAll,
+            &ContractRegistry::default(),
+        );
+        assert!(!report.passed());
+        assert_eq!(
+            read_address_slot(&bundle_state, STAKE_CONFIG_ADDR, &hints["StakeConfig"][0]),
+            Address::ZERO
+        );
+    }
+}
@@ -1,5 +1,35 @@
+pub mod accounts_writer;
+pub mod artifacts;
+pub mod attestation;
+pub mod balances;
+pub mod builder;
+pub mod chainspec;
+pub mod codehash;
+pub mod combined;
+pub mod config_format;
+pub mod config_template;
+pub mod config_validation;
+pub mod context;
+pub mod create2;
+pub mod diagnostics;
+pub mod error_signatures;
+pub mod event_log;
 pub mod execute;
-pub mod utils;
+pub mod extra_txns;
+pub mod gas_report;
 pub mod genesis;
+pub mod inspect;
+pub mod jwks;
+pub mod layout;
+pub mod linking;
+pub mod manifest;
+pub mod network_address;
 pub mod post_genesis;
-pub mod jwks;
\ No newline at end of file
+pub mod progress;
+pub mod randomness;
+pub mod registry;
+pub mod state_diff;
+pub mod state_root;
+pub mod supply_report;
+pub mod utils;
+pub mod validators_csv;
\ No newline at end of file
@@ -0,0 +1,156 @@
+use std::{collections::HashMap, path::Path};
+
+use revm_primitives::{Address, hex, keccak256};
+use walkdir::WalkDir;
+
+/// Length of a library placeholder, the same as a hex-encoded address
+const PLACEHOLDER_LEN: usize = 40;
+
+/// Placeholder solc emits for an unlinked library:
+/// `__$` + the first 34 hex characters of keccak256(fully qualified name) + `$__`
+pub fn library_placeholder(fully_qualified_name: &str) -> String {
+    let hash = hex::encode(keccak256(fully_qualified_name));
+    format!("__${}$__", &hash[..34])
+}
+
+/// Placeholder emitted by solc before 0.5: `__` + the library name, padded with `_` to 40 characters
+pub fn legacy_library_placeholder(name: &str) -> String {
+    let name: String = name.chars().take(PLACEHOLDER_LEN - 2).collect();
+    format!("__{:_<width$}", name, width = PLACEHOLDER_LEN - 2)
+}
+
+/// Replaces library placeholders in contract bytecode with deployed library addresses
+#[derive(Debug, Clone, Default)]
+pub struct LibraryLinker {
+    /// Placeholder -> (library name, address)
+    placeholders: HashMap<String, (String, Address)>,
+}
+
+impl LibraryLinker {
+    /// Register a library by plain name (`ValidatorManagerUtils`) or fully
+    /// qualified name (`src/lib/ValidatorManagerUtils.sol:ValidatorManagerUtils`)
+    pub fn add_library(&mut self, name: &str, address: Address) {
+        let short_name = name.rsplit(':').next().unwrap_or(name);
+        for placeholder in [
+            library_placeholder(name),
+            legacy_library_placeholder(short_name),
+        ] {
+            self.placeholders
+                .insert(placeholder, (name.to_string(), address));
+        }
+    }
+
+    /// Linker for every contract in `contracts`
+    ///
+    /// solc derives placeholders from the fully qualified library name, so when
+    /// `project_root` is given every `<Name>.sol` under its `src` directory is
+    /// also registered as `src/<path>/<Name>.sol:<Name>`.
+    pub fn from_contracts<S: AsRef<str>>(
+        contracts: &[(S, Address)],
+        project_root: Option<&Path>,
+    ) -> Self {
+        let mut linker = Self::default();
+        for (name, address) in contracts {
+            linker.add_library(name.as_ref(), *address);
+        }
+        let Some(project_root) = project_root else {
+            return linker;
+        };
+        for entry in WalkDir::new(project_root.join("src"))
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            let path = entry.path();
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if path.extension().is_none_or(|ext| ext != "sol") {
+                continue;
+            }
+            let Some((_, address)) = contracts.iter().find(|(name, _)| name.as_ref() == stem)
+            else {
+                continue;
+            };
+            let Ok(relative) = path.strip_prefix(project_root) else {
+                continue;
+            };
+            let fully_qualified_name = format!("{}:{}", relative.to_string_lossy(), stem);
+            linker.add_library(&fully_qualified_name, *address);
+        }
+        linker
+    }
+
+    /// Substitute every known placeholder in `bytecode_hex`
+    ///
+    /// Fails with the contract name and the first placeholder left unresolved.
+    pub fn link(&self, contract_name: &str, bytecode_hex: &str) -> Result<String, String> {
+        if !bytecode_hex.contains("__") {
+            return Ok(bytecode_hex.to_string());
+        }
+        let mut linked = bytecode_hex.to_string();
+        for (placeholder, (_, address)) in &self.placeholders {
+            if linked.contains(placeholder.as_str()) {
+                linked = linked.replace(placeholder.as_str(), &hex::encode(address));
+            }
+        }
+        if let Some(start) = linked.find("__") {
+            let end = (start + PLACEHOLDER_LEN).min(linked.len());
+            return Err(format!(
+                "{}: unresolved library placeholder {}",
+                contract_name,
+                &linked[start..end]
+            ));
+        }
+        Ok(linked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::address;
+
+    const LIBRARY: Address = address!("0000000000000000000000000000000000002014");
+    const FQN: &str = "src/lib/MathLib.sol:MathLib";
+
+    #[test]
+    fn test_placeholders_are_address_sized() {
+        assert_eq!(library_placeholder(FQN).len(), PLACEHOLDER_LEN);
+        assert_eq!(legacy_library_placeholder("MathLib").len(), PLACEHOLDER_LEN);
+        assert_eq!(
+            legacy_library_placeholder("MathLib"),
+            "__MathLib_______________________________"
+        );
+    }
+
+    #[test]
+    fn test_link_substitutes_placeholders() {
+        let mut linker = LibraryLinker::default();
+        linker.add_library(FQN, LIBRARY);
+
+        let bytecode = format!(
+            "6080{}73{}00",
+            library_placeholder(FQN),
+            legacy_library_placeholder("MathLib")
+        );
+        let linked = linker.link("Consumer", &bytecode).unwrap();
+        let address = hex::encode(LIBRARY);
+        assert_eq!(linked, format!("6080{}73{}00", address, address));
+    }
+
+    #[test]
+    fn test_unresolved_placeholder_names_contract() {
+        let linker = LibraryLinker::from_contracts(&[("MathLib", LIBRARY)], None);
+        let missing = library_placeholder("src/lib/Other.sol:Other");
+        let err = linker
+            .link("Consumer", &format!("6080{}00", missing))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            format!("Consumer: unresolved library placeholder {}", missing)
+        );
+
+        // Bytecode without placeholders is returned unchanged
+        assert_eq!(linker.link("Consumer", "60806040").unwrap(), "60806040");
+    }
+}
@@ -1,83 +1,510 @@
 use anyhow::Result;
-use clap::Parser;
-use gravity_genesis::{execute, genesis::GenesisConfig, post_genesis};
-use serde_json;
-use std::fs;
-use tracing::{Level, info};
+use clap::{Parser, Subcommand, ValueEnum};
+use gravity_genesis::{
+    artifacts::{self, OutputNames},
+    attestation, balances,
+    builder::GenesisBuilder,
+    chainspec::ExportFormat,
+    codehash, config_format, config_template, config_validation,
+    context::RunContext,
+    create2,
+    diagnostics::DiagnosticCode,
+    error_signatures,
+    execute::{BlockContext, DeployMode, DeployOptions},
+    genesis::{self, GenesisConfig},
+    inspect, layout,
+    linking::LibraryLinker,
+    post_genesis::{self, VerificationReport, VerifyMode, VerifyOptions},
+    progress,
+    registry::{self, ContractRegistry},
+    state_diff,
+    supply_report::{self, SupplyReport},
+    utils, validators_csv,
+};
+use revm_primitives::{Address, B256, U256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::IsTerminal,
+    path::Path,
+    process::ExitCode,
+    sync::{Arc, Mutex, PoisonError},
+};
+use tracing::{Level, error, info, warn};
+use tracing_appender::non_blocking::{NonBlockingBuilder, WorkerGuard};
+
+/// Exit code when genesis was generated but post-genesis verification failed
+const VERIFICATION_FAILED_EXIT_CODE: u8 = 2;
+
+/// Exit code when genesis was generated and verified but differs from the `--golden` file
+const GOLDEN_MISMATCH_EXIT_CODE: u8 = 3;
+
+/// Worker writing the `--log-file` records, shared with the panic hook
+///
+/// Dropping the guard blocks until the worker has written every queued record.
+static LOG_WORKER: Mutex<Option<WorkerGuard>> = Mutex::new(None);
+
+/// Write every queued file log record and stop the worker
+///
+/// Records logged afterwards are dropped, so this is only called on the way out.
+fn flush_file_logs() {
+    let guard = LOG_WORKER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    drop(guard);
+}
 
 // Custom guard to ensure proper log flushing
 struct LogGuard {
-    _guard: Option<tracing_appender::non_blocking::WorkerGuard>,
     has_file_logging: bool,
 }
 
 impl LogGuard {
-    fn new(guard: Option<tracing_appender::non_blocking::WorkerGuard>) -> Self {
+    fn new(guard: Option<WorkerGuard>) -> Self {
         let has_file_logging = guard.is_some();
-        Self {
-            _guard: guard,
-            has_file_logging,
-        }
-    }
-
-    fn flush_and_wait(&self) {
-        if self.has_file_logging {
-            tracing::info!("Ensuring all logs are written to file...");
-            // The drop of _guard will signal the background thread to finish
-            // We give it some time to complete
-            std::thread::sleep(std::time::Duration::from_millis(1000));
-        }
+        *LOG_WORKER.lock().unwrap_or_else(PoisonError::into_inner) = guard;
+        Self { has_file_logging }
     }
 }
 
 impl Drop for LogGuard {
     fn drop(&mut self) {
-        if self.has_file_logging {
-            // Ensure logs are flushed when guard is dropped
-            std::thread::sleep(std::time::Duration::from_millis(500));
-        }
+        flush_file_logs();
     }
 }
 
+/// Output format of log records
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per record, for log collectors
+    Json,
+}
+
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Enable debug logging
     #[arg(short, long)]
     debug: bool,
 
-    /// Byte code directory
+    /// Only log warnings and errors, then print a short summary of the run (ignored with --debug)
     #[arg(short, long)]
-    byte_code_dir: String,
+    quiet: bool,
+
+    /// Byte code directory
+    #[arg(short, long, required = true)]
+    byte_code_dir: Option<String>,
 
-    /// Genesis configuration file
+    /// Genesis configuration file (.json, .toml, .yaml or .yml, `-` for JSON on stdin); repeat to merge fragments in order
     #[arg(short, long, default_value = "generate/genesis_config.json")]
-    config_file: String,
+    config_file: Vec<String>,
 
-    /// Save results to file
-    #[arg(short, long)]
-    output: Option<String>,
+    /// Directory the genesis files are written to, created if missing
+    #[arg(short, long, default_value = "output")]
+    output: String,
+
+    /// Write into an output directory that already holds files, overwriting a previous genesis
+    #[arg(long)]
+    force: bool,
+
+    /// Also write accounts, contracts and metadata as one JSON document at this path
+    #[arg(long)]
+    combined_output: Option<String>,
+
+    /// Write every log emitted by the genesis transactions, with known events decoded, to this JSON file
+    #[arg(long)]
+    dump_logs: Option<String>,
+
+    /// Skip bundle_state.json, genesis_accounts.json and genesis_contracts.json
+    #[arg(long, requires = "combined_output")]
+    no_split: bool,
+
+    /// Keep the system caller's final state in bundle_state.json for debugging; it is never a genesis account
+    #[arg(long)]
+    keep_system_caller: bool,
+
+    /// Generate and verify the genesis in memory without writing any output file
+    #[arg(long, conflicts_with = "sign_key")]
+    no_write: bool,
+
+    /// Prefix of every output file name, e.g. `mainnet-` for mainnet-genesis_accounts.json
+    #[arg(long)]
+    output_prefix: Option<String>,
+
+    /// File name of genesis_accounts.json in the output directory, overriding --output-prefix
+    #[arg(long)]
+    accounts_out: Option<String>,
+
+    /// File name of genesis_contracts.json in the output directory, overriding --output-prefix
+    #[arg(long)]
+    contracts_out: Option<String>,
+
+    /// File name of bundle_state.json in the output directory, overriding --output-prefix
+    #[arg(long)]
+    bundle_out: Option<String>,
+
+    /// Directory of Foundry artifacts or storage layouts; writes genesis_storage_labeled.json
+    /// with each contract's storage decoded by variable name
+    #[arg(long)]
+    layout_dir: Option<String>,
+
+    /// Also export the genesis in this format (reth: reth_chainspec.json)
+    #[arg(long, value_enum)]
+    export: Option<ExportFormat>,
+
+    /// Keep accounts with no code, balance, nonce or storage in the exported alloc
+    #[arg(long, requires = "export")]
+    keep_empty: bool,
+
+    /// Golden genesis_accounts.json the generated genesis must match
+    #[arg(long)]
+    golden: Option<String>,
+
+    /// Overwrite the --golden file with the generated genesis instead of comparing against it
+    #[arg(long, requires = "golden")]
+    update_golden: bool,
 
     /// Log file path (optional)
     #[arg(short, long)]
     log_file: Option<String>,
 
-    /// JWKs file path (optional)
+    /// Log record format
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// JWKs file path (optional, `-` for stdin)
     #[arg(short, long)]
     jwks_file: Option<String>,
 
-    /// OIDC providers file path (optional)
+    /// OIDC providers file path (optional, `-` for stdin)
     #[arg(short, long)]
     oidc_providers_file: Option<String>,
+
+    /// Directory of `*.json` files, each holding one provider's JWKs, merged in place of --jwks-file
+    #[arg(long, conflicts_with = "jwks_file")]
+    jwks_dir: Option<String>,
+
+    /// Directory of `*.json` files, each holding one OIDC provider, merged in place of --oidc-providers-file
+    #[arg(long, conflicts_with = "oidc_providers_file")]
+    oidc_dir: Option<String>,
+
+    /// Skip the JWKs transactions with a warning if --jwks-file does not exist
+    #[arg(long, requires = "jwks_file")]
+    jwks_optional: bool,
+
+    /// Skip the OIDC provider transactions with a warning if --oidc-providers-file does not exist
+    #[arg(long, requires = "oidc_providers_file")]
+    oidc_optional: bool,
+
+    /// File of extra system calls to run after the standard genesis transactions
+    #[arg(long)]
+    extra_txns: Option<String>,
+
+    /// Genesis block number
+    #[arg(long, default_value_t = 0)]
+    block_number: u64,
+
+    /// Genesis timestamp in unix seconds (defaults to now), used for block.timestamp, the
+    /// manifest and the chain spec header
+    #[arg(long, visible_alias = "block-timestamp")]
+    genesis_timestamp: Option<u64>,
+
+    /// Genesis block basefee in wei
+    #[arg(long, default_value_t = 0)]
+    block_basefee: u64,
+
+    /// Genesis block coinbase (beneficiary) address
+    #[arg(long, default_value_t = Address::ZERO)]
+    block_coinbase: Address,
+
+    /// Genesis block gas limit; each transaction's gas limit is capped to it (unlimited by default)
+    #[arg(long)]
+    block_gas_limit: Option<u64>,
+
+    /// CSV of validators (operator,aptos_address,consensus_key,voting_power,validator_network_addr,fullnode_network_addr)
+    /// used instead of the validator arrays of the config, `-` for stdin
+    #[arg(long)]
+    validators_csv: Option<String>,
+
+    /// Pass consensus public keys as raw UTF-8 bytes instead of hex-decoding them
+    #[arg(long)]
+    raw_consensus_keys: bool,
+
+    /// JSON file mapping contract names to genesis balances in wei, overriding the defaults
+    #[arg(long)]
+    balances_file: Option<String>,
+
+    /// JSON file mapping contract names to approved runtime code hashes by version
+    #[arg(long)]
+    codehash_db: Option<String>,
+
+    /// bytecode_manifest.json mapping contract names to the expected keccak256 of their runtime bytecode
+    #[arg(long)]
+    bytecode_manifest: Option<String>,
+
+    /// Largest runtime bytecode accepted, in bytes (EIP-170 limit by default)
+    #[arg(long, default_value_t = revm_primitives::MAX_CODE_SIZE)]
+    max_code_size: usize,
+
+    /// Deploy without the contracts whose .hex file is missing, with a warning, instead of failing
+    #[arg(long)]
+    allow_missing: bool,
+
+    /// Deploy only these contracts (comma-separated), e.g. to debug one subsystem;
+    /// Genesis.initialize runs only when Genesis is among them
+    #[arg(long, value_delimiter = ',')]
+    only: Vec<String>,
+
+    /// Foundry project root whose src/ is scanned to resolve fully qualified library placeholders
+    #[arg(long)]
+    link_sources: Option<String>,
+
+    /// Hex-encoded secp256k1 private key used to sign the genesis state root and manifest hash
+    #[arg(long)]
+    sign_key: Option<String>,
+
+    /// Directory of contract ABI JSON files (e.g. Foundry's out/) used to name revert errors
+    #[arg(long)]
+    abi_dir: Option<String>,
+
+    /// JSON file listing storage slots of each contract that should hold a contract address
+    #[arg(long)]
+    layout_hints: Option<String>,
+
+    /// Foundry output directory (out/) whose deployedBytecode each genesis contract's code is checked against
+    #[arg(long)]
+    artifacts_dir: Option<String>,
+
+    /// bundle_state.json of an earlier genesis to run the genesis transactions on top of
+    #[arg(long)]
+    base_state: Option<String>,
+
+    /// Do not send Genesis.initialize, e.g. to deploy the contracts only or to layer
+    /// more transactions onto a --base-state that already ran it
+    #[arg(long)]
+    skip_initialize: bool,
+
+    /// Fail the run if any warning (GEN-W*** diagnostic) was emitted
+    #[arg(long)]
+    deny_warnings: bool,
+
+    /// Check every genesis transaction and report all failures instead of stopping at the first
+    #[arg(long)]
+    continue_on_error: bool,
+
+    /// Gas cap of each genesis system call; transactions over it are reported (GEN-W010)
+    #[arg(long)]
+    call_gas_limit: Option<u64>,
+
+    /// Run each genesis transaction with its gas cap as gas limit, so one that needs more fails.
+    /// A cap not given defaults to 30M, a mainnet-sized block
+    #[arg(long)]
+    enforce_gas_limits: bool,
+
+    /// Seconds each genesis transaction may run before generation aborts, naming the stuck transaction
+    #[arg(long, default_value_t = utils::DEFAULT_TX_TIME_BUDGET.as_secs())]
+    tx_timeout_secs: u64,
+
+    /// Stop verification at the first mismatch or collect every mismatch
+    #[arg(long, value_enum, default_value_t = VerifyMode::CollectAll)]
+    verify_mode: VerifyMode,
+
+    /// After generation, send Genesis.initialize again and fail verification unless it reverts as already initialized
+    #[arg(long)]
+    assert_idempotent: bool,
+
+    /// Address the genesis transactions are sent from; it is left out of the genesis state
+    #[arg(long, default_value_t = utils::SYSTEM_CALLER)]
+    system_caller: Address,
+
+    /// Balance in wei given to the system caller while the genesis transactions run
+    #[arg(long, default_value_t = utils::DEFAULT_SYSTEM_CALLER_BALANCE)]
+    system_caller_balance: U256,
+
+    /// Nonce of the deployed contract accounts (1 per EIP-161)
+    #[arg(long, default_value_t = utils::DEFAULT_CONTRACT_NONCE)]
+    contract_nonce: u64,
+
+    /// Number of largest balances listed in supply_report.json
+    #[arg(long, default_value_t = supply_report::DEFAULT_SUPPLY_REPORT_TOP)]
+    supply_report_top: usize,
+
+    /// Total wei the genesis accounts must hold; verification fails otherwise
+    #[arg(long)]
+    expected_total_supply: Option<U256>,
+
+    /// Write every verification check and the overall status to this JSON file
+    #[arg(long)]
+    verification_report: Option<String>,
+
+    /// Epoch duration EpochManager must report after genesis, in microseconds
+    #[arg(long, default_value_t = genesis::DEFAULT_EPOCH_INTERVAL_MICROS)]
+    epoch_interval_micros: u64,
+
+    /// JSON file mapping contract names to addresses, replacing the built-in contract table
+    #[arg(long)]
+    registry: Option<String>,
+
+    /// Place contracts at the registry addresses or at CREATE2-derived addresses
+    #[arg(long, value_enum, default_value_t = DeployMode::Fixed)]
+    deploy_mode: DeployMode,
+
+    /// Deployer address used to derive CREATE2 addresses
+    #[arg(long, default_value_t = create2::DEFAULT_CREATE2_DEPLOYER)]
+    create2_deployer: Address,
+
+    /// Salt used to derive CREATE2 addresses
+    #[arg(long, default_value_t = B256::ZERO)]
+    create2_salt: B256,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the contracts deployed at genesis and their addresses
+    ListContracts {
+        /// Report whether each contract's .hex file is present in this directory
+        #[arg(long)]
+        byte_code_dir: Option<String>,
+    },
+    /// Print an account of a generated bundle_state.json, decoding known storage slots
+    Inspect {
+        /// bundle_state.json written by genesis generation
+        #[arg(long, default_value = "output/bundle_state.json")]
+        bundle_state: String,
+
+        /// Contract name from the registry or account address
+        #[arg(long)]
+        address: String,
+
+        /// JSON file of address slot hints; the inspected contract's hints label its slots
+        #[arg(long)]
+        layout_hints: Option<String>,
+
+        /// Foundry artifact or storageLayout JSON of the inspected contract
+        #[arg(long)]
+        storage_layout: Option<String>,
+    },
+    /// Compare two genesis_accounts.json files, e.g. before and after a contract upgrade
+    Diff {
+        /// genesis_accounts.json of the earlier genesis
+        before: String,
+
+        /// genesis_accounts.json of the later genesis
+        after: String,
+
+        /// Print the differences as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write a template genesis_config.json with one placeholder validator and a README of its fields
+    InitConfig {
+        /// Directory the template and its README are written to
+        #[arg(long, default_value = ".")]
+        output_dir: String,
+    },
+    /// Verify a previously generated output directory without regenerating it
+    Verify {
+        /// Directory holding genesis_accounts.json, bundle_state.json and manifest.json
+        #[arg(long)]
+        output_dir: String,
+
+        /// Genesis configuration file (.json, .toml, .yaml or .yml, `-` for JSON on stdin); repeat to merge fragments in order
+        #[arg(long, default_value = "generate/genesis_config.json")]
+        config_file: Vec<String>,
+
+        /// JWKs file path (optional, `-` for stdin)
+        #[arg(long)]
+        jwks_file: Option<String>,
+
+        /// OIDC providers file path (optional, `-` for stdin)
+        #[arg(long)]
+        oidc_providers_file: Option<String>,
+
+        /// Directory of `*.json` files, each holding one provider's JWKs, merged in place of --jwks-file
+        #[arg(long, conflicts_with = "jwks_file")]
+        jwks_dir: Option<String>,
+
+        /// Directory of `*.json` files, each holding one OIDC provider, merged in place of --oidc-providers-file
+        #[arg(long, conflicts_with = "oidc_providers_file")]
+        oidc_dir: Option<String>,
+
+        /// JSON file listing storage slots of each contract that should hold a contract address
+        #[arg(long)]
+        layout_hints: Option<String>,
+
+        /// Stop verification at the first mismatch or collect every mismatch
+        #[arg(long, value_enum, default_value_t = VerifyMode::CollectAll)]
+        verify_mode: VerifyMode,
+    },
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     let args = Args::parse();
 
+    // Every command resolves contract addresses, so the registry goes in first
+    let mut contract_registry = match &args.registry {
+        Some(path) => registry::read_contract_registry(path).map_err(anyhow::Error::msg)?,
+        None => ContractRegistry::default(),
+    };
+    if args.command.is_none() && args.deploy_mode == DeployMode::Create2 {
+        contract_registry = create2::create2_registry(
+            &contract_registry,
+            args.byte_code_dir
+                .as_deref()
+                .expect("--byte-code-dir is required"),
+            args.create2_deployer,
+            args.create2_salt,
+        )
+        .map_err(anyhow::Error::msg)?;
+    }
+    let mut context = RunContext::new(contract_registry.with_system_caller(args.system_caller));
+    if let Some(abi_dir) = &args.abi_dir {
+        let signatures = error_signatures::load_error_signatures(abi_dir, &context.diagnostics)
+            .map_err(anyhow::Error::msg)?;
+        context = context.with_error_signatures(signatures);
+    }
+    let context = Arc::new(context);
+
+    if let Some(Command::ListContracts { byte_code_dir }) = &args.command {
+        println!(
+            "{}",
+            utils::list_contracts(&context.registry, byte_code_dir.as_deref())
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Inspect { .. }) = &args.command {
+        println!("{}", run_inspect(&args, &context.registry)?);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Diff { .. }) = &args.command {
+        println!("{}", run_diff(&args, &context.registry)?);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::InitConfig { output_dir }) = &args.command {
+        let (config_path, readme_path) =
+            config_template::write_config_template(Path::new(output_dir), args.force)
+                .map_err(anyhow::Error::msg)?;
+        println!(
+            "Wrote {} and {}",
+            config_path.display(),
+            readme_path.display()
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
     // Initialize logging
     let level = if args.debug {
         Level::DEBUG
+    } else if args.quiet {
+        Level::WARN
     } else {
         Level::INFO
     };
@@ -93,81 +520,844 @@ async fn main() -> Result<()> {
 
         // Set up logging to file
         let file_appender = tracing_appender::rolling::never("", log_file_path);
-        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+        // Block rather than drop records when the worker falls behind
+        let (non_blocking, guard) = NonBlockingBuilder::default()
+            .lossy(false)
+            .finish(file_appender);
 
-        tracing_subscriber::fmt()
+        let subscriber = tracing_subscriber::fmt()
             .with_max_level(level)
             .with_writer(non_blocking)
-            .with_ansi(false)
-            .init();
+            .with_ansi(false);
+        match args.log_format {
+            LogFormat::Text => subscriber.init(),
+            LogFormat::Json => subscriber.json().init(),
+        }
 
         info!("Logging to file: {}", log_file_path);
         LogGuard::new(Some(guard))
     } else {
-        // Console-only logging
-        tracing_subscriber::fmt().with_max_level(level).init();
+        // Console-only logging, written around the progress bar
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(level)
+            .with_writer({
+                let bar = context.progress.clone();
+                move || progress::LogWriter(std::io::stdout(), bar.clone())
+            });
+        match args.log_format {
+            LogFormat::Text => subscriber.init(),
+            LogFormat::Json => subscriber.json().init(),
+        }
         LogGuard::new(None)
     };
 
     // Set up panic hook to ensure logs are flushed before panic
     let has_file_logging = log_guard.has_file_logging;
+    let log_format = args.log_format;
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
+        if log_format == LogFormat::Json {
+            // Keep the panic parseable alongside the other records
+            let payload = panic_info
+                .payload()
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| {
+                    panic_info
+                        .payload()
+                        .downcast_ref::<String>()
+                        .map(String::as_str)
+                })
+                .unwrap_or("<non-string panic payload>");
+            let location = panic_info
+                .location()
+                .map(|location| location.to_string())
+                .unwrap_or_default();
+            tracing::error!(target: "panic", payload, location = %location, "PANIC");
+        }
         if has_file_logging {
             eprintln!("PANIC occurred! Ensuring all logs are written...");
             // Log the panic information
-            tracing::error!("PANIC: {}", panic_info);
+            if log_format == LogFormat::Text {
+                tracing::error!("PANIC: {}", panic_info);
+            }
             tracing::error!("Flushing logs before panic exit...");
-
-            // Give time for the background thread to write logs
-            std::thread::sleep(std::time::Duration::from_millis(1200));
-            eprintln!("Log flush attempt completed");
+            flush_file_logs();
+            eprintln!("Logs flushed");
         }
         original_hook(panic_info);
     }));
 
     info!("Starting Gravity Genesis Binary");
 
+    // The bar would only clutter logs that are not read on a terminal
+    if !args.quiet && args.log_format == LogFormat::Text && std::io::stderr().is_terminal() {
+        context.progress.enable();
+    }
+
     // Run the main logic
-    let result = run_main_logic(&args).await;
+    let result = run_main_logic(&args, &context).await;
+    context.progress.finish();
+    let result = check_diagnostics(&args, result, &context);
 
     // Ensure logs are flushed before exiting
     info!("Main execution completed");
-    log_guard.flush_and_wait();
+    drop(log_guard);
 
     result
 }
 
-async fn run_main_logic(args: &Args) -> Result<()> {
-    info!("Reading Genesis configuration from: {}", args.config_file);
-    let config_content = fs::read_to_string(&args.config_file)?;
-    let config: GenesisConfig = serde_json::from_str(&config_content)?;
+/// Output file names from `--output-prefix` and the per-file overrides
+fn output_names(args: &Args) -> OutputNames {
+    let mut names = OutputNames::with_prefix(args.output_prefix.as_deref().unwrap_or_default());
+    if let Some(accounts_out) = &args.accounts_out {
+        names.genesis_accounts = accounts_out.clone();
+    }
+    if let Some(contracts_out) = &args.contracts_out {
+        names.genesis_contracts = contracts_out.clone();
+    }
+    if let Some(bundle_out) = &args.bundle_out {
+        names.bundle_state = bundle_out.clone();
+    }
+    names
+}
+
+fn gas_limits(args: &Args) -> utils::GasLimits {
+    let default = args
+        .enforce_gas_limits
+        .then_some(utils::PRODUCTION_GAS_LIMIT);
+    utils::GasLimits {
+        call: args.call_gas_limit.or(default),
+        enforce: args.enforce_gas_limits,
+    }
+}
+
+fn read_genesis_config(
+    config_files: &[String],
+    validators_csv: Option<&str>,
+    raw_consensus_keys: bool,
+    context: &RunContext,
+) -> Result<GenesisConfig> {
+    let mut fragments = Vec::with_capacity(config_files.len());
+    for config_file in config_files {
+        info!("Reading Genesis configuration from: {}", config_file);
+        let config_content = utils::read_input_file(config_file, context)?;
+        let mut fragment: GenesisConfig =
+            config_format::parse_config_file(config_file, &config_content)
+                .map_err(anyhow::Error::msg)?;
+        if raw_consensus_keys {
+            fragment.raw_consensus_keys = true;
+        }
+        fragments.push((config_file.clone(), fragment));
+    }
+    let mut config = genesis::merge_genesis_configs(fragments).map_err(anyhow::Error::msg)?;
+    if let Some(validators_csv) = validators_csv {
+        info!("Reading validators from: {}", validators_csv);
+        let content = utils::read_input_file(validators_csv, context)?;
+        let validators = validators_csv::parse_validators_csv(&content)
+            .map_err(|e| anyhow::anyhow!("{}: {}", validators_csv, e))?;
+        if !config.validator_addresses.is_empty() {
+            info!(
+                "Replacing the {} validators of the config with the {} from {}",
+                config.validator_addresses.len(),
+                validators.validator_addresses.len(),
+                validators_csv
+            );
+        }
+        validators_csv::replace_validators(&mut config, validators);
+    }
+    if let Err(errors) = config_validation::validate_config(&config) {
+        for e in &errors {
+            error!("Invalid config: {}", e);
+        }
+        anyhow::bail!(config_validation::format_config_errors(&errors));
+    }
     info!("Genesis configuration loaded successfully");
     info!("Genesis configuration: {:?}", config);
+    Ok(config)
+}
 
-    if let Some(output_dir) = &args.output {
-        if !fs::metadata(&output_dir).is_ok() {
-            fs::create_dir_all(&output_dir).unwrap();
+/// End-of-run totals printed by `--quiet` in place of the per-transaction logs
+struct RunSummary {
+    contracts_deployed: usize,
+    transactions: usize,
+    total_gas_used: u64,
+    /// Name, failed checks and total checks of each verification
+    verifications: Vec<(&'static str, usize, usize)>,
+}
+
+impl RunSummary {
+    fn record(&mut self, name: &'static str, report: &VerificationReport) {
+        self.verifications
+            .push((name, report.failures().count(), report.checks.len()));
+    }
+}
+
+impl std::fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Contracts deployed:    {}", self.contracts_deployed)?;
+        writeln!(f, "Transactions executed: {}", self.transactions)?;
+        write!(f, "Total gas used:        {}", self.total_gas_used)?;
+        for (name, failed, total) in &self.verifications {
+            if *failed == 0 {
+                write!(f, "\nVerification of {}: passed ({} checks)", name, total)?;
+            } else {
+                write!(
+                    f,
+                    "\nVerification of {}: FAILED ({} of {} checks)",
+                    name, failed, total
+                )?;
+            }
         }
-        info!("Output directory: {}", output_dir);
+        Ok(())
     }
+}
 
-    let (db, bundle_state) = execute::genesis_generate(
-        &args.byte_code_dir,
-        &args.output.as_ref().unwrap(),
-        &config,
-        args.jwks_file.clone(),
-        args.oidc_providers_file.clone(),
+/// Log the outcome of post-genesis verification and return whether it passed
+fn log_verification_report(report: &VerificationReport) -> bool {
+    if report.passed() {
+        info!(
+            "Post-genesis verification passed ({} checks)",
+            report.checks.len()
+        );
+        return true;
+    }
+    error!(
+        "Post-genesis verification failed {} of {} checks:",
+        report.failures().count(),
+        report.checks.len()
     );
+    for failure in report.failures() {
+        error!(
+            "  - {}: expected {}, actual {}",
+            failure.field, failure.expected, failure.actual
+        );
+    }
+    false
+}
+
+/// Write the verification report to `--verification-report`, if given
+fn write_verification_report(args: &Args, report: &VerificationReport) -> Result<()> {
+    if let Some(path) = &args.verification_report {
+        report
+            .write_json(Path::new(path))
+            .map_err(anyhow::Error::msg)?;
+        info!("Verification report written to {}", path);
+    }
+    Ok(())
+}
+
+/// Linker for the registered contracts, resolving fully qualified placeholders from `--link-sources`
+fn library_linker(args: &Args, registry: &ContractRegistry) -> LibraryLinker {
+    LibraryLinker::from_contracts(
+        registry.contracts(),
+        args.link_sources.as_deref().map(std::path::Path::new),
+    )
+}
 
-    post_genesis::verify_result(
-        db,
+/// Deployed bytecode hashes from `--artifacts-dir`, if given
+fn read_artifact_code_hashes(
+    args: &Args,
+    registry: &ContractRegistry,
+) -> Result<Option<BTreeMap<String, B256>>> {
+    args.artifacts_dir
+        .as_deref()
+        .map(|dir| {
+            codehash::load_artifact_code_hashes(dir, &library_linker(args, registry), registry)
+        })
+        .transpose()
+        .map_err(anyhow::Error::msg)
+}
+
+fn read_layout_hints(
+    path: Option<&str>,
+    registry: &ContractRegistry,
+) -> Result<Option<layout::LayoutHints>> {
+    path.map(|path| layout::read_layout_hints(path, registry))
+        .transpose()
+        .map_err(anyhow::Error::msg)
+}
+
+/// Describe one account of a bundle state for the inspect subcommand
+fn run_inspect(args: &Args, registry: &ContractRegistry) -> Result<String> {
+    let Some(Command::Inspect {
         bundle_state,
+        address,
+        layout_hints,
+        storage_layout,
+    }) = &args.command
+    else {
+        unreachable!("run_inspect is only called for the inspect subcommand");
+    };
+
+    let address = inspect::resolve_address(address, registry).map_err(anyhow::Error::msg)?;
+    let mut labels = match storage_layout {
+        Some(path) => inspect::read_storage_layout(path).map_err(anyhow::Error::msg)?,
+        None => inspect::SlotLabels::new(),
+    };
+    if let Some(layout_hints) = read_layout_hints(layout_hints.as_deref(), registry)? {
+        let hints = registry
+            .label_for(address)
+            .and_then(|name| layout_hints.get(name));
+        for (slot, mut hint_labels) in
+            inspect::labels_from_hints(hints.map(Vec::as_slice).unwrap_or_default())
+        {
+            labels.entry(slot).or_default().append(&mut hint_labels);
+        }
+    }
+    let bundle_state = inspect::read_bundle_state(bundle_state).map_err(anyhow::Error::msg)?;
+    inspect::inspect_account(&bundle_state, address, &labels, registry).map_err(anyhow::Error::msg)
+}
+
+/// Describe the differences between two genesis_accounts.json files for the diff subcommand
+fn run_diff(args: &Args, registry: &ContractRegistry) -> Result<String> {
+    let Some(Command::Diff {
+        before,
+        after,
+        json,
+    }) = &args.command
+    else {
+        unreachable!("run_diff is only called for the diff subcommand");
+    };
+
+    let before = state_diff::read_genesis_accounts(before).map_err(anyhow::Error::msg)?;
+    let after = state_diff::read_genesis_accounts(after).map_err(anyhow::Error::msg)?;
+    let diff = state_diff::diff_genesis_accounts(&before, &after, registry);
+    if *json {
+        return Ok(serde_json::to_string_pretty(&diff)?);
+    }
+    Ok(diff.to_string())
+}
+
+/// Run the post-genesis checks against an existing output directory
+fn run_verify(args: &Args, context: &RunContext) -> Result<ExitCode> {
+    let report = verify_output_dir(args, context)?;
+    write_verification_report(args, &report)?;
+    if !log_verification_report(&report) {
+        return Ok(ExitCode::from(VERIFICATION_FAILED_EXIT_CODE));
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Every check of the `verify` subcommand against its output directory
+fn verify_output_dir(args: &Args, context: &RunContext) -> Result<VerificationReport> {
+    let Some(Command::Verify {
+        output_dir,
+        config_file,
+        jwks_file,
+        oidc_providers_file,
+        jwks_dir,
+        oidc_dir,
+        layout_hints,
+        verify_mode,
+    }) = &args.command
+    else {
+        unreachable!("verify_output_dir is only called for the verify subcommand");
+    };
+    let jwks_file = file_or_dir_input(jwks_file, jwks_dir, "--jwks-dir")?;
+    let oidc_providers_file = file_or_dir_input(oidc_providers_file, oidc_dir, "--oidc-dir")?;
+
+    let config = read_genesis_config(
+        config_file,
+        args.validators_csv.as_deref(),
+        args.raw_consensus_keys,
+        context,
+    )?;
+    let layout_hints = read_layout_hints(layout_hints.as_deref(), &context.registry)?;
+    let artifact_code_hashes = read_artifact_code_hashes(args, &context.registry)?;
+    let output = artifacts::load_genesis_output(output_dir, &output_names(args))
+        .map_err(anyhow::Error::msg)?;
+    info!("Verifying genesis in {} at {:?}", output_dir, output.block);
+    context.progress.start_phase("verifying", None);
+
+    let mut report = post_genesis::verify_result(
+        output.db,
+        output.bundle_state.clone(),
         &config,
-        args.jwks_file.clone(),
-        args.oidc_providers_file.clone(),
+        jwks_file,
+        oidc_providers_file,
+        &output.block,
+        &VerifyOptions {
+            mode: *verify_mode,
+            system_caller: output.system_caller,
+            expected_code_hashes: artifact_code_hashes,
+            epoch_interval_micros: args.epoch_interval_micros,
+        },
+        context,
     );
+    if let Some(layout_hints) = &layout_hints {
+        report.merge(layout::verify_address_slots(
+            &output.bundle_state,
+            layout_hints,
+            *verify_mode,
+            &context.registry,
+        ));
+    }
+    if let Some(expected_total_supply) = args.expected_total_supply {
+        let path = std::path::Path::new(output_dir).join(&output_names(args).genesis_accounts);
+        let accounts = state_diff::read_genesis_accounts(&path.to_string_lossy())
+            .map_err(anyhow::Error::msg)?;
+        report.merge(supply_report::verify_total_supply(
+            &SupplyReport::new(&accounts, args.supply_report_top, &context.registry),
+            expected_total_supply,
+            *verify_mode,
+        ));
+    }
+    Ok(report)
+}
 
-    info!("Gravity Genesis Binary completed successfully");
+/// List the warnings emitted during the run and fail it under `--deny-warnings`
+fn check_diagnostics(
+    args: &Args,
+    result: Result<ExitCode>,
+    context: &RunContext,
+) -> Result<ExitCode> {
+    let emitted = context.diagnostics.emitted();
+    if emitted.is_empty() {
+        return result;
+    }
+    warn!("{} warning(s) emitted during the run:", emitted.len());
+    for diagnostic in &emitted {
+        warn!("  {}", diagnostic);
+    }
+    if args.deny_warnings && result.is_ok() {
+        anyhow::bail!(
+            "{} warning(s) emitted and --deny-warnings is set",
+            emitted.len()
+        );
+    }
+    result
+}
+
+/// Standard input can only feed one of the config, JWKs and OIDC provider inputs
+fn check_stdin_inputs(args: &Args) -> Result<()> {
+    let (config_files, jwks_file, oidc_providers_file) = match &args.command {
+        Some(Command::Verify {
+            config_file,
+            jwks_file,
+            oidc_providers_file,
+            ..
+        }) => (config_file, jwks_file, oidc_providers_file),
+        _ => (
+            &args.config_file,
+            &args.jwks_file,
+            &args.oidc_providers_file,
+        ),
+    };
+    let stdin_inputs = config_files
+        .iter()
+        .chain(jwks_file)
+        .chain(oidc_providers_file)
+        .chain(&args.validators_csv)
+        .filter(|path| path.as_str() == utils::STDIN_PATH)
+        .count();
+    if stdin_inputs > 1 {
+        anyhow::bail!(
+            "'{}' (standard input) was given for {} inputs; at most one input can be read from standard input",
+            utils::STDIN_PATH,
+            stdin_inputs
+        );
+    }
+    Ok(())
+}
+
+/// The directory given for an input if any, failing unless it is a directory, else its file
+fn file_or_dir_input(
+    file: &Option<String>,
+    dir: &Option<String>,
+    dir_flag: &str,
+) -> Result<Option<String>> {
+    match dir {
+        Some(dir) if !std::path::Path::new(dir).is_dir() => {
+            anyhow::bail!("{} {} is not a directory", dir_flag, dir)
+        }
+        Some(dir) => Ok(Some(dir.clone())),
+        None => Ok(file.clone()),
+    }
+}
+
+/// `path`, or `None` with a warning if the input is optional and the file does not exist
+///
+/// A file that exists but cannot be read or parsed still fails the run later on.
+fn optional_input(
+    path: &Option<String>,
+    optional: bool,
+    input: &str,
+    context: &RunContext,
+) -> Option<String> {
+    match path {
+        Some(path)
+            if optional
+                && path.as_str() != utils::STDIN_PATH
+                && !std::path::Path::new(path).exists() =>
+        {
+            context.diagnostics.emit(
+                DiagnosticCode::MissingOptionalInput,
+                format!(
+                    "{} file {} does not exist, skipping its transactions",
+                    input, path
+                ),
+            );
+            None
+        }
+        _ => path.clone(),
+    }
+}
+
+/// Refuse to write into an output directory that already holds files, unless `force` is set
+///
+/// The log file is ignored, since it is created before the genesis is generated.
+fn check_output_dir(output_dir: &str, force: bool, log_file: Option<&str>) -> Result<()> {
+    let Ok(entries) = fs::read_dir(output_dir) else {
+        return Ok(());
+    };
+    let log_file = log_file.and_then(|path| fs::canonicalize(path).ok());
+    let occupied = entries.filter_map(Result::ok).any(|entry| match &log_file {
+        Some(log_file) => fs::canonicalize(entry.path()).ok().as_ref() != Some(log_file),
+        None => true,
+    });
+    if occupied && !force {
+        anyhow::bail!(
+            "output directory {} is not empty; pass --force to overwrite the genesis in it",
+            output_dir
+        );
+    }
     Ok(())
 }
+
+async fn run_main_logic(args: &Args, context: &Arc<RunContext>) -> Result<ExitCode> {
+    check_stdin_inputs(args)?;
+    if let Some(Command::Verify { .. }) = &args.command {
+        return run_verify(args, context);
+    }
+    if args.assert_idempotent && !args.only.is_empty() && !args.only.iter().any(|c| c == "Genesis")
+    {
+        anyhow::bail!("--assert-idempotent needs Genesis among the --only contracts");
+    }
+    if args.assert_idempotent && args.skip_initialize && args.base_state.is_none() {
+        anyhow::bail!("--assert-idempotent needs Genesis.initialize, sent or in the --base-state");
+    }
+
+    let config = read_genesis_config(
+        &args.config_file,
+        args.validators_csv.as_deref(),
+        args.raw_consensus_keys,
+        context,
+    )?;
+
+    if !args.no_write {
+        let output_dir = &args.output;
+        check_output_dir(output_dir, args.force, args.log_file.as_deref())?;
+        fs::create_dir_all(output_dir).map_err(|e| {
+            anyhow::anyhow!("Failed to create output directory {}: {}", output_dir, e)
+        })?;
+        info!("Output directory: {}", output_dir);
+
+        if args.deploy_mode == DeployMode::Create2 {
+            for (name, address) in context.registry.contracts() {
+                info!("{} CREATE2 address: {}", name, address);
+            }
+            create2::write_create2_addresses(context.registry.contracts(), output_dir)?;
+        }
+    }
+
+    let mut block = BlockContext {
+        number: args.block_number,
+        basefee: args.block_basefee,
+        coinbase: args.block_coinbase,
+        gas_limit: args.block_gas_limit,
+        ..BlockContext::default()
+    };
+    if let Some(timestamp) = args.genesis_timestamp {
+        block.timestamp = timestamp;
+    }
+
+    let contract_balances = match &args.balances_file {
+        Some(balances_file) => {
+            balances::read_contract_balances(balances_file, context).map_err(anyhow::Error::msg)?
+        }
+        None => balances::default_contract_balances(),
+    };
+
+    let codehash_db = args
+        .codehash_db
+        .as_deref()
+        .map(codehash::read_codehash_db)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+    let bytecode_manifest = args
+        .bytecode_manifest
+        .as_deref()
+        .map(|path| codehash::read_bytecode_manifest(path, &context.registry))
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+
+    let sign_key = args
+        .sign_key
+        .as_deref()
+        .map(attestation::read_signing_key)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+
+    let layout_hints = read_layout_hints(args.layout_hints.as_deref(), &context.registry)?;
+    let jwks_file = optional_input(
+        &file_or_dir_input(&args.jwks_file, &args.jwks_dir, "--jwks-dir")?,
+        args.jwks_optional,
+        "JWKs",
+        context,
+    );
+    let oidc_providers_file = optional_input(
+        &file_or_dir_input(&args.oidc_providers_file, &args.oidc_dir, "--oidc-dir")?,
+        args.oidc_optional,
+        "OIDC providers",
+        context,
+    );
+    let artifact_code_hashes = read_artifact_code_hashes(args, &context.registry)?;
+
+    let mut builder = GenesisBuilder::new(
+        args.byte_code_dir
+            .as_deref()
+            .expect("--byte-code-dir is required"),
+    )
+    .with_write_files(!args.no_write)
+    .with_config(config.clone())
+    .with_block(block.clone())
+    .with_continue_on_error(args.continue_on_error)
+    .with_gas_limits(gas_limits(args))
+    .with_tx_time_budget(std::time::Duration::from_secs(args.tx_timeout_secs))
+    .with_output_names(output_names(args))
+    .with_supply_report_top(args.supply_report_top)
+    .with_keep_system_caller(args.keep_system_caller)
+    .with_context(Arc::clone(context))
+    .with_deploy_options(DeployOptions {
+        balances: contract_balances,
+        codehash_db,
+        bytecode_manifest,
+        max_code_size: args.max_code_size,
+        allow_missing: args.allow_missing,
+        only: (!args.only.is_empty()).then(|| args.only.iter().cloned().collect()),
+        linker: library_linker(args, &context.registry),
+        system_caller: args.system_caller,
+        system_caller_balance: args.system_caller_balance,
+        contract_nonce: args.contract_nonce,
+        initialize: !args.skip_initialize,
+    });
+    if !args.no_write {
+        builder = builder.with_output_dir(&args.output);
+    }
+    if let Some(jwks_file) = &jwks_file {
+        builder = builder.with_jwks_file(jwks_file);
+    }
+    if let Some(oidc_providers_file) = &oidc_providers_file {
+        builder = builder.with_oidc_file(oidc_providers_file);
+    }
+    if let Some(layout_dir) = &args.layout_dir {
+        builder = builder.with_storage_layouts(
+            inspect::read_storage_layouts(layout_dir, &context.registry)
+                .map_err(anyhow::Error::msg)?,
+        );
+    }
+    if let Some(extra_txns) = &args.extra_txns {
+        builder = builder.with_extra_txns_file(extra_txns);
+    }
+    if let Some(dump_logs) = &args.dump_logs {
+        builder = builder.with_log_dump(dump_logs);
+    }
+    if let Some(export) = args.export {
+        builder = builder
+            .with_export(export)
+            .with_keep_empty_accounts(args.keep_empty);
+    }
+    if let Some(base_state) = &args.base_state {
+        info!("Reading base state from: {}", base_state);
+        builder = builder
+            .with_base_state(inspect::read_bundle_state(base_state).map_err(anyhow::Error::msg)?);
+    }
+    if let Some(combined_output) = &args.combined_output {
+        builder = builder
+            .with_combined_output(combined_output)
+            .with_split_files(!args.no_split);
+    }
+    let genesis = builder.build()?;
+    let mut summary = RunSummary {
+        contracts_deployed: genesis.contracts_deployed,
+        transactions: genesis.gas_report.transactions.len(),
+        total_gas_used: genesis.gas_report.total_gas_used,
+        verifications: Vec::new(),
+    };
+
+    context.progress.start_phase("verifying", None);
+    let idempotency_report = args.assert_idempotent.then(|| {
+        post_genesis::verify_initialize_idempotent(
+            &genesis.db,
+            genesis.bundle_state.clone(),
+            &config,
+            &block,
+            &VerifyOptions {
+                mode: args.verify_mode,
+                system_caller: args.system_caller,
+                ..VerifyOptions::default()
+            },
+            context,
+        )
+    });
+    // Without Genesis.initialize there is no validator set or epoch to check
+    // yet, until a later run layers it onto this genesis with --base-state
+    let mut report = if args.skip_initialize && args.base_state.is_none() {
+        info!("Genesis.initialize was skipped, not verifying the post-genesis state");
+        VerificationReport::new(args.verify_mode)
+    } else {
+        post_genesis::verify_result(
+            genesis.db,
+            genesis.bundle_state.clone(),
+            &config,
+            jwks_file,
+            oidc_providers_file,
+            &block,
+            &VerifyOptions {
+                mode: args.verify_mode,
+                system_caller: args.system_caller,
+                expected_code_hashes: artifact_code_hashes,
+                epoch_interval_micros: args.epoch_interval_micros,
+            },
+            context,
+        )
+    };
+    summary.record("post-genesis state", &report);
+    if let Some(layout_hints) = &layout_hints {
+        let layout_report = layout::verify_address_slots(
+            &genesis.bundle_state,
+            layout_hints,
+            args.verify_mode,
+            &context.registry,
+        );
+        summary.record("address layout", &layout_report);
+        report.merge(layout_report);
+    }
+    if let Some(expected_total_supply) = args.expected_total_supply {
+        let supply_report = supply_report::verify_total_supply(
+            &genesis.supply_report,
+            expected_total_supply,
+            args.verify_mode,
+        );
+        summary.record("total supply", &supply_report);
+        report.merge(supply_report);
+    }
+    if let Some(idempotency_report) = idempotency_report {
+        summary.record("re-initialization", &idempotency_report);
+        report.merge(idempotency_report);
+    }
+    write_verification_report(args, &report)?;
+    let passed = log_verification_report(&report);
+    if args.quiet {
+        println!("{}", summary);
+    }
+    if !passed {
+        return Ok(ExitCode::from(VERIFICATION_FAILED_EXIT_CODE));
+    }
+
+    if let Some(golden) = &args.golden {
+        if args.update_golden {
+            state_diff::write_golden(golden, &genesis.genesis_state).map_err(anyhow::Error::msg)?;
+            info!("Golden genesis updated: {}", golden);
+        } else if let Some(mismatch) =
+            state_diff::check_golden(golden, &genesis.genesis_state, &context.registry)
+                .map_err(anyhow::Error::msg)?
+        {
+            error!("Genesis differs from the golden {}: {}", golden, mismatch);
+            return Ok(ExitCode::from(GOLDEN_MISMATCH_EXIT_CODE));
+        } else {
+            info!("Genesis matches the golden {}", golden);
+        }
+    }
+
+    // Only a genesis that passed verification is attested to
+    if let Some(sign_key) = &sign_key {
+        let output_dir = std::path::Path::new(&args.output);
+        let names = output_names(args);
+        let attestation = attestation::attest_manifest(&output_dir.join(&names.manifest), sign_key)
+            .map_err(anyhow::Error::msg)?;
+        attestation.write_to(&output_dir.join(&names.attestation))?;
+        info!(
+            "Genesis attested by {} (digest {})",
+            attestation.signer, attestation.digest
+        );
+    }
+
+    info!("Gravity Genesis Binary completed successfully");
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gravity_genesis::{config_template::template_config, utils::VALIDATOR_MANAGER_ADDR};
+
+    /// Contract bytecode compiled by `forge build` and `extract_bytecode.py`
+    const BYTECODE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../out");
+
+    /// Generate a genesis into `dir/output` and return the `verify` arguments checking it
+    fn generate_and_verify_args(dir: &Path) -> Args {
+        let config_file = dir.join("genesis_config.json");
+        fs::write(
+            &config_file,
+            serde_json::to_string(&template_config()).unwrap(),
+        )
+        .unwrap();
+        let output_dir = dir.join("output");
+        GenesisBuilder::new(BYTECODE_DIR)
+            .with_config(template_config())
+            .with_output_dir(&output_dir)
+            .build()
+            .unwrap();
+        Args::try_parse_from([
+            "gravity-genesis",
+            "verify",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--config-file",
+            config_file.to_str().unwrap(),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    #[ignore = "needs ../out from forge build and extract_bytecode.py"]
+    fn test_verify_passes_on_a_generated_output_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = generate_and_verify_args(dir.path());
+
+        let report = verify_output_dir(&args, &RunContext::default()).unwrap();
+        assert!(
+            report.passed(),
+            "verification failures: {:?}",
+            report.failures().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[ignore = "needs ../out from forge build and extract_bytecode.py"]
+    fn test_verify_detects_a_tampered_bundle_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = generate_and_verify_args(dir.path());
+
+        // Wipe the validator set ValidatorManager stored during Genesis.initialize
+        let path = dir.path().join("output").join("bundle_state.json");
+        let mut bundle_state: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let storage =
+            bundle_state["state"][VALIDATOR_MANAGER_ADDR.to_string().to_lowercase()]["storage"]
+                .as_object_mut()
+                .unwrap();
+        assert!(!storage.is_empty());
+        for slot in storage.values_mut() {
+            slot["present_value"] = serde_json::json!("0x0");
+        }
+        fs::write(&path, serde_json::to_string(&bundle_state).unwrap()).unwrap();
+
+        // Reading the wiped state may fail outright instead of failing a check
+        assert!(
+            !matches!(verify_output_dir(&args, &RunContext::default()), Ok(report) if report.passed())
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::{fs::File, io::BufWriter};
+
+use revm::primitives::{Address, B256, Env, SpecId};
+use revm_primitives::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::{execute::BlockContext, genesis::GenesisConfig, utils::SYSTEM_CALLER};
+
+/// Hash identifying the inputs a genesis was generated from
+///
+/// `keccak256(keccak256(config) ++ keccak256(jwks) ++ keccak256(oidc_providers))`,
+/// where the config is hashed in its canonical JSON encoding (fields in
+/// declaration order, so equal configs hash the same whatever file format
+/// they came from) and an absent JWKs or OIDC providers file contributes zero.
+pub fn config_hash(
+    config: &GenesisConfig,
+    jwks: Option<&[u8]>,
+    oidc_providers: Option<&[u8]>,
+) -> B256 {
+    let config_json = serde_json::to_vec(config).expect("GenesisConfig serializes to JSON");
+    let file_hash = |content: Option<&[u8]>| content.map(keccak256).unwrap_or_default();
+    keccak256(
+        [
+            keccak256(config_json).as_slice(),
+            file_hash(jwks).as_slice(),
+            file_hash(oidc_providers).as_slice(),
+        ]
+        .concat(),
+    )
+}
+
+/// Describes how a genesis was produced, written next to the state files as `manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisManifest {
+    pub chain_id: u64,
+    pub block_number: u64,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    pub coinbase: Address,
+    pub basefee: u64,
+    /// Block gas limit, absent when the genesis was executed without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_gas_limit: Option<u64>,
+    pub spec_id: String,
+    /// Ethereum state root of `genesis_accounts.json`
+    pub state_root: B256,
+    /// [`config_hash`] of the config, JWKs and OIDC providers the genesis was generated from
+    #[serde(default)]
+    pub config_hash: B256,
+    /// Version of the tool that generated the genesis
+    #[serde(default)]
+    pub tool_version: String,
+    /// Unix timestamp in seconds (UTC) at which the genesis was generated
+    #[serde(default)]
+    pub generated_at: u64,
+    /// Sender of the genesis transactions, needed to replay the read-only verification calls
+    #[serde(default = "default_system_caller")]
+    pub system_caller: Address,
+}
+
+fn default_system_caller() -> Address {
+    SYSTEM_CALLER
+}
+
+impl GenesisManifest {
+    /// Record the block context of the `Env` the genesis transactions were executed in
+    ///
+    /// The state root and config hash are left zero until the final genesis
+    /// state and the inputs are known.
+    pub fn from_env(env: &Env, spec_id: SpecId) -> Self {
+        let generated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self {
+            chain_id: env.cfg.chain_id,
+            block_number: env.block.number.saturating_to(),
+            timestamp: env.block.timestamp.saturating_to(),
+            coinbase: env.block.coinbase,
+            basefee: env.block.basefee.saturating_to(),
+            block_gas_limit: u64::try_from(env.block.gas_limit).ok(),
+            spec_id: format!("{:?}", spec_id),
+            state_root: B256::ZERO,
+            config_hash: B256::ZERO,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at,
+            system_caller: SYSTEM_CALLER,
+        }
+    }
+
+    /// Block context the genesis transactions were executed in
+    pub fn block_context(&self) -> BlockContext {
+        BlockContext {
+            chain_id: self.chain_id,
+            number: self.block_number,
+            timestamp: self.timestamp,
+            basefee: self.basefee,
+            coinbase: self.coinbase,
+            gas_limit: self.block_gas_limit,
+        }
+    }
+
+    pub fn write(&self, output_dir: &str) -> std::io::Result<()> {
+        let file = File::create(format!("{output_dir}/manifest.json"))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execute::prepare_env;
+
+    #[test]
+    fn test_manifest_records_env_block_context() {
+        let block = BlockContext {
+            chain_id: 1,
+            number: 7,
+            timestamp: 1_735_689_600,
+            basefee: 0,
+            coinbase: Address::repeat_byte(0xcb),
+            gas_limit: Some(36_000_000),
+        };
+        let env = prepare_env(&block);
+        let manifest = GenesisManifest::from_env(&env, SpecId::LATEST);
+
+        assert_eq!(manifest.chain_id, env.cfg.chain_id);
+        assert_eq!(manifest.chain_id, 1);
+        assert_eq!(manifest.timestamp, 1_735_689_600);
+        assert_eq!(manifest.block_number, 7);
+        assert_eq!(manifest.basefee, 0);
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["chain_id"], 1);
+        assert_eq!(json["timestamp"], 1_735_689_600);
+
+        let restored = manifest.block_context();
+        assert_eq!(restored.number, block.number);
+        assert_eq!(restored.timestamp, block.timestamp);
+        assert_eq!(manifest.coinbase, block.coinbase);
+        assert_eq!(restored.coinbase, block.coinbase);
+        assert_eq!(restored.gas_limit, Some(36_000_000));
+
+        let unlimited =
+            GenesisManifest::from_env(&prepare_env(&BlockContext::default()), SpecId::LATEST);
+        assert_eq!(unlimited.block_gas_limit, None);
+        assert!(
+            serde_json::to_value(&unlimited)
+                .unwrap()
+                .get("block_gas_limit")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_config_hash_covers_every_input() {
+        let config: GenesisConfig = serde_json::from_value(serde_json::json!({
+            "validatorAddresses": ["0x1111111111111111111111111111111111111111"],
+            "consensusPublicKeys": ["aa".repeat(48)],
+            "votingPowers": ["1"],
+            "validatorNetworkAddresses": [""],
+            "fullnodeNetworkAddresses": [""],
+            "aptosAddresses": [
+                "0000000000000000000000001111111111111111111111111111111111111111"
+            ]
+        }))
+        .unwrap();
+        let hash = config_hash(&config, None, None);
+        assert_eq!(hash, config_hash(&config.clone(), None, None));
+        assert_ne!(hash, B256::ZERO);
+
+        let mut changed = config.clone();
+        changed.voting_powers[0] = "2".to_string();
+        assert_ne!(hash, config_hash(&changed, None, None));
+        let with_jwks = config_hash(&config, Some(b"{}"), None);
+        assert_ne!(hash, with_jwks);
+        assert_ne!(with_jwks, config_hash(&config, None, Some(b"{}")));
+    }
+
+    #[test]
+    fn test_manifest_without_provenance_still_loads() {
+        let manifest: GenesisManifest = serde_json::from_value(serde_json::json!({
+            "chain_id": 1,
+            "block_number": 0,
+            "timestamp": 0,
+            "coinbase": Address::ZERO,
+            "basefee": 0,
+            "spec_id": "LATEST",
+            "state_root": B256::ZERO,
+        }))
+        .unwrap();
+        assert_eq!(manifest.config_hash, B256::ZERO);
+        assert!(manifest.tool_version.is_empty());
+        assert_eq!(manifest.system_caller, SYSTEM_CALLER);
+
+        let manifest =
+            GenesisManifest::from_env(&prepare_env(&BlockContext::default()), SpecId::LATEST);
+        assert_eq!(manifest.tool_version, env!("CARGO_PKG_VERSION"));
+        assert!(manifest.generated_at > 0);
+    }
+}
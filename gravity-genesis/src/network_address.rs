@@ -0,0 +1,177 @@
+use std::{
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use revm_primitives::hex;
+
+/// One `/name/value` component of an Aptos `NetworkAddress`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Protocol {
+    Ip4(Ipv4Addr),
+    Ip6(Ipv6Addr),
+    Dns(String),
+    Dns4(String),
+    Dns6(String),
+    Tcp(u16),
+    Memory(u16),
+    /// x25519 public key of the noise IK handshake
+    NoiseIk([u8; 32]),
+    Handshake(u8),
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip4(addr) => write!(f, "/ip4/{}", addr),
+            Self::Ip6(addr) => write!(f, "/ip6/{}", addr),
+            Self::Dns(name) => write!(f, "/dns/{}", name),
+            Self::Dns4(name) => write!(f, "/dns4/{}", name),
+            Self::Dns6(name) => write!(f, "/dns6/{}", name),
+            Self::Tcp(port) => write!(f, "/tcp/{}", port),
+            Self::Memory(port) => write!(f, "/memory/{}", port),
+            Self::NoiseIk(key) => write!(f, "/noise-ik/0x{}", hex::encode(key)),
+            Self::Handshake(version) => write!(f, "/handshake/{}", version),
+        }
+    }
+}
+
+/// Longest DNS name accepted, as in Aptos `DnsName`
+const MAX_DNS_NAME_LENGTH: usize = 255;
+
+fn parse_dns_name(name: &str) -> Result<String, String> {
+    if name.is_empty() || name.len() > MAX_DNS_NAME_LENGTH {
+        return Err(format!(
+            "DNS name must be 1 to {} characters",
+            MAX_DNS_NAME_LENGTH
+        ));
+    }
+    if !name.is_ascii() {
+        return Err(format!("DNS name {:?} is not ASCII", name));
+    }
+    Ok(name.to_string())
+}
+
+fn parse_protocol(name: &str, value: Option<&str>) -> Result<Protocol, String> {
+    let value = value.ok_or_else(|| format!("/{} is missing its value", name))?;
+    let invalid = |e: &dyn fmt::Display| format!("invalid /{} value {:?}: {}", name, value, e);
+    Ok(match name {
+        "ip4" => Protocol::Ip4(value.parse().map_err(|e| invalid(&e))?),
+        "ip6" => Protocol::Ip6(value.parse().map_err(|e| invalid(&e))?),
+        "dns" => Protocol::Dns(parse_dns_name(value)?),
+        "dns4" => Protocol::Dns4(parse_dns_name(value)?),
+        "dns6" => Protocol::Dns6(parse_dns_name(value)?),
+        "tcp" => Protocol::Tcp(value.parse().map_err(|e| invalid(&e))?),
+        "memory" => Protocol::Memory(value.parse().map_err(|e| invalid(&e))?),
+        "noise-ik" => {
+            let key = hex::decode(value).map_err(|e| invalid(&e))?;
+            Protocol::NoiseIk(key.try_into().map_err(|key: Vec<u8>| {
+                invalid(&format!("public key is {} bytes, expected 32", key.len()))
+            })?)
+        }
+        "handshake" => Protocol::Handshake(value.parse().map_err(|e| invalid(&e))?),
+        _ => return Err(format!("unknown protocol /{}", name)),
+    })
+}
+
+/// Parse and check an Aptos network address such as
+/// `/ip4/127.0.0.1/tcp/6180/noise-ik/<x25519 key>/handshake/0`
+///
+/// Mirrors the checks of Aptos `NetworkAddress::from_str` followed by
+/// `is_aptosnet_addr`: an IP or DNS host with a TCP port (or an in-memory
+/// port), then the noise IK public key, then the handshake version.
+pub fn parse_network_address(address: &str) -> Result<Vec<Protocol>, String> {
+    let Some(rest) = address.strip_prefix('/') else {
+        return Err("must start with '/'".to_string());
+    };
+    let mut parts = rest.split('/');
+    let mut protocols = Vec::new();
+    while let Some(name) = parts.next() {
+        protocols.push(parse_protocol(name, parts.next())?);
+    }
+
+    let transport_len = match protocols.as_slice() {
+        [
+            Protocol::Ip4(_)
+            | Protocol::Ip6(_)
+            | Protocol::Dns(_)
+            | Protocol::Dns4(_)
+            | Protocol::Dns6(_),
+            Protocol::Tcp(_),
+            ..,
+        ] => 2,
+        [Protocol::Memory(_), ..] => 1,
+        _ => {
+            return Err(
+                "must start with an /ip4, /ip6, /dns, /dns4 or /dns6 host and a /tcp port, or a /memory port"
+                    .to_string(),
+            );
+        }
+    };
+    match &protocols[transport_len..] {
+        [Protocol::NoiseIk(_), Protocol::Handshake(_)] => Ok(protocols),
+        [Protocol::NoiseIk(_)] => Err("is missing the /handshake version".to_string()),
+        [] => Err("is missing the /noise-ik public key and /handshake version".to_string()),
+        [extra, ..] => Err(format!(
+            "expected /noise-ik/<key>/handshake/<version> after the transport, found {}",
+            extra
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f";
+
+    #[test]
+    fn test_valid_network_addresses() {
+        let protocols = parse_network_address(&format!(
+            "/ip4/127.0.0.1/tcp/2024/noise-ik/{KEY}/handshake/0"
+        ))
+        .unwrap();
+        assert_eq!(protocols[0], Protocol::Ip4(Ipv4Addr::LOCALHOST));
+        assert_eq!(protocols[1], Protocol::Tcp(2024));
+        assert_eq!(protocols[3], Protocol::Handshake(0));
+
+        for address in [
+            format!("/dns/validator.example.com/tcp/6180/noise-ik/0x{KEY}/handshake/0"),
+            format!("/ip6/::1/tcp/6180/noise-ik/{KEY}/handshake/1"),
+            format!("/memory/1234/noise-ik/{KEY}/handshake/0"),
+        ] {
+            assert!(
+                parse_network_address(&address).is_ok(),
+                "rejected {}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_network_addresses_report_reason() {
+        let cases = [
+            ("ip4/127.0.0.1/tcp/2024", "must start with '/'"),
+            (
+                "/ip4/127.0.0.256/tcp/2024",
+                "invalid /ip4 value \"127.0.0.256\"",
+            ),
+            ("/ip4/127.0.0.1/tcp/70000", "invalid /tcp value \"70000\""),
+            ("/ip4/127.0.0.1/udp/2024", "unknown protocol /udp"),
+            ("/ip4/127.0.0.1/tcp", "/tcp is missing its value"),
+            ("/tcp/2024/ip4/127.0.0.1", "must start with an /ip4"),
+            (
+                "/ip4/127.0.0.1/tcp/2024",
+                "is missing the /noise-ik public key",
+            ),
+            (
+                "/ip4/127.0.0.1/tcp/2024/noise-ik/abcd/handshake/0",
+                "expected 32",
+            ),
+        ];
+        for (address, reason) in cases {
+            let err = parse_network_address(address).unwrap_err();
+            assert!(err.contains(reason), "{}: {}", address, err);
+        }
+    }
+}
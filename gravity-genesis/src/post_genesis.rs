@@ -1,27 +1,215 @@
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+use rayon::prelude::*;
 use revm::{DatabaseRef, InMemoryDB, db::BundleState};
-use revm_primitives::{ExecutionResult, SpecId, TxEnv, hex};
+use revm_primitives::{
+    Address, B256, Bytes, ExecutionResult, SpecId, TxEnv, TxKind, U256, hex, keccak256,
+};
+use serde::Serialize;
 use tracing::{error, info};
 
 use crate::{
-    execute::prepare_env,
+    context::RunContext,
+    diagnostics::DiagnosticCode,
+    execute::{BlockContext, prepare_env},
     genesis::{
-        GenesisConfig, call_get_current_epoch_info, call_get_validator_set,
+        DEFAULT_EPOCH_INTERVAL_MICROS, EpochInfo, GenesisConfig, already_initialized_error,
+        call_genesis_initialize, call_get_current_epoch_info, call_get_validator_set,
         print_current_epoch_info_result, print_validator_set_result,
     },
     jwks::{
         call_get_active_providers, call_get_observed_jwks, print_jwks_result,
-        print_oidc_providers_result,
+        print_oidc_providers_result, read_jwks_from_file, read_oidc_providers_from_file,
+    },
+    randomness::{
+        call_current_randomness_config, call_is_dkg_in_progress,
+        call_is_reconfiguration_in_progress, print_not_in_progress_result,
+        print_randomness_config_result,
     },
-    utils::execute_revm_sequential,
+    utils::{SYSTEM_CALLER, analyze_txn_result, execute_revm_sequential, explain_halt},
 };
 
+/// How verification reacts to a mismatch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum VerifyMode {
+    /// Stop at the first mismatch
+    FailFast,
+    /// Run every check and report all mismatches
+    #[default]
+    CollectAll,
+}
+
+/// Settings shared by every post-genesis verification
+#[derive(Debug, Clone)]
+pub struct VerifyOptions {
+    pub mode: VerifyMode,
+    /// Caller the read-only verification calls are sent from
+    pub system_caller: Address,
+    /// Code hash each contract's deployed bytecode must match, if given
+    pub expected_code_hashes: Option<BTreeMap<String, B256>>,
+    /// Epoch duration `EpochManager` must report, in microseconds
+    pub epoch_interval_micros: u64,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self {
+            mode: VerifyMode::default(),
+            system_caller: SYSTEM_CALLER,
+            expected_code_hashes: None,
+            epoch_interval_micros: DEFAULT_EPOCH_INTERVAL_MICROS,
+        }
+    }
+}
+
+/// Outcome of comparing one field against its expected value
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// What was checked, e.g. `validator 0 voting power`
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+    pub passed: bool,
+}
+
+/// Every field checked during post-genesis verification
+///
+/// In [`VerifyMode::FailFast`] the report stops recording checks once one of
+/// them has failed, so it holds at most one failure.
+#[derive(Debug, Clone, Default)]
+pub struct VerificationReport {
+    pub mode: VerifyMode,
+    pub checks: Vec<CheckResult>,
+}
+
+impl VerificationReport {
+    pub fn new(mode: VerifyMode) -> Self {
+        Self {
+            mode,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Report for a verification whose view call could not be executed
+    pub fn call_failed(mode: VerifyMode, function_name: &str) -> Self {
+        let mut report = Self::new(mode);
+        report.record(
+            format!("{} call", function_name),
+            "success",
+            "reverted or halted",
+            false,
+        );
+        report
+    }
+
+    /// Record the comparison of `expected` and `actual`, returning whether they match
+    pub fn check_eq<T: PartialEq + Display>(
+        &mut self,
+        field: impl Into<String>,
+        expected: T,
+        actual: T,
+    ) -> bool {
+        let passed = expected == actual;
+        self.record(field, expected, actual, passed);
+        passed
+    }
+
+    /// Record a check whose outcome was decided by the caller
+    pub fn record(
+        &mut self,
+        field: impl Into<String>,
+        expected: impl Display,
+        actual: impl Display,
+        passed: bool,
+    ) {
+        if self.should_stop() {
+            return;
+        }
+        let check = CheckResult {
+            field: field.into(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+            passed,
+        };
+        if check.passed {
+            info!("✅ {} matches: {}", check.field, check.actual);
+        } else {
+            error!(
+                "❌ {} mismatch! Expected: {}, Actual: {}",
+                check.field, check.expected, check.actual
+            );
+        }
+        self.checks.push(check);
+    }
+
+    /// Append the checks of another report
+    pub fn merge(&mut self, other: VerificationReport) {
+        for check in other.checks {
+            if self.should_stop() {
+                break;
+            }
+            self.checks.push(check);
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+
+    /// Whether verification should stop given the checks recorded so far
+    pub fn should_stop(&self) -> bool {
+        self.mode == VerifyMode::FailFast && !self.passed()
+    }
+
+    /// Write the report as the JSON document described by [`VerificationReportFile`]
+    pub fn write_json(&self, path: &Path) -> Result<(), String> {
+        let file = VerificationReportFile {
+            schema_version: VERIFICATION_REPORT_SCHEMA_VERSION,
+            status: if self.passed() { "passed" } else { "failed" },
+            mode: self.mode,
+            checks_total: self.checks.len(),
+            checks_failed: self.failures().count(),
+            checks: &self.checks,
+        };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| format!("Failed to serialize the verification report: {}", e))?;
+        std::fs::write(path, json + "\n")
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Version of the `--verification-report` document, bumped when a field changes meaning or is removed
+pub const VERIFICATION_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// `--verification-report` document: every check of a verification and its overall status
+#[derive(Debug, Serialize)]
+pub struct VerificationReportFile<'a> {
+    pub schema_version: u32,
+    /// `passed` when every check passed, `failed` otherwise
+    pub status: &'static str,
+    pub mode: VerifyMode,
+    pub checks_total: usize,
+    pub checks_failed: usize,
+    pub checks: &'a [CheckResult],
+}
+
 /// Generic template for handling execution results
 ///
 /// This function provides a common structure for all print_* functions,
 /// reducing code duplication and making the codebase more maintainable.
-pub fn handle_execution_result<F>(result: &ExecutionResult, function_name: &str, success_handler: F)
+/// Returns `None` when the call reverted or halted.
+pub fn handle_execution_result<F, T>(
+    result: &ExecutionResult,
+    function_name: &str,
+    success_handler: F,
+) -> Option<T>
 where
-    F: FnOnce(&[u8]),
+    F: FnOnce(&[u8]) -> T,
 {
     match result {
         ExecutionResult::Success { output, .. } => {
@@ -34,16 +222,197 @@ where
             info!("Output length: {} bytes", output_bytes.len());
             info!("Raw output: 0x{}", hex::encode(output_bytes));
 
-            success_handler(output_bytes);
+            Some(success_handler(output_bytes))
         }
         ExecutionResult::Revert { output, .. } => {
             error!("{} call reverted", function_name);
             error!("Revert output: 0x{}", hex::encode(output));
+            None
         }
         ExecutionResult::Halt { reason, .. } => {
-            error!("{} call halted: {:?}", function_name, reason);
+            error!(
+                "{} call halted: {:?} ({})",
+                function_name,
+                reason,
+                explain_halt(reason)
+            );
+            None
+        }
+    }
+}
+
+/// Compare the target account before and after a verification call
+///
+/// Verification calls are expected to be read-only, so any change to the
+/// target's balance, nonce, code hash or storage means a state-mutating
+/// function is being treated as a view. Returns one description per change.
+pub fn detect_view_state_changes(
+    pre_bundle: &BundleState,
+    post_bundle: &BundleState,
+    target: Address,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    let Some(post_account) = post_bundle.state.get(&target) else {
+        return changes;
+    };
+    let pre_account = pre_bundle.state.get(&target);
+
+    // Accounts untouched by genesis are only in the database, in which case
+    // the bundle keeps the loaded value as the original info.
+    let pre_info = match pre_account {
+        Some(account) => account.info.as_ref(),
+        None => post_account.original_info.as_ref(),
+    };
+    match (pre_info, post_account.info.as_ref()) {
+        (Some(pre), Some(post)) => {
+            if pre.balance != post.balance {
+                changes.push(format!("balance {} -> {}", pre.balance, post.balance));
+            }
+            if pre.nonce != post.nonce {
+                changes.push(format!("nonce {} -> {}", pre.nonce, post.nonce));
+            }
+            if pre.code_hash != post.code_hash {
+                changes.push(format!("code hash {} -> {}", pre.code_hash, post.code_hash));
+            }
+        }
+        (Some(_), None) => changes.push("account destroyed".to_string()),
+        (None, Some(_)) => changes.push("account created".to_string()),
+        (None, None) => {}
+    }
+
+    for (slot, value) in post_account.storage.iter() {
+        let before = pre_account
+            .and_then(|account| account.storage.get(slot))
+            .map(|pre_slot| pre_slot.present_value)
+            .unwrap_or(value.previous_or_original_value);
+        if before != value.present_value {
+            changes.push(format!(
+                "storage slot {:#x}: {} -> {}",
+                slot, before, value.present_value
+            ));
+        }
+    }
+
+    changes
+}
+
+/// Send `Genesis.initialize` again on top of the genesis state and check it is refused
+///
+/// The second initialization must revert with `GenesisAlreadyCompleted` or
+/// `AlreadyInitialized`. Any other outcome, above all success, means the
+/// contracts do not guard against being initialized twice.
+pub fn verify_initialize_idempotent(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    config: &GenesisConfig,
+    block: &BlockContext,
+    options: &VerifyOptions,
+    context: &RunContext,
+) -> VerificationReport {
+    let mut report = VerificationReport::new(options.mode);
+    let field = "Genesis.initialize sent again";
+    let expected = "revert with GenesisAlreadyCompleted or AlreadyInitialized";
+    let genesis_address = context.registry.genesis_addr();
+    let transaction = match call_genesis_initialize(options.system_caller, genesis_address, config)
+    {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            report.record(field, expected, format!("not built: {}", e), false);
+            return report;
+        }
+    };
+    let result = match execute_revm_sequential(
+        db,
+        SpecId::LATEST,
+        prepare_env(block),
+        &[transaction],
+        Some(bundle_state),
+        context,
+    ) {
+        Ok((results, _)) => results.into_iter().next(),
+        Err(e) => {
+            let e = e.map_db_err(|_| "Database error".to_string());
+            report.record(field, expected, format!("not executed: {:?}", e), false);
+            return report;
+        }
+    };
+    let (actual, passed) = match &result {
+        Some(ExecutionResult::Success { .. }) => (
+            "success: Genesis can be initialized twice".to_string(),
+            false,
+        ),
+        Some(result @ ExecutionResult::Revert { output, .. }) => {
+            match already_initialized_error(output) {
+                Some(error) => (format!("revert with {}", error), true),
+                None => (analyze_txn_result(result, context), false),
+            }
+        }
+        Some(result) => (analyze_txn_result(result, context), false),
+        None => ("no execution result".to_string(), false),
+    };
+    report.record(field, expected, actual, passed);
+    report
+}
+
+/// Run a read-only call on top of the genesis state and return its result
+///
+/// Warns about any state the call wrote to its target, see [`detect_view_state_changes`].
+pub fn execute_view_call(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    transaction: TxEnv,
+    call_name: &str,
+    context: &RunContext,
+) -> Result<ExecutionResult, String> {
+    let env = prepare_env(block);
+    let target = match transaction.transact_to {
+        TxKind::Call(address) => Some(address),
+        TxKind::Create => None,
+    };
+    let (result, post_bundle) = execute_revm_sequential(
+        db,
+        SpecId::LATEST,
+        env,
+        &[transaction],
+        Some(bundle_state.clone()),
+        context,
+    )
+    .map_err(|e| format!("{:?}", e.map_db_err(|_| "Database error".to_string())))?;
+
+    if let Some(target) = target {
+        for change in detect_view_state_changes(&bundle_state, &post_bundle, target) {
+            context.diagnostics.emit(
+                DiagnosticCode::ViewCallWroteState,
+                format!(
+                    "{}: view call wrote state on {}: {}",
+                    call_name,
+                    context.registry.display_address(target),
+                    change
+                ),
+            );
         }
     }
+    result
+        .into_iter()
+        .next()
+        .ok_or_else(|| "no execution result".to_string())
+}
+
+/// Output of a successful call, or the reason it reverted or halted
+pub fn successful_output(
+    result: &ExecutionResult,
+    function_name: &str,
+    context: &RunContext,
+) -> Result<Bytes, String> {
+    match result {
+        ExecutionResult::Success { output, .. } => Ok(output.data().clone()),
+        _ => Err(format!(
+            "{} call failed: {}",
+            function_name,
+            analyze_txn_result(result, context)
+        )),
+    }
 }
 
 /// Generic template for verification functions
@@ -53,92 +422,660 @@ where
 fn execute_verification<F>(
     db: impl DatabaseRef,
     bundle_state: BundleState,
+    block: &BlockContext,
+    mode: VerifyMode,
     transaction: TxEnv,
     verification_name: &str,
+    context: &RunContext,
     result_handler: F,
-) where
-    F: FnOnce(&ExecutionResult),
+) -> VerificationReport
+where
+    F: FnOnce(&ExecutionResult) -> VerificationReport,
 {
-    let env = prepare_env();
-    let r = execute_revm_sequential(db, SpecId::LATEST, env, &[transaction], Some(bundle_state));
-    
-    match r {
-        Ok((result, _)) => {
-            if let Some(execution_result) = result.get(0) {
-                result_handler(execution_result);
-            }
-        }
+    match execute_view_call(
+        db,
+        bundle_state,
+        block,
+        transaction,
+        &format!("verify {}", verification_name),
+        context,
+    ) {
+        Ok(execution_result) => result_handler(&execution_result),
         Err(e) => {
-            error!(
-                "verify {} error: {:?}",
-                verification_name,
-                e.map_db_err(|_| "Database error".to_string())
+            let mut report = VerificationReport::new(mode);
+            report.record(
+                format!("verify {}", verification_name),
+                "execution",
+                e,
+                false,
             );
+            report
         }
     }
 }
 
-fn verify_validator_set(db: impl DatabaseRef, bundle_state: BundleState, config: &GenesisConfig) {
-    let get_validator_set_txn = call_get_validator_set();
+fn verify_validator_set(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    config: &GenesisConfig,
+    mode: VerifyMode,
+    context: &RunContext,
+) -> VerificationReport {
+    let get_validator_set_txn =
+        call_get_validator_set(caller, context.registry.validator_manager_addr());
     execute_verification(
         db,
         bundle_state,
+        block,
+        mode,
         get_validator_set_txn,
         "validator set",
-        |result| print_validator_set_result(result, config),
-    );
+        context,
+        |result| print_validator_set_result(result, config, mode),
+    )
 }
 
-fn verify_epoch_info(db: impl DatabaseRef, bundle_state: BundleState) {
-    let get_epoch_info_txn = call_get_current_epoch_info();
+/// Check the epoch, last transition time and duration `EpochManager` reports
+///
+/// The `Timestamp` contract is only advanced by blocks, so the first
+/// transition happens at time 0 of its clock.
+fn verify_epoch_info(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    epoch_interval_micros: u64,
+    mode: VerifyMode,
+    context: &RunContext,
+) -> VerificationReport {
+    let get_epoch_info_txn =
+        call_get_current_epoch_info(caller, context.registry.epoch_manager_addr());
+    let expected = EpochInfo::at_genesis(0, epoch_interval_micros);
     execute_verification(
         db,
         bundle_state,
+        block,
+        mode,
         get_epoch_info_txn,
         "epoch info",
-        |result| print_current_epoch_info_result(result),
-    );
+        context,
+        |result| print_current_epoch_info_result(result, &expected, mode),
+    )
+}
+
+/// Check the randomness config matches the configured one and that no DKG
+/// or reconfiguration is in progress
+///
+/// Contracts missing from the registry are skipped.
+fn verify_randomness(
+    db: impl DatabaseRef + Clone,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    config: &GenesisConfig,
+    mode: VerifyMode,
+    context: &RunContext,
+) -> VerificationReport {
+    let mut report = VerificationReport::new(mode);
+    let registry = &context.registry;
+    if let Some(current_txn) = call_current_randomness_config(caller, registry) {
+        report.merge(execute_verification(
+            db.clone(),
+            bundle_state.clone(),
+            block,
+            mode,
+            current_txn,
+            "randomness config",
+            context,
+            |result| {
+                print_randomness_config_result(result, config.randomness_config.as_ref(), mode)
+            },
+        ));
+    }
+    for (call, function_name) in [
+        (call_is_dkg_in_progress(caller, registry), "isDKGInProgress"),
+        (
+            call_is_reconfiguration_in_progress(caller, registry),
+            "isReconfigurationInProgress",
+        ),
+    ] {
+        if report.should_stop() {
+            break;
+        }
+        let Some(txn) = call else {
+            continue;
+        };
+        report.merge(execute_verification(
+            db.clone(),
+            bundle_state.clone(),
+            block,
+            mode,
+            txn,
+            function_name,
+            context,
+            |result| print_not_in_progress_result(result, function_name, mode),
+        ));
+    }
+    report
 }
 
-pub fn verify_jwks(db: impl DatabaseRef, bundle_state: BundleState, jwks_file: &str) {
-    let get_jwks_txn = call_get_observed_jwks();
+pub fn verify_jwks(
+    db: impl DatabaseRef,
+    bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
+    jwks_file: &str,
+    mode: VerifyMode,
+    context: &RunContext,
+) -> VerificationReport {
+    let expected = match read_jwks_from_file(jwks_file, context) {
+        Ok(expected) => expected,
+        Err(e) => {
+            let mut report = VerificationReport::new(mode);
+            report.record("expected JWKs", jwks_file, e, false);
+            return report;
+        }
+    };
+    let get_jwks_txn = call_get_observed_jwks(caller, context.registry.jwk_manager_addr());
     execute_verification(
         db,
         bundle_state,
+        block,
+        mode,
         get_jwks_txn,
         "jwks",
-        |result| print_jwks_result(result, jwks_file),
-    );
+        context,
+        |result| print_jwks_result(result, &expected, mode),
+    )
 }
 
 pub fn verify_oidc_providers(
     db: impl DatabaseRef,
     bundle_state: BundleState,
+    block: &BlockContext,
+    caller: Address,
     oidc_providers_file: &str,
-) {
-    let get_oidc_providers_txn = call_get_active_providers();
+    mode: VerifyMode,
+    context: &RunContext,
+) -> VerificationReport {
+    let expected = match read_oidc_providers_from_file(oidc_providers_file, context) {
+        Ok(expected) => expected,
+        Err(e) => {
+            let mut report = VerificationReport::new(mode);
+            report.record("expected OIDC providers", oidc_providers_file, e, false);
+            return report;
+        }
+    };
+    let get_oidc_providers_txn =
+        call_get_active_providers(caller, context.registry.jwk_manager_addr());
     execute_verification(
         db,
         bundle_state,
+        block,
+        mode,
         get_oidc_providers_txn,
         "oidc providers",
-        |result| print_oidc_providers_result(result, oidc_providers_file),
-    );
+        context,
+        |result| print_oidc_providers_result(result, &expected, oidc_providers_file, mode, context),
+    )
+}
+
+/// keccak256 of the code `address` holds after genesis, `None` if it has no code
+fn final_code_hash(
+    db: &impl DatabaseRef,
+    bundle_state: &BundleState,
+    address: Address,
+) -> Option<B256> {
+    let info = match bundle_state.account(&address) {
+        Some(account) => account.info.clone()?,
+        None => db.basic_ref(address).ok()??,
+    };
+    let code = match info.code {
+        Some(code) => code,
+        None => match bundle_state.contracts.get(&info.code_hash) {
+            Some(code) => code.clone(),
+            None => db.code_by_hash_ref(info.code_hash).ok()?,
+        },
+    };
+    let code = code.original_bytes();
+    (!code.is_empty()).then(|| keccak256(&code))
 }
 
+/// Compare the code at each registered contract address with its artifact's deployed bytecode
+///
+/// `expected` maps contract names to the keccak256 of their deployed
+/// bytecode, as loaded by [`load_artifact_code_hashes`](crate::codehash::load_artifact_code_hashes).
+/// Contracts without an expected hash are skipped.
+pub fn verify_bytecode(
+    db: &impl DatabaseRef,
+    bundle_state: &BundleState,
+    expected: &BTreeMap<String, B256>,
+    mode: VerifyMode,
+    context: &RunContext,
+) -> VerificationReport {
+    let mut report = VerificationReport::new(mode);
+    for (contract_name, address) in context.registry.contracts() {
+        let Some(expected_hash) = expected.get(contract_name) else {
+            continue;
+        };
+        match final_code_hash(db, bundle_state, *address) {
+            Some(code_hash) => {
+                report.check_eq(
+                    format!("{} code hash", contract_name),
+                    *expected_hash,
+                    code_hash,
+                );
+            }
+            None => report.record(
+                format!("{} code hash", contract_name),
+                expected_hash,
+                format!("no code at {}", address),
+                false,
+            ),
+        }
+        if report.should_stop() {
+            break;
+        }
+    }
+    report
+}
+
+/// A verification that can run independently of the others
+type Verification<'a> = Box<dyn Fn() -> VerificationReport + Send + Sync + 'a>;
+
+/// Run every post-genesis verification and aggregate their reports
+///
+/// The verifications only read the state, so they run concurrently, each on
+/// its own copy of the database and bundle state. Their reports are merged in
+/// a fixed order; in [`VerifyMode::FailFast`] the reports after the first one
+/// with a mismatch are dropped.
 pub fn verify_result(
     db: InMemoryDB,
     bundle_state: BundleState,
     config: &GenesisConfig,
     jwks_file: Option<String>,
     oidc_providers_file: Option<String>,
-) {
-    verify_validator_set(db.clone(), bundle_state.clone(), config);
-    verify_epoch_info(db.clone(), bundle_state.clone());
-    if let Some(jwks_file) = jwks_file {
-        verify_jwks(db.clone(), bundle_state.clone(), &jwks_file);
-    }
-    if let Some(oidc_providers_file) = oidc_providers_file {
-        verify_oidc_providers(db.clone(), bundle_state.clone(), &oidc_providers_file);
+    block: &BlockContext,
+    options: &VerifyOptions,
+    context: &RunContext,
+) -> VerificationReport {
+    let mode = options.mode;
+    let caller = options.system_caller;
+    let (db, bundle_state) = (&db, &bundle_state);
+    let mut verifications: Vec<Verification> = Vec::new();
+    if let Some(expected_code_hashes) = &options.expected_code_hashes {
+        verifications.push(Box::new(move || {
+            verify_bytecode(db, bundle_state, expected_code_hashes, mode, context)
+        }));
+    }
+    verifications.push(Box::new(move || {
+        verify_validator_set(
+            db.clone(),
+            bundle_state.clone(),
+            block,
+            caller,
+            config,
+            mode,
+            context,
+        )
+    }));
+    let epoch_interval_micros = options.epoch_interval_micros;
+    verifications.push(Box::new(move || {
+        verify_epoch_info(
+            db.clone(),
+            bundle_state.clone(),
+            block,
+            caller,
+            epoch_interval_micros,
+            mode,
+            context,
+        )
+    }));
+    verifications.push(Box::new(move || {
+        verify_randomness(
+            db.clone(),
+            bundle_state.clone(),
+            block,
+            caller,
+            config,
+            mode,
+            context,
+        )
+    }));
+    if let Some(jwks_file) = &jwks_file {
+        verifications.push(Box::new(move || {
+            verify_jwks(
+                db.clone(),
+                bundle_state.clone(),
+                block,
+                caller,
+                jwks_file,
+                mode,
+                context,
+            )
+        }));
+    }
+    if let Some(oidc_providers_file) = &oidc_providers_file {
+        verifications.push(Box::new(move || {
+            verify_oidc_providers(
+                db.clone(),
+                bundle_state.clone(),
+                block,
+                caller,
+                oidc_providers_file,
+                mode,
+                context,
+            )
+        }));
+    }
+
+    let reports: Vec<VerificationReport> = verifications
+        .par_iter()
+        .map(|verification| verification())
+        .collect();
+    let mut report = VerificationReport::new(mode);
+    for verification_report in reports {
+        report.merge(verification_report);
+        if report.should_stop() {
+            break;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use revm::primitives::{AccountInfo, Bytecode, Bytes};
+    use revm_primitives::address;
+    use std::collections::BTreeSet;
+
+    use crate::{
+        execute::{DeployOptions, genesis_generate},
+        utils::{SYSTEM_ACCOUNT_INFO, new_system_call_txn},
+    };
+
+    use super::*;
+
+    const TARGET: Address = address!("00000000000000000000000000000000000c0de0");
+
+    fn run_view_call(runtime_code: &str) -> (BundleState, BundleState) {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(SYSTEM_CALLER, SYSTEM_ACCOUNT_INFO);
+        db.insert_account_info(
+            TARGET,
+            AccountInfo {
+                code: Some(Bytecode::new_raw(Bytes::from(
+                    hex::decode(runtime_code).unwrap(),
+                ))),
+                ..AccountInfo::default()
+            },
+        );
+
+        let pre_bundle = BundleState::default();
+        let (_, post_bundle) = execute_revm_sequential(
+            db,
+            SpecId::LATEST,
+            prepare_env(&BlockContext::default()),
+            &[new_system_call_txn(SYSTEM_CALLER, TARGET, Bytes::new())],
+            Some(pre_bundle.clone()),
+            &RunContext::default(),
+        )
+        .unwrap();
+        (pre_bundle, post_bundle)
+    }
+
+    #[test]
+    fn test_verification_report_is_written_as_json() {
+        let mut report = VerificationReport::new(VerifyMode::CollectAll);
+        report.check_eq("validator count", 2, 2);
+        report.check_eq("epoch", 1, 0);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        report.write_json(&path).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["schema_version"], VERIFICATION_REPORT_SCHEMA_VERSION);
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["mode"], "collect-all");
+        assert_eq!(json["checks_total"], 2);
+        assert_eq!(json["checks_failed"], 1);
+        assert_eq!(
+            json["checks"][1],
+            serde_json::json!({
+                "field": "epoch",
+                "expected": "1",
+                "actual": "0",
+                "passed": false
+            })
+        );
+    }
+
+    #[test]
+    fn test_view_call_writing_state_is_detected() {
+        // PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+        let (pre_bundle, post_bundle) = run_view_call("600160005500");
+        let changes = detect_view_state_changes(&pre_bundle, &post_bundle, TARGET);
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].contains("storage slot 0x0"));
+    }
+
+    #[test]
+    fn test_read_only_view_call_is_clean() {
+        // PUSH1 0x00 SLOAD POP STOP
+        let (pre_bundle, post_bundle) = run_view_call("6000545000");
+        let changes = detect_view_state_changes(&pre_bundle, &post_bundle, TARGET);
+        assert!(changes.is_empty(), "unexpected changes: {:?}", changes);
+    }
+
+    #[test]
+    fn test_verify_bytecode_compares_deployed_code_hashes() {
+        let code = Bytes::from_static(&[0x60, 0x01]);
+        let context = RunContext::default();
+        let mut db = InMemoryDB::default();
+        for address in [
+            context.registry.genesis_addr(),
+            context.registry.validator_manager_addr(),
+        ] {
+            db.insert_account_info(
+                address,
+                AccountInfo {
+                    code: Some(Bytecode::new_raw(code.clone())),
+                    ..AccountInfo::default()
+                },
+            );
+        }
+        let expected = BTreeMap::from([
+            ("Genesis".to_string(), keccak256(&code)),
+            ("ValidatorManager".to_string(), keccak256([0x60, 0x02])),
+            ("EpochManager".to_string(), keccak256(&code)),
+        ]);
+
+        let report = verify_bytecode(
+            &db,
+            &BundleState::default(),
+            &expected,
+            VerifyMode::CollectAll,
+            &context,
+        );
+        assert_eq!(report.checks.len(), 3);
+        let failures: Vec<_> = report
+            .failures()
+            .map(|check| check.field.as_str())
+            .collect();
+        assert_eq!(
+            failures,
+            ["ValidatorManager code hash", "EpochManager code hash"]
+        );
+        // Checks follow the registry order: ValidatorManager, EpochManager, ..., Genesis
+        assert!(report.checks[1].actual.starts_with("no code at"));
+        assert!(report.checks[2].passed);
+
+        let report = verify_bytecode(
+            &db,
+            &BundleState::default(),
+            &expected,
+            VerifyMode::FailFast,
+            &context,
+        );
+        assert_eq!(report.checks.len(), 1);
+    }
+
+    /// Contract bytecode compiled by `forge build` and `extract_bytecode.py`
+    ///
+    /// Neither is committed, so the tests reading it are ignored by default.
+    const BYTECODE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../out");
+
+    /// Smallest genesis config accepted by the contracts: one validator
+    fn minimal_config() -> GenesisConfig {
+        let network_address = "/ip4/127.0.0.1/tcp/2024/noise-ik/2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f/handshake/0";
+        serde_json::from_value(serde_json::json!({
+            "validatorAddresses": ["0x6e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"],
+            "consensusPublicKeys": [
+                "851d41932d866f5fabed6673898e15473e6a0adcf5033d2c93816c6b115c85ad3451e0bac61d570d5ed9f23e1e7f77c4"
+            ],
+            "votingPowers": ["20000"],
+            "validatorNetworkAddresses": [network_address],
+            "fullnodeNetworkAddresses": [network_address],
+            "aptosAddresses": [
+                "2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f"
+            ]
+        }))
+        .unwrap()
+    }
+
+    /// Write a JWKs and an OIDC providers file for one issuer into `dir`
+    fn write_identity_inputs(dir: &std::path::Path) -> (Option<String>, Option<String>) {
+        let jwks_file = dir.join("jwks.json");
+        std::fs::write(
+            &jwks_file,
+            serde_json::json!({
+                "entries": [{
+                    "issuer": "https://accounts.google.com",
+                    "version": 1,
+                    "jwks": [{
+                        "variant": 1,
+                        "data": "0x0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20"
+                    }]
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let oidc_file = dir.join("oidc_providers.json");
+        std::fs::write(
+            &oidc_file,
+            serde_json::json!({
+                "providers": [{
+                    "name": "https://accounts.google.com",
+                    "configUrl": "https://accounts.google.com/.well-known/openid-configuration",
+                    "active": true
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        (
+            Some(jwks_file.to_string_lossy().to_string()),
+            Some(oidc_file.to_string_lossy().to_string()),
+        )
+    }
+
+    #[test]
+    #[ignore = "needs ../out from forge build and extract_bytecode.py"]
+    fn test_genesis_is_generated_and_verified_in_a_temp_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let (jwks_file, oidc_file) = write_identity_inputs(dir.path());
+        let output_dir = dir.path().join("output");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let config = minimal_config();
+        let block = BlockContext::default();
+        let (db, bundle_state) = genesis_generate(
+            BYTECODE_DIR,
+            &output_dir.to_string_lossy(),
+            &config,
+            jwks_file.clone(),
+            oidc_file.clone(),
+            &block,
+            &DeployOptions::default(),
+        )
+        .unwrap();
+        assert!(output_dir.join("genesis_accounts.json").exists());
+
+        let idempotency = verify_initialize_idempotent(
+            &db,
+            bundle_state.clone(),
+            &config,
+            &block,
+            &VerifyOptions::default(),
+            &RunContext::default(),
+        );
+        assert!(
+            idempotency.passed(),
+            "{:?}",
+            idempotency.failures().collect::<Vec<_>>()
+        );
+
+        let report = verify_result(
+            db,
+            bundle_state,
+            &config,
+            jwks_file,
+            oidc_file,
+            &block,
+            &VerifyOptions::default(),
+            &RunContext::default(),
+        );
+        assert!(
+            report.passed(),
+            "verification failures: {:?}",
+            report.failures().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[ignore = "needs ../out from forge build and extract_bytecode.py"]
+    fn test_bundle_state_json_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let (jwks_file, oidc_file) = write_identity_inputs(dir.path());
+        let output_dir = dir.path().join("output");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let (_, mut bundle_state) = genesis_generate(
+            BYTECODE_DIR,
+            &output_dir.to_string_lossy(),
+            &minimal_config(),
+            jwks_file,
+            oidc_file,
+            &BlockContext::default(),
+            &DeployOptions::default(),
+        )
+        .unwrap();
+        // The file leaves out the system caller, see `--keep-system-caller`
+        bundle_state.state.remove(&SYSTEM_CALLER);
+        let path = output_dir.join("bundle_state.json");
+        let restored = crate::inspect::read_bundle_state(&path.to_string_lossy()).unwrap();
+
+        let addresses =
+            |state: &BundleState| -> BTreeSet<Address> { state.state.keys().copied().collect() };
+        assert_eq!(addresses(&restored), addresses(&bundle_state));
+        for (address, account) in &bundle_state.state {
+            let restored_account = &restored.state[address];
+            assert_eq!(restored_account.info, account.info, "{}", address);
+            let slots = |account: &revm::db::BundleAccount| -> BTreeMap<U256, U256> {
+                account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (*slot, value.present_value))
+                    .collect()
+            };
+            assert_eq!(slots(restored_account), slots(account), "{}", address);
+        }
+        let code_hashes =
+            |state: &BundleState| -> BTreeSet<B256> { state.contracts.keys().copied().collect() };
+        assert_eq!(code_hashes(&restored), code_hashes(&bundle_state));
     }
 }
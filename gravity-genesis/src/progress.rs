@@ -0,0 +1,90 @@
+use std::{
+    fmt, io,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+/// Progress bar of one run, shown on stderr once enabled
+///
+/// Until [`Progress::enable`] is called, the other methods do nothing, so
+/// library users and non-interactive runs see no progress output. Clones
+/// share the same bar.
+#[derive(Clone, Default)]
+pub struct Progress(Arc<OnceLock<ProgressBar>>);
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("enabled", &self.0.get().is_some())
+            .finish()
+    }
+}
+
+impl Progress {
+    /// Show the progress bar on stderr for the rest of the run
+    pub fn enable(&self) {
+        self.0.get_or_init(|| {
+            let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+            bar.enable_steady_tick(Duration::from_millis(100));
+            bar
+        });
+    }
+
+    /// Start a phase of the run, such as "deploying contracts"
+    ///
+    /// With a `total` the bar counts steps towards it, otherwise it spins and
+    /// counts the steps taken so far.
+    pub fn start_phase(&self, name: &'static str, total: Option<u64>) {
+        let Some(bar) = self.0.get() else {
+            return;
+        };
+        let template = match total {
+            Some(_) => "{spinner} {msg} ({pos}/{len}) [{elapsed}]",
+            None => "{spinner} {msg} ({pos}) [{elapsed}]",
+        };
+        bar.set_style(ProgressStyle::with_template(template).expect("valid progress template"));
+        bar.set_message(name);
+        bar.set_length(total.unwrap_or(0));
+        bar.set_position(0);
+    }
+
+    /// Count one step of the current phase
+    pub fn step(&self) {
+        if let Some(bar) = self.0.get() {
+            bar.inc(1);
+        }
+    }
+
+    /// Count the current phase as done, for phases whose steps are not counted one by one
+    pub fn complete_phase(&self) {
+        if let Some(bar) = self.0.get() {
+            bar.set_position(bar.length().unwrap_or(bar.position()));
+        }
+    }
+
+    /// Remove the progress bar once the run is over
+    pub fn finish(&self) {
+        if let Some(bar) = self.0.get() {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+/// Log writer that hides the progress bar while a record is written, so the
+/// bar is redrawn below the record instead of through it
+pub struct LogWriter<W>(pub W, pub Progress);
+
+impl<W: io::Write> io::Write for LogWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.1.0.get() {
+            Some(bar) => bar.suspend(|| self.0.write(buf)),
+            None => self.0.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
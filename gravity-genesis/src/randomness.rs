@@ -0,0 +1,420 @@
+use alloy_sol_macro::sol;
+use alloy_sol_types::{SolCall, SolValue};
+use revm_primitives::{Address, ExecutionResult, TxEnv, hex};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    post_genesis::{VerificationReport, VerifyMode, handle_execution_result},
+    registry::ContractRegistry,
+    utils::new_system_call_txn,
+};
+
+sol! {
+    interface IRandomnessConfig {
+        struct FixedPoint64 {
+            uint128 value;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        enum ConfigVariant {
+            V1,
+            V2
+        }
+
+        struct ConfigV1 {
+            FixedPoint64 secrecyThreshold;
+            FixedPoint64 reconstructionThreshold;
+        }
+
+        struct ConfigV2 {
+            FixedPoint64 secrecyThreshold;
+            FixedPoint64 reconstructionThreshold;
+            FixedPoint64 fastPathSecrecyThreshold;
+        }
+
+        struct RandomnessConfigData {
+            ConfigVariant variant;
+            ConfigV1 configV1;
+            ConfigV2 configV2;
+        }
+
+        function setForNextEpoch(RandomnessConfigData memory newConfig) external;
+        function onNewEpoch() external;
+        function current() external view returns (RandomnessConfigData memory);
+    }
+
+    interface IDKG {
+        function isDKGInProgress() external view returns (bool);
+    }
+
+    interface IReconfigurationWithDKG {
+        function isReconfigurationInProgress() external view returns (bool);
+    }
+}
+
+use IRandomnessConfig::{ConfigV1, ConfigV2, ConfigVariant, FixedPoint64, RandomnessConfigData};
+
+/// `FixedPoint64` of 1/2, the secrecy threshold `Genesis.initialize` sets
+pub const HALF: u128 = 1 << 63;
+/// `FixedPoint64` of 2/3, the reconstruction and fast path thresholds `Genesis.initialize` sets
+pub const TWO_THIRDS: u128 = 12_297_829_382_473_033_728;
+
+/// Randomness variant, `V2` adding a fast path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RandomnessVariant {
+    V1,
+    V2,
+}
+
+/// Randomness thresholds applied at genesis
+///
+/// Thresholds are `FixedPoint64` values, where 2^64 stands for 1, given as
+/// decimal strings like voting powers. Without one the chain keeps the V2
+/// config `Genesis.initialize` sets: secrecy 1/2, reconstruction and fast
+/// path 2/3.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RandomnessConfig {
+    pub variant: RandomnessVariant,
+    #[serde(rename = "secrecyThreshold")]
+    pub secrecy_threshold: String,
+    #[serde(rename = "reconstructionThreshold")]
+    pub reconstruction_threshold: String,
+    /// Required by `V2`, not allowed with `V1`
+    #[serde(
+        rename = "fastPathSecrecyThreshold",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fast_path_secrecy_threshold: Option<String>,
+}
+
+fn parse_threshold(field: &str, value: &str) -> Result<u128, String> {
+    value
+        .parse::<u128>()
+        .map_err(|e| format!("{} {:?} is not a FixedPoint64 value: {}", field, value, e))
+}
+
+fn fixed(value: u128) -> FixedPoint64 {
+    FixedPoint64 { value }
+}
+
+impl RandomnessConfig {
+    /// Convert to the contract struct, applying the checks of `RandomnessConfig._validateConfig`
+    pub fn to_data(&self) -> Result<RandomnessConfigData, String> {
+        let secrecy = parse_threshold("secrecyThreshold", &self.secrecy_threshold)?;
+        let reconstruction =
+            parse_threshold("reconstructionThreshold", &self.reconstruction_threshold)?;
+        if reconstruction <= secrecy {
+            return Err(format!(
+                "reconstructionThreshold {} must be above secrecyThreshold {}",
+                reconstruction, secrecy
+            ));
+        }
+        let data = match (self.variant, &self.fast_path_secrecy_threshold) {
+            (RandomnessVariant::V1, None) => RandomnessConfigData {
+                variant: ConfigVariant::V1,
+                configV1: ConfigV1 {
+                    secrecyThreshold: fixed(secrecy),
+                    reconstructionThreshold: fixed(reconstruction),
+                },
+                configV2: ConfigV2 {
+                    secrecyThreshold: fixed(0),
+                    reconstructionThreshold: fixed(0),
+                    fastPathSecrecyThreshold: fixed(0),
+                },
+            },
+            (RandomnessVariant::V1, Some(_)) => {
+                return Err("fastPathSecrecyThreshold is only used by V2".to_string());
+            }
+            (RandomnessVariant::V2, None) => {
+                return Err("V2 requires fastPathSecrecyThreshold".to_string());
+            }
+            (RandomnessVariant::V2, Some(fast_path)) => {
+                let fast_path = parse_threshold("fastPathSecrecyThreshold", fast_path)?;
+                if fast_path <= secrecy {
+                    return Err(format!(
+                        "fastPathSecrecyThreshold {} must be above secrecyThreshold {}",
+                        fast_path, secrecy
+                    ));
+                }
+                RandomnessConfigData {
+                    variant: ConfigVariant::V2,
+                    configV1: ConfigV1 {
+                        secrecyThreshold: fixed(0),
+                        reconstructionThreshold: fixed(0),
+                    },
+                    configV2: ConfigV2 {
+                        secrecyThreshold: fixed(secrecy),
+                        reconstructionThreshold: fixed(reconstruction),
+                        fastPathSecrecyThreshold: fixed(fast_path),
+                    },
+                }
+            }
+        };
+        Ok(data)
+    }
+}
+
+/// The config `Genesis.initialize` hands to `RandomnessConfig.initialize`
+pub fn default_randomness_data() -> RandomnessConfigData {
+    RandomnessConfigData {
+        variant: ConfigVariant::V2,
+        configV1: ConfigV1 {
+            secrecyThreshold: fixed(0),
+            reconstructionThreshold: fixed(0),
+        },
+        configV2: ConfigV2 {
+            secrecyThreshold: fixed(HALF),
+            reconstructionThreshold: fixed(TWO_THIRDS),
+            fastPathSecrecyThreshold: fixed(TWO_THIRDS),
+        },
+    }
+}
+
+fn randomness_config_addr(registry: &ContractRegistry) -> Result<Address, String> {
+    registry
+        .address_of("RandomnessConfig")
+        .ok_or_else(|| "RandomnessConfig is missing from the contract registry".to_string())
+}
+
+/// Transactions replacing the default randomness config with `config`
+///
+/// `Genesis.initialize` has already initialized `RandomnessConfig`, so the
+/// config is staged with `setForNextEpoch` and applied at once with `onNewEpoch`.
+pub fn randomness_transactions(
+    caller: Address,
+    registry: &ContractRegistry,
+    config: &RandomnessConfig,
+) -> Result<Vec<(String, TxEnv)>, String> {
+    let data = config.to_data()?;
+    let address = randomness_config_addr(registry)?;
+    info!("Setting the genesis randomness config to {:?}", config);
+    Ok(vec![
+        (
+            "RandomnessConfig.setForNextEpoch".to_string(),
+            new_system_call_txn(
+                caller,
+                address,
+                IRandomnessConfig::setForNextEpochCall { newConfig: data }
+                    .abi_encode()
+                    .into(),
+            ),
+        ),
+        (
+            "RandomnessConfig.onNewEpoch".to_string(),
+            new_system_call_txn(
+                caller,
+                address,
+                IRandomnessConfig::onNewEpochCall {}.abi_encode().into(),
+            ),
+        ),
+    ])
+}
+
+/// Call `RandomnessConfig.current`, `None` if the registry has no `RandomnessConfig`
+pub fn call_current_randomness_config(
+    caller: Address,
+    registry: &ContractRegistry,
+) -> Option<TxEnv> {
+    let address = randomness_config_addr(registry).ok()?;
+    Some(new_system_call_txn(
+        caller,
+        address,
+        IRandomnessConfig::currentCall {}.abi_encode().into(),
+    ))
+}
+
+/// Call `DKG.isDKGInProgress`, `None` if the registry has no `DKG`
+pub fn call_is_dkg_in_progress(caller: Address, registry: &ContractRegistry) -> Option<TxEnv> {
+    let address = registry.address_of("DKG")?;
+    Some(new_system_call_txn(
+        caller,
+        address,
+        IDKG::isDKGInProgressCall {}.abi_encode().into(),
+    ))
+}
+
+/// Call `ReconfigurationWithDKG.isReconfigurationInProgress`, `None` if the registry has no `ReconfigurationWithDKG`
+pub fn call_is_reconfiguration_in_progress(
+    caller: Address,
+    registry: &ContractRegistry,
+) -> Option<TxEnv> {
+    let address = registry.address_of("ReconfigurationWithDKG")?;
+    Some(new_system_call_txn(
+        caller,
+        address,
+        IReconfigurationWithDKG::isReconfigurationInProgressCall {}
+            .abi_encode()
+            .into(),
+    ))
+}
+
+fn variant_name(variant: &ConfigVariant) -> &'static str {
+    match variant {
+        ConfigVariant::V1 => "V1",
+        ConfigVariant::V2 => "V2",
+        _ => "unknown",
+    }
+}
+
+/// Compare the `current()` result against the expected randomness config
+///
+/// `expected` is the configured one, or the `Genesis.initialize` default when
+/// the config sets none.
+pub fn print_randomness_config_result(
+    result: &ExecutionResult,
+    expected: Option<&RandomnessConfig>,
+    mode: VerifyMode,
+) -> VerificationReport {
+    let expected = match expected.map(RandomnessConfig::to_data).transpose() {
+        Ok(expected) => expected.unwrap_or_else(default_randomness_data),
+        Err(e) => {
+            let mut report = VerificationReport::new(mode);
+            report.record("expected randomness config", "randomnessConfig", e, false);
+            return report;
+        }
+    };
+    handle_execution_result(result, "current", |output_bytes| {
+        let mut report = VerificationReport::new(mode);
+        let actual = match IRandomnessConfig::currentCall::abi_decode_returns(output_bytes, false) {
+            Ok(actual) => actual._0,
+            Err(e) => {
+                report.record(
+                    "randomness config",
+                    "decodable current() result",
+                    e.to_string(),
+                    false,
+                );
+                return report;
+            }
+        };
+        info!(
+            "Randomness config: {} V1 {:?} V2 {:?}",
+            variant_name(&actual.variant),
+            (
+                actual.configV1.secrecyThreshold.value,
+                actual.configV1.reconstructionThreshold.value
+            ),
+            (
+                actual.configV2.secrecyThreshold.value,
+                actual.configV2.reconstructionThreshold.value,
+                actual.configV2.fastPathSecrecyThreshold.value
+            ),
+        );
+        report.check_eq(
+            "randomness variant",
+            variant_name(&expected.variant),
+            variant_name(&actual.variant),
+        );
+        for (name, expected, actual) in [
+            (
+                "randomness V1 secrecy threshold",
+                &expected.configV1.secrecyThreshold,
+                &actual.configV1.secrecyThreshold,
+            ),
+            (
+                "randomness V1 reconstruction threshold",
+                &expected.configV1.reconstructionThreshold,
+                &actual.configV1.reconstructionThreshold,
+            ),
+            (
+                "randomness V2 secrecy threshold",
+                &expected.configV2.secrecyThreshold,
+                &actual.configV2.secrecyThreshold,
+            ),
+            (
+                "randomness V2 reconstruction threshold",
+                &expected.configV2.reconstructionThreshold,
+                &actual.configV2.reconstructionThreshold,
+            ),
+            (
+                "randomness V2 fast path secrecy threshold",
+                &expected.configV2.fastPathSecrecyThreshold,
+                &actual.configV2.fastPathSecrecyThreshold,
+            ),
+        ] {
+            report.check_eq(name, expected.value, actual.value);
+        }
+        report
+    })
+    .unwrap_or_else(|| VerificationReport::call_failed(mode, "current"))
+}
+
+/// Check that a DKG or reconfiguration progress flag is cleared right after genesis
+///
+/// The call only succeeds once the contract is initialized, so this also
+/// checks `Genesis.initialize` reached it.
+pub fn print_not_in_progress_result(
+    result: &ExecutionResult,
+    function_name: &str,
+    mode: VerifyMode,
+) -> VerificationReport {
+    handle_execution_result(result, function_name, |output_bytes| {
+        let mut report = VerificationReport::new(mode);
+        match bool::abi_decode(output_bytes, false) {
+            Ok(in_progress) => {
+                report.check_eq(function_name, false, in_progress);
+            }
+            Err(_) => report.record(
+                function_name,
+                "a bool",
+                format!("0x{}", hex::encode(output_bytes)),
+                false,
+            ),
+        }
+        report
+    })
+    .unwrap_or_else(|| VerificationReport::call_failed(mode, function_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v2(secrecy: &str, reconstruction: &str, fast_path: Option<&str>) -> RandomnessConfig {
+        RandomnessConfig {
+            variant: RandomnessVariant::V2,
+            secrecy_threshold: secrecy.to_string(),
+            reconstruction_threshold: reconstruction.to_string(),
+            fast_path_secrecy_threshold: fast_path.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_randomness_config_matches_genesis_default_and_is_checked() {
+        let half = HALF.to_string();
+        let two_thirds = TWO_THIRDS.to_string();
+        let config = v2(&half, &two_thirds, Some(&two_thirds));
+        assert_eq!(
+            config.to_data().unwrap().abi_encode(),
+            default_randomness_data().abi_encode()
+        );
+
+        let config: RandomnessConfig = serde_json::from_value(serde_json::json!({
+            "variant": "V1",
+            "secrecyThreshold": "1",
+            "reconstructionThreshold": "2",
+        }))
+        .unwrap();
+        let data = config.to_data().unwrap();
+        assert_eq!(data.variant, ConfigVariant::V1);
+        assert_eq!(data.configV1.reconstructionThreshold.value, 2);
+
+        for (config, reason) in [
+            (
+                v2("2", "2", Some("3")),
+                "reconstructionThreshold 2 must be above",
+            ),
+            (
+                v2("2", "3", Some("1")),
+                "fastPathSecrecyThreshold 1 must be above",
+            ),
+            (v2("2", "3", None), "V2 requires fastPathSecrecyThreshold"),
+            (v2("half", "3", Some("4")), "secrecyThreshold \"half\""),
+        ] {
+            let err = config.to_data().unwrap_err();
+            assert!(err.starts_with(reason), "{}", err);
+        }
+    }
+}
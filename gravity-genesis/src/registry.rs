@@ -0,0 +1,388 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use revm_primitives::Address;
+use tracing::info;
+
+use crate::utils::{CONTRACTS, SYSTEM_CALLER};
+
+/// Contracts the tool calls directly, which every registry must place
+pub const REQUIRED_CONTRACTS: [&str; 4] =
+    ["Genesis", "ValidatorManager", "EpochManager", "JWKManager"];
+
+/// Contracts each genesis contract calls into, for checking a partial deployment
+/// and ordering the deployment
+///
+/// `Genesis` lists the contracts its `initialize` sets up.
+pub const CONTRACT_DEPENDENCIES: [(&str, &[&str]); 8] = [
+    (
+        "Genesis",
+        &[
+            "Block",
+            "EpochManager",
+            "Timestamp",
+            "ReconfigurationWithDKG",
+            "DKG",
+            "RandomnessConfig",
+            "StakeConfig",
+            "ValidatorManager",
+            "ValidatorPerformanceTracker",
+            "GovToken",
+            "Timelock",
+            "GravityGovernor",
+            "JWKManager",
+            "KeylessAccount",
+        ],
+    ),
+    (
+        "ValidatorManager",
+        &[
+            "StakeConfig",
+            "ValidatorManagerUtils",
+            "StakeCredit",
+            "Timestamp",
+        ],
+    ),
+    ("ValidatorPerformanceTracker", &["ValidatorManager"]),
+    ("EpochManager", &["Timestamp", "ValidatorManager"]),
+    (
+        "Block",
+        &[
+            "ValidatorManager",
+            "EpochManager",
+            "Timestamp",
+            "ValidatorPerformanceTracker",
+        ],
+    ),
+    ("Delegation", &["StakeConfig", "ValidatorManager"]),
+    ("GravityGovernor", &["GovToken", "Timelock"]),
+    ("JWKManager", &["EpochManager", "HashOracle"]),
+];
+
+/// Indices of `names` ordered so that each comes after its `dependencies` among them
+///
+/// Of the contracts whose dependencies are all placed, the earliest in `names`
+/// goes next, so the order only departs from `names` where a dependency
+/// requires it. Dependencies outside `names` are ignored.
+fn topological_order(
+    names: &[&str],
+    dependencies: &[(&str, &[&str])],
+) -> Result<Vec<usize>, String> {
+    let depends_on = |name: &str| {
+        dependencies
+            .iter()
+            .find(|(contract, _)| *contract == name)
+            .map_or(&[][..], |(_, dependencies)| *dependencies)
+    };
+    let mut remaining: Vec<usize> = (0..names.len()).collect();
+    let mut ordered = Vec::with_capacity(names.len());
+    while !remaining.is_empty() {
+        let Some(next) = remaining.iter().position(|&i| {
+            depends_on(names[i])
+                .iter()
+                .all(|dependency| !remaining.iter().any(|&j| names[j] == *dependency))
+        }) else {
+            let unordered: Vec<&str> = remaining.iter().map(|&i| names[i]).collect();
+            return Err(format!(
+                "contract dependencies form a cycle; cannot order {}",
+                unordered.join(", ")
+            ));
+        };
+        ordered.push(remaining.remove(next));
+    }
+    Ok(ordered)
+}
+
+/// `contracts` in deployment order: each after the [`CONTRACT_DEPENDENCIES`] deployed with it
+///
+/// Fails when the dependencies form a cycle.
+pub fn deployment_order(
+    contracts: &[(String, Address)],
+) -> Result<Vec<&(String, Address)>, String> {
+    let names: Vec<&str> = contracts.iter().map(|(name, _)| name.as_str()).collect();
+    Ok(topological_order(&names, &CONTRACT_DEPENDENCIES)?
+        .into_iter()
+        .map(|i| &contracts[i])
+        .collect())
+}
+
+/// Contract names and the addresses they are deployed at, in deployment order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractRegistry {
+    contracts: Vec<(String, Address)>,
+    system_caller: Address,
+}
+
+impl Default for ContractRegistry {
+    /// The built-in `CONTRACTS` table
+    fn default() -> Self {
+        Self {
+            contracts: CONTRACTS
+                .iter()
+                .map(|(name, address)| (name.to_string(), *address))
+                .collect(),
+            system_caller: SYSTEM_CALLER,
+        }
+    }
+}
+
+impl ContractRegistry {
+    /// Registry holding exactly the contracts in `entries`
+    ///
+    /// Names from the built-in table keep its deployment order, other names
+    /// follow alphabetically. Fails if a required contract is missing.
+    pub fn from_entries(mut entries: BTreeMap<String, Address>) -> Result<Self, String> {
+        let missing: Vec<&str> = REQUIRED_CONTRACTS
+            .into_iter()
+            .filter(|name| !entries.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Contract registry is missing required contract(s): {}",
+                missing.join(", ")
+            ));
+        }
+
+        let mut contracts = Vec::with_capacity(entries.len());
+        for (name, _) in CONTRACTS {
+            if let Some(address) = entries.remove(name) {
+                contracts.push((name.to_string(), address));
+            }
+        }
+        contracts.extend(entries);
+        Ok(Self {
+            contracts,
+            system_caller: SYSTEM_CALLER,
+        })
+    }
+
+    /// Label `system_caller` as the system caller, for runs given another `--system-caller`
+    pub fn with_system_caller(mut self, system_caller: Address) -> Self {
+        self.system_caller = system_caller;
+        self
+    }
+
+    pub fn contracts(&self) -> &[(String, Address)] {
+        &self.contracts
+    }
+
+    /// Address of `name`, `None` if it is not deployed
+    pub fn address_of(&self, name: &str) -> Option<Address> {
+        self.contracts
+            .iter()
+            .find(|(contract_name, _)| contract_name == name)
+            .map(|(_, address)| *address)
+    }
+
+    /// Known dependencies of the `selected` contracts that are not selected, by contract
+    ///
+    /// Dependencies missing from the registry are not reported.
+    pub fn missing_dependencies(
+        &self,
+        selected: &BTreeSet<String>,
+    ) -> Vec<(&'static str, Vec<&'static str>)> {
+        CONTRACT_DEPENDENCIES
+            .iter()
+            .filter(|(contract, _)| selected.contains(*contract))
+            .map(|(contract, dependencies)| {
+                let missing = dependencies
+                    .iter()
+                    .copied()
+                    .filter(|dependency| {
+                        !selected.contains(*dependency) && self.address_of(dependency).is_some()
+                    })
+                    .collect::<Vec<_>>();
+                (*contract, missing)
+            })
+            .filter(|(_, missing)| !missing.is_empty())
+            .collect()
+    }
+
+    /// Name of the registered contract at `address`, or `SystemCaller` for the system caller
+    pub fn label_for(&self, address: Address) -> Option<&str> {
+        if address == self.system_caller {
+            return Some("SystemCaller");
+        }
+        self.contracts
+            .iter()
+            .find(|(_, contract_address)| *contract_address == address)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// `Name (address)` for addresses with a [`label_for`](Self::label_for), the bare address otherwise
+    pub fn display_address(&self, address: Address) -> String {
+        match self.label_for(address) {
+            Some(label) => format!("{} ({})", label, address),
+            None => address.to_string(),
+        }
+    }
+
+    /// Address of a contract every registry holds, see [`REQUIRED_CONTRACTS`]
+    fn required_address(&self, name: &str) -> Address {
+        self.address_of(name)
+            .unwrap_or_else(|| panic!("{} is missing from the contract registry", name))
+    }
+
+    pub fn genesis_addr(&self) -> Address {
+        self.required_address("Genesis")
+    }
+
+    pub fn validator_manager_addr(&self) -> Address {
+        self.required_address("ValidatorManager")
+    }
+
+    pub fn epoch_manager_addr(&self) -> Address {
+        self.required_address("EpochManager")
+    }
+
+    pub fn jwk_manager_addr(&self) -> Address {
+        self.required_address("JWKManager")
+    }
+}
+
+/// Read a registry file of the form `{"Genesis": "0x..", "ValidatorManager": "0x..", ..}`
+///
+/// The file replaces the built-in table: contracts it leaves out are not deployed.
+pub fn read_contract_registry(path: &str) -> Result<ContractRegistry, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read contract registry: {}", e))?;
+    let entries: BTreeMap<String, Address> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse contract registry: {}", e))?;
+    let registry = ContractRegistry::from_entries(entries)?;
+    info!(
+        "Loaded {} contract addresses from {}",
+        registry.contracts.len(),
+        path
+    );
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{GENESIS_ADDR, JWK_MANAGER_ADDR};
+    use revm_primitives::address;
+
+    #[test]
+    fn test_registry_overrides_addresses_and_requires_core_contracts() {
+        let relocated = address!("0000000000000000000000000000000000003008");
+        let extra = address!("0000000000000000000000000000000000003100");
+        let entries: BTreeMap<String, Address> = serde_json::from_value(serde_json::json!({
+            "Bridge": extra,
+            "JWKManager": JWK_MANAGER_ADDR,
+            "EpochManager": "0x0000000000000000000000000000000000003010",
+            "ValidatorManager": "0x0000000000000000000000000000000000003013",
+            "Genesis": relocated,
+        }))
+        .unwrap();
+        let registry = ContractRegistry::from_entries(entries.clone()).unwrap();
+
+        assert_eq!(registry.address_of("Genesis"), Some(relocated));
+        assert_eq!(registry.address_of("Block"), None);
+        // Built-in deployment order first, unknown contracts last
+        let names: Vec<&str> = registry
+            .contracts()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "ValidatorManager",
+                "EpochManager",
+                "JWKManager",
+                "Genesis",
+                "Bridge"
+            ]
+        );
+
+        let mut incomplete = entries;
+        incomplete.remove("Genesis");
+        incomplete.remove("JWKManager");
+        assert_eq!(
+            ContractRegistry::from_entries(incomplete).unwrap_err(),
+            "Contract registry is missing required contract(s): Genesis, JWKManager"
+        );
+
+        assert_eq!(
+            ContractRegistry::default().address_of("Genesis"),
+            Some(GENESIS_ADDR)
+        );
+    }
+
+    #[test]
+    fn test_contracts_are_deployed_after_their_dependencies() {
+        let registry = ContractRegistry::default();
+        let order: Vec<&str> = deployment_order(registry.contracts())
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(order.len(), registry.contracts().len());
+        assert_eq!(order[0], "System");
+        for (contract, dependencies) in CONTRACT_DEPENDENCIES {
+            let position = order.iter().position(|name| *name == contract).unwrap();
+            for dependency in dependencies {
+                let dependency_position = order.iter().position(|name| name == dependency).unwrap();
+                assert!(
+                    dependency_position < position,
+                    "{} deployed before its dependency {}",
+                    contract,
+                    dependency
+                );
+            }
+        }
+
+        let names = ["A", "B", "C", "D"];
+        let dependencies: [(&str, &[&str]); 2] = [("A", &["C"]), ("C", &["Unknown"])];
+        assert_eq!(
+            topological_order(&names, &dependencies).unwrap(),
+            vec![1, 2, 0, 3]
+        );
+        let cycle: [(&str, &[&str]); 3] = [("A", &["C"]), ("C", &["D"]), ("D", &["A"])];
+        assert_eq!(
+            topological_order(&names, &cycle).unwrap_err(),
+            "contract dependencies form a cycle; cannot order A, C, D"
+        );
+    }
+
+    #[test]
+    fn test_addresses_are_labelled() {
+        let registry = ContractRegistry::default();
+        let (name, address) = &registry.contracts()[0];
+        assert_eq!(registry.label_for(*address), Some(name.as_str()));
+        assert_eq!(
+            registry.display_address(*address),
+            format!("{} ({})", name, address)
+        );
+        assert_eq!(registry.label_for(SYSTEM_CALLER), Some("SystemCaller"));
+        let unknown = Address::repeat_byte(0xab);
+        assert_eq!(registry.label_for(unknown), None);
+        assert_eq!(registry.display_address(unknown), unknown.to_string());
+
+        let registry = registry.with_system_caller(unknown);
+        assert_eq!(registry.label_for(unknown), Some("SystemCaller"));
+        assert_eq!(registry.label_for(SYSTEM_CALLER), None);
+    }
+
+    #[test]
+    fn test_missing_dependencies_of_a_selection() {
+        let selected: BTreeSet<String> = ["GravityGovernor", "GovToken", "Delegation"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let registry = ContractRegistry::default();
+        assert_eq!(
+            registry.missing_dependencies(&selected),
+            vec![
+                ("Delegation", vec!["StakeConfig", "ValidatorManager"]),
+                ("GravityGovernor", vec!["Timelock"]),
+            ]
+        );
+        let all: BTreeSet<String> = registry
+            .contracts()
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+        assert!(registry.missing_dependencies(&all).is_empty());
+    }
+}
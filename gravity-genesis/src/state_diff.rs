@@ -0,0 +1,390 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    io::Write,
+};
+
+use revm::{db::PlainAccount, primitives::AccountInfo};
+use revm_primitives::{Address, B256, KECCAK_EMPTY, U256, keccak256};
+use serde::Serialize;
+
+use crate::{
+    accounts_writer::write_genesis_accounts, registry::ContractRegistry,
+    state_root::compute_state_root,
+};
+
+/// Accounts of a `genesis_accounts.json`, sorted by address
+pub type GenesisAccounts = BTreeMap<Address, PlainAccount>;
+
+/// Load a `genesis_accounts.json` written by genesis generation
+pub fn read_genesis_accounts(path: &str) -> Result<GenesisAccounts, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+/// Value of a field before and after
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn of(before: T, after: T) -> Option<Self> {
+        (before != after).then_some(Self { before, after })
+    }
+}
+
+/// Change of one storage slot; a slot missing on one side is `None` there
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SlotChange {
+    pub slot: U256,
+    pub before: Option<U256>,
+    pub after: Option<U256>,
+}
+
+/// Changes of an account present in both states
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccountDiff {
+    pub address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<Change<U256>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Change<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<Change<B256>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub storage: Vec<SlotChange>,
+}
+
+/// Differences between two genesis states
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GenesisDiff {
+    pub added: Vec<Address>,
+    pub removed: Vec<Address>,
+    pub changed: Vec<AccountDiff>,
+    /// How the registered accounts above are printed, see [`ContractRegistry::display_address`]
+    #[serde(skip)]
+    pub labels: BTreeMap<Address, String>,
+}
+
+impl GenesisDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// `Name (address)` for labelled accounts, the bare address otherwise
+    fn describe(&self, address: &Address) -> String {
+        self.labels
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| address.to_string())
+    }
+}
+
+/// keccak256 of the account's code, recomputed so a stale `code_hash` field is not trusted
+fn code_hash(info: &AccountInfo) -> B256 {
+    match &info.code {
+        Some(code) if !code.is_empty() => keccak256(code.original_bytes()),
+        _ => KECCAK_EMPTY,
+    }
+}
+
+fn diff_account(address: Address, before: &PlainAccount, after: &PlainAccount) -> AccountDiff {
+    let slots: BTreeSet<&U256> = before.storage.keys().chain(after.storage.keys()).collect();
+    let storage = slots
+        .into_iter()
+        .filter_map(|slot| {
+            let before = before.storage.get(slot).copied();
+            let after = after.storage.get(slot).copied();
+            (before != after).then_some(SlotChange {
+                slot: *slot,
+                before,
+                after,
+            })
+        })
+        .collect();
+    AccountDiff {
+        address,
+        balance: Change::of(before.info.balance, after.info.balance),
+        nonce: Change::of(before.info.nonce, after.info.nonce),
+        code_hash: Change::of(code_hash(&before.info), code_hash(&after.info)),
+        storage,
+    }
+}
+
+/// Compare two genesis states account by account
+///
+/// Accounts of contracts in `registry` are printed with their name.
+pub fn diff_genesis_accounts(
+    before: &GenesisAccounts,
+    after: &GenesisAccounts,
+    registry: &ContractRegistry,
+) -> GenesisDiff {
+    let mut diff = GenesisDiff::default();
+    for (address, before_account) in before {
+        match after.get(address) {
+            Some(after_account) => {
+                let account_diff = diff_account(*address, before_account, after_account);
+                if account_diff.balance.is_some()
+                    || account_diff.nonce.is_some()
+                    || account_diff.code_hash.is_some()
+                    || !account_diff.storage.is_empty()
+                {
+                    diff.changed.push(account_diff);
+                }
+            }
+            None => diff.removed.push(*address),
+        }
+    }
+    diff.added = after
+        .keys()
+        .filter(|address| !before.contains_key(address))
+        .copied()
+        .collect();
+    diff.labels = diff
+        .added
+        .iter()
+        .chain(&diff.removed)
+        .chain(diff.changed.iter().map(|account| &account.address))
+        .filter(|address| registry.label_for(**address).is_some())
+        .map(|address| (*address, registry.display_address(*address)))
+        .collect();
+    diff
+}
+
+fn describe_slot_value(value: Option<U256>) -> String {
+    match value {
+        Some(value) => format!("{:#x}", value),
+        None => "unset".to_string(),
+    }
+}
+
+impl fmt::Display for GenesisDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No differences");
+        }
+        writeln!(
+            f,
+            "{} added, {} removed, {} changed accounts",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )?;
+        for address in &self.added {
+            writeln!(f, "+ {}", self.describe(address))?;
+        }
+        for address in &self.removed {
+            writeln!(f, "- {}", self.describe(address))?;
+        }
+        for account in &self.changed {
+            writeln!(f, "~ {}", self.describe(&account.address))?;
+            if let Some(balance) = &account.balance {
+                writeln!(f, "    balance: {} -> {}", balance.before, balance.after)?;
+            }
+            if let Some(nonce) = &account.nonce {
+                writeln!(f, "    nonce: {} -> {}", nonce.before, nonce.after)?;
+            }
+            if let Some(code_hash) = &account.code_hash {
+                writeln!(
+                    f,
+                    "    code hash: {} -> {}",
+                    code_hash.before, code_hash.after
+                )?;
+            }
+            for slot in &account.storage {
+                writeln!(
+                    f,
+                    "    slot {:#x}: {} -> {}",
+                    slot.slot,
+                    describe_slot_value(slot.before),
+                    describe_slot_value(slot.after)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How a generated genesis differs from its golden `genesis_accounts.json`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenMismatch {
+    pub golden_state_root: B256,
+    pub state_root: B256,
+    /// From the golden state to the generated one
+    pub diff: GenesisDiff,
+}
+
+impl fmt::Display for GoldenMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "state root {} differs from the golden {}",
+            self.state_root, self.golden_state_root
+        )?;
+        write!(f, "{}", self.diff)
+    }
+}
+
+/// Compare a generated genesis state with the golden `genesis_accounts.json` at `path`
+///
+/// Returns `None` when they are identical.
+pub fn check_golden(
+    path: &str,
+    genesis_state: &HashMap<Address, PlainAccount>,
+    registry: &ContractRegistry,
+) -> Result<Option<GoldenMismatch>, String> {
+    let golden = read_genesis_accounts(path)?;
+    let generated: GenesisAccounts = genesis_state
+        .iter()
+        .map(|(address, account)| (*address, account.clone()))
+        .collect();
+    let diff = diff_genesis_accounts(&golden, &generated, registry);
+    if diff.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(GoldenMismatch {
+        golden_state_root: compute_state_root(&golden.into_iter().collect()),
+        state_root: compute_state_root(genesis_state),
+        diff,
+    }))
+}
+
+/// Replace the golden `genesis_accounts.json` at `path` with a generated genesis state
+pub fn write_golden(
+    path: &str,
+    genesis_state: &HashMap<Address, PlainAccount>,
+) -> Result<(), String> {
+    let file =
+        std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut writer = std::io::BufWriter::new(file);
+    write_genesis_accounts(&mut writer, genesis_state)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use revm::primitives::{Bytecode, Bytes};
+
+    use super::*;
+
+    fn account(balance: u64, code: &[u8], storage: &[(u64, u64)]) -> PlainAccount {
+        let code = (!code.is_empty()).then(|| Bytecode::new_raw(Bytes::copy_from_slice(code)));
+        PlainAccount {
+            info: AccountInfo {
+                balance: U256::from(balance),
+                ..code.map(AccountInfo::from_bytecode).unwrap_or_default()
+            },
+            storage: storage
+                .iter()
+                .map(|(slot, value)| (U256::from(*slot), U256::from(*value)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_account_and_slot_changes() {
+        let kept = Address::with_last_byte(1);
+        let removed = Address::with_last_byte(2);
+        let added = Address::with_last_byte(3);
+        let unchanged = Address::with_last_byte(4);
+        let before = GenesisAccounts::from([
+            (kept, account(1, &[0x60, 0x00], &[(0, 1), (1, 2)])),
+            (removed, account(5, &[], &[])),
+            (unchanged, account(7, &[0x60], &[(0, 1)])),
+        ]);
+        let after = GenesisAccounts::from([
+            (kept, account(2, &[0x60, 0x01], &[(1, 3), (2, 4)])),
+            (added, account(6, &[], &[])),
+            (unchanged, account(7, &[0x60], &[(0, 1)])),
+        ]);
+
+        let registry = ContractRegistry::default().with_system_caller(added);
+        let diff = diff_genesis_accounts(&before, &after, &registry);
+        assert_eq!(diff.added, vec![added]);
+        assert_eq!(diff.removed, vec![removed]);
+        assert_eq!(diff.changed.len(), 1);
+        let changed = &diff.changed[0];
+        assert_eq!(changed.address, kept);
+        assert_eq!(
+            changed.balance,
+            Some(Change {
+                before: U256::from(1),
+                after: U256::from(2)
+            })
+        );
+        assert_eq!(changed.nonce, None);
+        assert_eq!(
+            changed.code_hash,
+            Some(Change {
+                before: keccak256([0x60, 0x00]),
+                after: keccak256([0x60, 0x01])
+            })
+        );
+        assert_eq!(
+            changed.storage,
+            vec![
+                SlotChange {
+                    slot: U256::from(0),
+                    before: Some(U256::from(1)),
+                    after: None
+                },
+                SlotChange {
+                    slot: U256::from(1),
+                    before: Some(U256::from(2)),
+                    after: Some(U256::from(3))
+                },
+                SlotChange {
+                    slot: U256::from(2),
+                    before: None,
+                    after: Some(U256::from(4))
+                },
+            ]
+        );
+
+        let text = diff.to_string();
+        assert!(text.starts_with("1 added, 1 removed, 1 changed accounts\n"));
+        assert!(text.contains("    slot 0x0: 0x1 -> unset\n"));
+        assert!(
+            text.contains(&format!("+ SystemCaller ({})\n", added)),
+            "{}",
+            text
+        );
+        assert!(text.contains(&format!("- {}\n", removed)), "{}", text);
+        assert!(diff_genesis_accounts(&after, &after, &ContractRegistry::default()).is_empty());
+        assert_eq!(
+            diff_genesis_accounts(&after, &after, &ContractRegistry::default()).to_string(),
+            "No differences"
+        );
+    }
+
+    #[test]
+    fn test_golden_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("genesis_accounts.json");
+        let path = path.to_str().unwrap();
+        let mut genesis_state = HashMap::from([
+            (Address::with_last_byte(1), account(1, &[0x60], &[(0, 1)])),
+            (Address::with_last_byte(2), account(2, &[], &[])),
+        ]);
+
+        write_golden(path, &genesis_state).unwrap();
+        assert_eq!(
+            check_golden(path, &genesis_state, &ContractRegistry::default()).unwrap(),
+            None
+        );
+
+        genesis_state.insert(Address::with_last_byte(3), account(3, &[], &[]));
+        let mismatch = check_golden(path, &genesis_state, &ContractRegistry::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(mismatch.diff.added, vec![Address::with_last_byte(3)]);
+        assert_eq!(mismatch.state_root, compute_state_root(&genesis_state));
+        assert_ne!(mismatch.golden_state_root, mismatch.state_root);
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use alloy_rlp::Encodable;
+use alloy_trie::{EMPTY_ROOT_HASH, HashBuilder, Nibbles};
+use revm::db::PlainAccount;
+use revm_primitives::{Address, B256, U256, keccak256};
+
+/// Root of a trie whose leaves are keyed by `keccak256(key)`
+fn secure_trie_root(leaves: impl IntoIterator<Item = (B256, Vec<u8>)>) -> B256 {
+    let mut leaves: Vec<_> = leaves.into_iter().collect();
+    if leaves.is_empty() {
+        return EMPTY_ROOT_HASH;
+    }
+    leaves.sort_unstable_by_key(|(hashed_key, _)| *hashed_key);
+    let mut builder = HashBuilder::default();
+    for (hashed_key, value) in leaves {
+        builder.add_leaf(Nibbles::unpack(hashed_key), &value);
+    }
+    builder.root()
+}
+
+fn storage_root<'a>(storage: impl IntoIterator<Item = (&'a U256, &'a U256)>) -> B256 {
+    secure_trie_root(
+        storage
+            .into_iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(slot, value)| {
+                (
+                    keccak256(slot.to_be_bytes::<32>()),
+                    alloy_rlp::encode(value),
+                )
+            }),
+    )
+}
+
+/// RLP of the `[nonce, balance, storageRoot, codeHash]` account leaf
+fn encode_account(account: &PlainAccount) -> Vec<u8> {
+    let info = &account.info;
+    // Contracts deployed straight into the genesis state may carry code
+    // without having had their code hash filled in
+    let code_hash = info
+        .code
+        .as_ref()
+        .map(|code| code.hash_slow())
+        .unwrap_or(info.code_hash);
+    let storage_root = storage_root(&account.storage);
+
+    let payload_length =
+        info.nonce.length() + info.balance.length() + storage_root.length() + code_hash.length();
+    let mut out = Vec::with_capacity(payload_length + 3);
+    alloy_rlp::Header {
+        list: true,
+        payload_length,
+    }
+    .encode(&mut out);
+    info.nonce.encode(&mut out);
+    info.balance.encode(&mut out);
+    storage_root.encode(&mut out);
+    code_hash.encode(&mut out);
+    out
+}
+
+/// Ethereum state root of the genesis accounts
+pub fn compute_state_root(accounts: &HashMap<Address, PlainAccount>) -> B256 {
+    secure_trie_root(
+        accounts
+            .iter()
+            .map(|(address, account)| (keccak256(address), encode_account(account))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::{AccountInfo, Bytecode, Bytes, address, b256};
+
+    #[test]
+    fn test_state_root() {
+        assert_eq!(
+            compute_state_root(&HashMap::new()),
+            b256!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+        );
+
+        let contract = address!("0000000000000000000000000000000000002008");
+        let account = |storage: HashMap<U256, U256>| PlainAccount {
+            info: AccountInfo {
+                code: Some(Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]))),
+                ..AccountInfo::default()
+            },
+            storage: storage.into_iter().collect(),
+        };
+        let root = compute_state_root(&HashMap::from([(contract, account(HashMap::new()))]));
+        assert_eq!(
+            root,
+            b256!("2bb9f1e8eb2847194f0524ef4c7f9bc3cd8ee05c856d1fed20583947cf844798")
+        );
+
+        // Zero-valued slots are not part of the storage trie
+        let zero_slot = HashMap::from([(U256::from(1), U256::ZERO)]);
+        assert_eq!(
+            compute_state_root(&HashMap::from([(contract, account(zero_slot))])),
+            root
+        );
+        let set_slot = HashMap::from([(U256::from(1), U256::from(7))]);
+        assert_eq!(
+            compute_state_root(&HashMap::from([(contract, account(set_slot))])),
+            b256!("e1338472e31b63d291e9420d6e23f97c4aac1d9644af2d79de8d2cac2ed3102f")
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use revm::db::PlainAccount;
+use revm_primitives::{Address, U256};
+use serde::Serialize;
+
+use crate::{
+    post_genesis::{VerificationReport, VerifyMode},
+    registry::ContractRegistry,
+};
+
+/// Number of largest balances listed in `supply_report.json` by default
+pub const DEFAULT_SUPPLY_REPORT_TOP: usize = 10;
+
+/// Balance of one genesis account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SupplyReportEntry {
+    pub address: Address,
+    /// Registered contract at the address, `None` for other accounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    pub balance: U256,
+}
+
+/// Wei allocated at genesis, written as `supply_report.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SupplyReport {
+    /// Sum of every account balance
+    pub total_supply: U256,
+    /// Number of accounts with a non-zero balance
+    pub funded_accounts: usize,
+    /// The largest balances, largest first
+    pub top_balances: Vec<SupplyReportEntry>,
+}
+
+impl SupplyReport {
+    /// Sum the balances of `accounts` and list the `top` largest, naming the contracts of `registry`
+    ///
+    /// Equal balances are listed by address, so the report does not depend on
+    /// the order of `accounts`.
+    pub fn new<'a>(
+        accounts: impl IntoIterator<Item = (&'a Address, &'a PlainAccount)>,
+        top: usize,
+        registry: &ContractRegistry,
+    ) -> Self {
+        let mut balances: Vec<(Address, U256)> = accounts
+            .into_iter()
+            .map(|(address, account)| (*address, account.info.balance))
+            .filter(|(_, balance)| !balance.is_zero())
+            .collect();
+        let total_supply = balances.iter().fold(U256::ZERO, |total, (_, balance)| {
+            total.saturating_add(*balance)
+        });
+        let funded_accounts = balances.len();
+        balances.sort_by(|(a, a_balance), (b, b_balance)| b_balance.cmp(a_balance).then(a.cmp(b)));
+        let top_balances = balances
+            .into_iter()
+            .take(top)
+            .map(|(address, balance)| SupplyReportEntry {
+                address,
+                contract: registry.label_for(address).map(str::to_string),
+                balance,
+            })
+            .collect();
+        Self {
+            total_supply,
+            funded_accounts,
+            top_balances,
+        }
+    }
+}
+
+/// Check the total supply of a genesis against the one the operator expects
+pub fn verify_total_supply(
+    report: &SupplyReport,
+    expected: U256,
+    mode: VerifyMode,
+) -> VerificationReport {
+    let mut verification = VerificationReport::new(mode);
+    verification.check_eq("total supply (wei)", expected, report.total_supply);
+    verification
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::STAKE_CONFIG_ADDR;
+    use revm::primitives::AccountInfo;
+    use std::collections::HashMap;
+
+    fn funded(balance: u64) -> PlainAccount {
+        PlainAccount {
+            info: AccountInfo {
+                balance: U256::from(balance),
+                ..AccountInfo::default()
+            },
+            storage: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_supply_report_totals_and_ranks_balances() {
+        let genesis_state = HashMap::from([
+            (Address::with_last_byte(1), funded(5)),
+            (Address::with_last_byte(2), funded(0)),
+            (Address::with_last_byte(3), funded(7)),
+            (STAKE_CONFIG_ADDR, funded(7)),
+            (Address::with_last_byte(4), funded(1)),
+        ]);
+
+        let report = SupplyReport::new(&genesis_state, 3, &ContractRegistry::default());
+        assert_eq!(report.total_supply, U256::from(20));
+        assert_eq!(report.funded_accounts, 4);
+        let top: Vec<(Address, u64)> = report
+            .top_balances
+            .iter()
+            .map(|entry| (entry.address, entry.balance.to::<u64>()))
+            .collect();
+        let mut tied = [Address::with_last_byte(3), STAKE_CONFIG_ADDR];
+        tied.sort();
+        assert_eq!(
+            top,
+            vec![(tied[0], 7), (tied[1], 7), (Address::with_last_byte(1), 5)]
+        );
+        let stake_config = report
+            .top_balances
+            .iter()
+            .find(|entry| entry.address == STAKE_CONFIG_ADDR)
+            .unwrap();
+        assert_eq!(stake_config.contract.as_deref(), Some("StakeConfig"));
+
+        assert!(verify_total_supply(&report, U256::from(20), VerifyMode::CollectAll).passed());
+        let mismatch = verify_total_supply(&report, U256::from(21), VerifyMode::CollectAll);
+        assert_eq!(mismatch.failures().count(), 1);
+    }
+}
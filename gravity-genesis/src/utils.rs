@@ -1,16 +1,28 @@
 use alloy_primitives::address;
 
 use alloy_sol_macro::sol;
-use alloy_sol_types::SolEvent;
+use alloy_sol_types::{Panic, Revert, SolError, SolEvent, SolInterface};
 use revm::{
-    DatabaseCommit, DatabaseRef, EvmBuilder, StateBuilder,
+    Database, DatabaseCommit, DatabaseRef, EvmBuilder, EvmContext, Inspector, StateBuilder,
     db::{BundleState, states::bundle_state::BundleRetention},
-    primitives::{Address, EVMError, Env, ExecutionResult, SpecId, TxEnv, U256},
+    inspector_handle_register,
+    interpreter::{InstructionResult, Interpreter},
+    primitives::{
+        Address, EVMError, Env, ExecutionResult, HaltReason, OutOfGasError, SpecId, TxEnv, U256,
+    },
 };
 use revm_primitives::{AccountInfo, Bytes, KECCAK_EMPTY, TxKind, hex, uint};
-use std::u64;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+    u64,
+};
 use tracing::info;
 
+use crate::{
+    context::RunContext, error_signatures::lookup_error_signature, registry::ContractRegistry,
+};
+
 pub const DEAD_ADDRESS: Address = address!("000000000000000000000000000000000000dEaD");
 pub const GENESIS_ADDR: Address = address!("0000000000000000000000000000000000002008");
 pub const SYSTEM_CONTRACT_ADDRESS: Address = address!("00000000000000000000000000000000000020FF");
@@ -39,6 +51,7 @@ pub const RECONFIGURATION_WITH_DKG_ADDR: Address = address!("0000000000000000000
 pub const HASH_ORACLE_ADDR: Address = address!("0000000000000000000000000000000000002023");
 
 // this address is used to call evm. It's not used for gravity pre compile contract
+// Default for `--system-caller`; forks with another privileged caller pass their own
 pub const SYSTEM_CALLER: Address = address!("0000000000000000000000000000000000002000");
 
 pub const CONTRACTS: [(&str, Address); 22] = [
@@ -69,19 +82,164 @@ pub const CONTRACTS: [(&str, Address); 22] = [
     ("HashOracle", HASH_ORACLE_ADDR),
 ];
 
-pub const SYSTEM_ACCOUNT_INFO: AccountInfo = AccountInfo {
-    balance: uint!(1_000_000_000_000_000_000_U256),
-    nonce: 1,
-    code_hash: KECCAK_EMPTY,
-    code: None,
-};
+/// Default nonce of deployed contract accounts
+///
+/// EIP-161 (Spurious Dragon) starts contract accounts at nonce 1. Nodes and
+/// tooling expect the same of accounts whose code is placed directly in the
+/// genesis, and an account with nonce 0 and no code is considered empty.
+pub const DEFAULT_CONTRACT_NONCE: u64 = 1;
+
+/// Default balance of the system caller, 1 ether
+pub const DEFAULT_SYSTEM_CALLER_BALANCE: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+pub const SYSTEM_ACCOUNT_INFO: AccountInfo = system_account_info(DEFAULT_SYSTEM_CALLER_BALANCE);
+
+/// Account the system caller is given before the genesis transactions run
+pub const fn system_account_info(balance: U256) -> AccountInfo {
+    AccountInfo {
+        balance,
+        nonce: 1,
+        code_hash: KECCAK_EMPTY,
+        code: None,
+    }
+}
 
 sol! {
-    // event Log(string message);
-    event Log(string message, uint256 value);
+    /// Debug events contracts emit, overloaded as `Log_0` and `Log_1`
+    interface IDebugLog {
+        event Log(string message);
+        event Log(string message, uint256 value);
+    }
+}
+
+/// Render a `Log(string)` or `Log(string, uint256)` debug event, `None` for any other log
+pub fn decode_debug_log(log: &alloy_primitives::Log) -> Option<String> {
+    if let Ok(event) = IDebugLog::Log_1::decode_log(log, true) {
+        return Some(format!(
+            "Log(message: {:?}, value: {})",
+            event.message, event.value
+        ));
+    }
+    IDebugLog::Log_0::decode_log(log, true)
+        .ok()
+        .map(|event| format!("Log(message: {:?})", event.message))
+}
+
+sol! {
+    /// Custom errors declared by `System.sol`
+    interface ISystem {
+        error OnlySystemCaller(address errorAddress);
+        error UnknownParam(string key, bytes value);
+        error InvalidValue(string key, bytes value);
+        error OnlyCoinbase();
+        error OnlyZeroGasPrice();
+        error OnlySystemContract(address systemContract);
+    }
+}
+
+/// Decode revert output as a `System` custom error, `Error(string)` or `Panic(uint256)`
+///
+/// Other custom errors are resolved to their signature through the selector
+/// table loaded from contract ABIs, if one was registered.
+pub fn decode_revert_output(output: &[u8], context: &RunContext) -> Option<String> {
+    if let Ok(error) = ISystem::ISystemErrors::abi_decode(output, true) {
+        let decoded = match error {
+            ISystem::ISystemErrors::OnlySystemCaller(e) => format!(
+                "OnlySystemCaller(errorAddress: {})",
+                context.registry.display_address(e.errorAddress)
+            ),
+            ISystem::ISystemErrors::UnknownParam(e) => {
+                format!("UnknownParam(key: {:?}, value: {})", e.key, e.value)
+            }
+            ISystem::ISystemErrors::InvalidValue(e) => {
+                format!("InvalidValue(key: {:?}, value: {})", e.key, e.value)
+            }
+            ISystem::ISystemErrors::OnlyCoinbase(_) => "OnlyCoinbase()".to_string(),
+            ISystem::ISystemErrors::OnlyZeroGasPrice(_) => "OnlyZeroGasPrice()".to_string(),
+            ISystem::ISystemErrors::OnlySystemContract(e) => format!(
+                "OnlySystemContract(systemContract: {})",
+                context.registry.display_address(e.systemContract)
+            ),
+        };
+        return Some(decoded);
+    }
+    if let Some(signature) = output
+        .get(0..4)
+        .and_then(|selector| lookup_error_signature(&context.error_signatures, selector))
+    {
+        return Some(format!(
+            "{} with arguments 0x{}",
+            signature,
+            hex::encode(&output[4..])
+        ));
+    }
+    if let Ok(revert) = Revert::abi_decode(output, true) {
+        return Some(format!("Error({:?})", revert.reason));
+    }
+    if let Ok(panic) = Panic::abi_decode(output, true) {
+        return Some(format!("Panic(0x{:x}): {}", panic.code, panic));
+    }
+    None
 }
 
-pub fn analyze_txn_result(result: &ExecutionResult) -> String {
+/// What a halt means and what most likely caused it during genesis
+///
+/// Genesis runs trusted bytecode that compiled fine, so the likely causes
+/// point at the inputs: gas limits, the bytecode files and the config.
+pub fn explain_halt(reason: &HaltReason) -> &'static str {
+    match reason {
+        HaltReason::OutOfGas(OutOfGasError::Basic) => {
+            "contract ran out of gas; increase the gas limit or check for an unbounded loop in initialize"
+        }
+        HaltReason::OutOfGas(OutOfGasError::Memory | OutOfGasError::MemoryLimit) => {
+            "memory expansion ran out of gas; a config array or string is likely far larger than intended"
+        }
+        HaltReason::OutOfGas(OutOfGasError::Precompile) => {
+            "a precompile ran out of gas; increase the gas limit"
+        }
+        HaltReason::OutOfGas(_) => "contract ran out of gas; increase the gas limit",
+        HaltReason::OpcodeNotFound | HaltReason::InvalidFEOpcode => {
+            "contract hit an undefined or INVALID (0xfe) opcode; the bytecode file is likely truncated, not runtime code, or built for a newer EVM version"
+        }
+        HaltReason::InvalidJump => {
+            "contract jumped to an invalid destination; the bytecode file is likely corrupted or has unlinked libraries"
+        }
+        HaltReason::NotActivated => {
+            "contract used an opcode not active in this hardfork; the contracts were built for a newer EVM version"
+        }
+        HaltReason::StackUnderflow | HaltReason::StackOverflow => {
+            "the EVM stack under- or overflowed; the bytecode file is likely corrupted"
+        }
+        HaltReason::OutOfOffset => "contract read return data out of bounds",
+        HaltReason::CreateCollision => {
+            "contract creation targeted an address that already has code or a nonce"
+        }
+        HaltReason::PrecompileError => "a precompile call failed on its input",
+        HaltReason::NonceOverflow => "the sender's nonce overflowed",
+        HaltReason::CreateContractSizeLimit => {
+            "created contract exceeds the code size limit; raise --max-code-size or split the contract"
+        }
+        HaltReason::CreateContractStartingWithEF => {
+            "created contract code starts with 0xEF, which is reserved"
+        }
+        HaltReason::CreateInitCodeSizeLimit => {
+            "contract creation code exceeds the init code size limit"
+        }
+        HaltReason::OverflowPayment => "the transaction's gas payment overflowed",
+        HaltReason::StateChangeDuringStaticCall | HaltReason::CallNotAllowedInsideStatic => {
+            "contract tried to change state inside a static call; a function called as a view writes state"
+        }
+        HaltReason::OutOfFunds => {
+            "a call sent more value than its sender holds; check the system caller and contract balances"
+        }
+        HaltReason::CallTooDeep => {
+            "the call stack exceeded 1024 frames; check for unbounded recursion between contracts"
+        }
+        _ => "the EVM halted execution",
+    }
+}
+
+pub fn analyze_txn_result(result: &ExecutionResult, context: &RunContext) -> String {
     match result {
         ExecutionResult::Revert { gas_used, output } => {
             let mut reason = format!("Revert with gas used: {}", gas_used);
@@ -89,45 +247,92 @@ pub fn analyze_txn_result(result: &ExecutionResult) -> String {
             if let Some(selector) = output.get(0..4) {
                 reason.push_str(&format!("\nFunction selector: 0x{}", hex::encode(selector)));
 
-                match selector {
-                    [0x49, 0xfd, 0x36, 0xf2] => reason.push_str(" (OnlySystemCaller)"),
-                    [0x97, 0xb8, 0x83, 0x54] => reason.push_str(" (UnknownParam)"),
-                    [0x0a, 0x5a, 0x60, 0x41] => reason.push_str(" (InvalidValue)"),
-                    [0x11, 0x6c, 0x64, 0xa8] => reason.push_str(" (OnlyCoinbase)"),
-                    [0x83, 0xf1, 0xb1, 0xd3] => reason.push_str(" (OnlyZeroGasPrice)"),
-                    [0xf2, 0x2c, 0x43, 0x90] => reason.push_str(" (OnlySystemContract)"),
-                    _ => reason.push_str(" (Unknown error selector)"),
+                match decode_revert_output(output, context) {
+                    Some(decoded) => reason.push_str(&format!("\nDecoded error: {}", decoded)),
+                    None => {
+                        reason.push_str(" (Unknown error selector)");
+                        if output.len() > 4 {
+                            reason.push_str(&format!(
+                                "\nAdditional data: 0x{}",
+                                hex::encode(&output[4..])
+                            ));
+                        }
+                    }
                 }
             }
 
-            if output.len() > 4 {
-                reason.push_str(&format!(
-                    "\nAdditional data: 0x{}",
-                    hex::encode(&output[4..])
-                ));
-            }
-
             reason
         }
         ExecutionResult::Success { gas_used, logs, .. } => {
             let mut log_msg = String::new();
             for log in logs {
-                if let Ok(parsed) = Log::decode_log(log, true) {
+                if let Some(decoded) = decode_debug_log(log) {
                     log_msg.push_str(&format!(
-                        "txn event Log: {:?}, {:?}.",
-                        parsed.message, parsed.value
+                        "txn event {} from {}.",
+                        decoded,
+                        context.registry.display_address(log.address)
                     ));
                 }
             }
             format!("Success with gas used: {}, {}", gas_used, log_msg)
         }
         ExecutionResult::Halt { reason, gas_used } => {
-            format!("Halt: {:?} with gas used: {}", reason, gas_used)
+            format!(
+                "Halt: {:?} with gas used: {}\nExplanation: {}",
+                reason,
+                gas_used,
+                explain_halt(reason)
+            )
+        }
+    }
+}
+
+/// Wall-clock time one transaction may run before it is aborted
+pub const DEFAULT_TX_TIME_BUDGET: Duration = Duration::from_secs(120);
+
+/// Opcodes executed between two looks at the clock
+const TIME_BUDGET_CHECK_INTERVAL: u32 = 4096;
+
+/// Inspector halting the running transaction once it has used up its time budget
+///
+/// Transactions run with effectively unlimited gas, so an unbounded loop in a
+/// contract would otherwise never return. Once the budget is spent every frame
+/// is halted as out of gas, unwinding the call stack.
+struct TimeBudget {
+    budget: Duration,
+    started: Instant,
+    steps: u32,
+    exceeded: bool,
+}
+
+impl TimeBudget {
+    fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            started: Instant::now(),
+            steps: 0,
+            exceeded: false,
         }
     }
+
+    fn restart(&mut self) {
+        self.started = Instant::now();
+        self.steps = 0;
+        self.exceeded = false;
+    }
 }
 
-pub const MINER_ADDRESS: usize = 999;
+impl<DB: Database> Inspector<DB> for TimeBudget {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.steps = self.steps.wrapping_add(1);
+        if self.steps % TIME_BUDGET_CHECK_INTERVAL == 0 && self.started.elapsed() > self.budget {
+            self.exceeded = true;
+        }
+        if self.exceeded {
+            interp.instruction_result = InstructionResult::OutOfGas;
+        }
+    }
+}
 
 /// Simulate the sequential execution of transactions with detailed logging
 pub(crate) fn execute_revm_sequential<DB>(
@@ -136,13 +341,58 @@ pub(crate) fn execute_revm_sequential<DB>(
     env: Env,
     txs: &[TxEnv],
     pre_bundle: Option<BundleState>,
+    context: &RunContext,
 ) -> Result<(Vec<ExecutionResult>, BundleState), EVMError<DB::Error>>
+where
+    DB: DatabaseRef,
+{
+    execute_revm_sequential_timed(
+        db,
+        spec_id,
+        env,
+        txs,
+        pre_bundle,
+        DEFAULT_TX_TIME_BUDGET,
+        context,
+    )
+}
+
+/// [`execute_revm_sequential`], aborting a transaction that runs longer than `time_budget`
+///
+/// The abort is an [`EVMError::Custom`] naming the 1-based transaction.
+pub(crate) fn execute_revm_sequential_timed<DB>(
+    db: DB,
+    spec_id: SpecId,
+    env: Env,
+    txs: &[TxEnv],
+    pre_bundle: Option<BundleState>,
+    time_budget: Duration,
+    context: &RunContext,
+) -> Result<(Vec<ExecutionResult>, BundleState), EVMError<DB::Error>>
+where
+    DB: DatabaseRef,
+{
+    execute_sequential_indexed(db, spec_id, env, txs, pre_bundle, time_budget, context)
+        .map_err(|(_, e)| e)
+}
+
+/// [`execute_revm_sequential_timed`], with the index of the transaction an error stopped at
+fn execute_sequential_indexed<DB>(
+    db: DB,
+    spec_id: SpecId,
+    env: Env,
+    txs: &[TxEnv],
+    pre_bundle: Option<BundleState>,
+    time_budget: Duration,
+    context: &RunContext,
+) -> Result<(Vec<ExecutionResult>, BundleState), (usize, EVMError<DB::Error>)>
 where
     DB: DatabaseRef,
 {
     let db = if let Some(pre_bundle) = pre_bundle {
         StateBuilder::new()
             .with_bundle_prestate(pre_bundle)
+            .with_bundle_update()
             .with_database_ref(db)
             .build()
     } else {
@@ -151,44 +401,240 @@ where
             .with_database_ref(db)
             .build()
     };
+    // A transaction may not ask for more gas than the block allows
+    let block_gas_limit: u64 = env.block.gas_limit.saturating_to();
     let mut evm = EvmBuilder::default()
         .with_db(db)
+        .with_external_context(TimeBudget::new(time_budget))
         .with_spec_id(spec_id)
         .with_env(Box::new(env))
+        .append_handler_register(inspector_handle_register)
         .build();
 
     let mut results = Vec::with_capacity(txs.len());
     for (i, tx) in txs.iter().enumerate() {
         info!("=== Executing transaction {} ===", i + 1);
         info!("Transaction details:");
-        info!("  Caller: {:?}", tx.caller);
-        info!("  To: {:?}", tx.transact_to);
+        info!("  Caller: {}", context.registry.display_address(tx.caller));
+        match tx.transact_to {
+            TxKind::Call(to) => info!("  To: {}", context.registry.display_address(to)),
+            TxKind::Create => info!("  To: contract creation"),
+        }
         info!("  Data length: {}", tx.data.len());
         if tx.data.len() >= 4 {
             info!("  Function selector: 0x{}", hex::encode(&tx.data[0..4]));
         }
 
-        *evm.tx_mut() = tx.clone();
+        *evm.tx_mut() = TxEnv {
+            gas_limit: tx.gas_limit.min(block_gas_limit),
+            ..tx.clone()
+        };
 
-        let result_and_state = evm.transact()?;
+        evm.context.external.restart();
+        let result_and_state = evm.transact().map_err(|e| (i, e))?;
+        if evm.context.external.exceeded {
+            return Err((
+                i,
+                EVMError::Custom(format!(
+                    "transaction {} exceeded time budget of {:?}",
+                    i + 1,
+                    time_budget
+                )),
+            ));
+        }
         info!("transaction evm state {:?}", result_and_state.state);
         evm.db_mut().commit(result_and_state.state);
 
         info!(
             "Transaction result: {}",
-            analyze_txn_result(&result_and_state.result)
+            analyze_txn_result(&result_and_state.result, context)
         );
         results.push(result_and_state.result);
         info!("=== Transaction {} completed ===", i + 1);
+        context.progress.step();
     }
     evm.db_mut().merge_transitions(BundleRetention::Reverts);
 
     Ok((results, evm.db_mut().take_bundle()))
 }
 
-pub fn new_system_call_txn(contract: Address, input: Bytes) -> TxEnv {
+/// Why the EVM stopped before executing a transaction of an [`ExecutionBatch`]
+///
+/// Transactions that execute but revert or halt are not errors; their
+/// outcome is in [`ExecutionBatch::results`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecError {
+    /// The transaction was rejected, e.g. its caller cannot pay for its gas
+    InvalidTransaction { index: usize, reason: String },
+    /// The block environment was rejected
+    InvalidHeader { index: usize, reason: String },
+    /// The database failed to load state
+    Database { index: usize, message: String },
+    /// A precompile or custom EVM error
+    Other { index: usize, message: String },
+}
+
+impl ExecError {
+    fn new<E: fmt::Display>(index: usize, error: EVMError<E>) -> Self {
+        match error {
+            EVMError::Transaction(reason) => Self::InvalidTransaction {
+                index,
+                reason: reason.to_string(),
+            },
+            EVMError::Header(reason) => Self::InvalidHeader {
+                index,
+                reason: reason.to_string(),
+            },
+            EVMError::Database(e) => Self::Database {
+                index,
+                message: e.to_string(),
+            },
+            EVMError::Custom(message) | EVMError::Precompile(message) => {
+                Self::Other { index, message }
+            }
+        }
+    }
+
+    /// Index of the transaction the EVM stopped at
+    pub fn index(&self) -> usize {
+        match self {
+            Self::InvalidTransaction { index, .. }
+            | Self::InvalidHeader { index, .. }
+            | Self::Database { index, .. }
+            | Self::Other { index, .. } => *index,
+        }
+    }
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTransaction { index, reason } => {
+                write!(f, "transaction {} is invalid: {}", index, reason)
+            }
+            Self::InvalidHeader { index, reason } => write!(
+                f,
+                "block header rejected at transaction {}: {}",
+                index, reason
+            ),
+            Self::Database { index, message } => {
+                write!(f, "database error at transaction {}: {}", index, message)
+            }
+            Self::Other { index, message } => {
+                write!(f, "EVM error at transaction {}: {}", index, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExecError {}
+
+/// Outcome of executing a list of transactions one after another
+#[derive(Debug, Clone)]
+pub struct ExecutionBatch {
+    /// Result of each transaction, in order
+    pub results: Vec<ExecutionResult>,
+    /// State changes of all transactions together
+    pub bundle_state: BundleState,
+}
+
+impl ExecutionBatch {
+    /// Gas used by all transactions together
+    pub fn gas_used(&self) -> u64 {
+        self.results.iter().map(ExecutionResult::gas_used).sum()
+    }
+
+    /// Index of the first transaction that reverted or halted
+    pub fn first_failure(&self) -> Option<usize> {
+        self.results.iter().position(|result| !result.is_success())
+    }
+}
+
+/// Execute `txs` in order on top of `db` and `pre_bundle`, for pipelines built on this crate
+///
+/// Each transaction sees the state left by the ones before it. Fails at the
+/// first transaction the EVM cannot execute at all.
+pub fn execute_transactions<DB>(
+    db: DB,
+    spec_id: SpecId,
+    env: Env,
+    txs: &[TxEnv],
+    pre_bundle: Option<BundleState>,
+    context: &RunContext,
+) -> Result<ExecutionBatch, ExecError>
+where
+    DB: DatabaseRef,
+    DB::Error: fmt::Display,
+{
+    let (results, bundle_state) = execute_sequential_indexed(
+        db,
+        spec_id,
+        env,
+        txs,
+        pre_bundle,
+        DEFAULT_TX_TIME_BUDGET,
+        context,
+    )
+    .map_err(|(index, e)| ExecError::new(index, e))?;
+    Ok(ExecutionBatch {
+        results,
+        bundle_state,
+    })
+}
+
+/// Gas limit of a mainnet-sized block, the realistic cap for one genesis transaction
+pub const PRODUCTION_GAS_LIMIT: u64 = 30_000_000;
+
+/// Gas cap of the genesis system calls
+///
+/// Transactions are built with unlimited gas. Without `enforce` they keep
+/// running that way and the caps only flag the transactions that would not
+/// fit; with `enforce` each transaction runs with its cap as its gas limit,
+/// so one that needs more fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasLimits {
+    /// Cap of system calls, `None` for no cap
+    pub call: Option<u64>,
+    pub enforce: bool,
+}
+
+impl GasLimits {
+    /// Cap that applies to `tx`; genesis deploys by writing code, so creations are never capped
+    pub fn cap(&self, tx: &TxEnv) -> Option<u64> {
+        match tx.transact_to {
+            TxKind::Call(_) => self.call,
+            TxKind::Create => None,
+        }
+    }
+
+    /// Set each transaction's gas limit to its cap, when enforcing
+    pub fn apply(&self, txs: &mut [TxEnv]) {
+        if !self.enforce {
+            return;
+        }
+        for tx in txs {
+            if let Some(cap) = self.cap(tx) {
+                tx.gas_limit = cap;
+            }
+        }
+    }
+
+    /// Index, gas used and cap of each transaction that used more gas than its cap
+    pub fn exceeded(&self, txs: &[TxEnv], results: &[ExecutionResult]) -> Vec<(usize, u64, u64)> {
+        txs.iter()
+            .zip(results)
+            .enumerate()
+            .filter_map(|(i, (tx, result))| {
+                let cap = self.cap(tx)?;
+                (result.gas_used() > cap).then_some((i, result.gas_used(), cap))
+            })
+            .collect()
+    }
+}
+
+pub fn new_system_call_txn(caller: Address, contract: Address, input: Bytes) -> TxEnv {
     TxEnv {
-        caller: SYSTEM_CALLER,
+        caller,
         gas_limit: u64::MAX,
         gas_price: U256::ZERO,
         transact_to: TxKind::Call(contract),
@@ -198,11 +644,11 @@ pub fn new_system_call_txn(contract: Address, input: Bytes) -> TxEnv {
     }
 }
 
-pub fn new_system_create_txn(hex_code: &str, args: Bytes) -> TxEnv {
+pub fn new_system_create_txn(caller: Address, hex_code: &str, args: Bytes) -> TxEnv {
     let mut data = hex::decode(hex_code).expect("Invalid hex string");
     data.extend_from_slice(&args);
     TxEnv {
-        caller: SYSTEM_CALLER,
+        caller,
         gas_limit: u64::MAX,
         gas_price: U256::ZERO,
         transact_to: TxKind::Create,
@@ -212,6 +658,378 @@ pub fn new_system_create_txn(hex_code: &str, args: Bytes) -> TxEnv {
     }
 }
 
-pub fn read_hex_from_file(path: &str) -> String {
-    std::fs::read_to_string(path).expect(&format!("Failed to open {}", path))
+/// Input path that stands for standard input
+pub const STDIN_PATH: &str = "-";
+
+/// Read an input file, or standard input when `path` is [`STDIN_PATH`]
+///
+/// Standard input is read once and kept in `context`, so an input given as
+/// `-` can be read again during the run, e.g. by post-genesis verification.
+pub fn read_input_file(path: &str, context: &RunContext) -> std::io::Result<String> {
+    if path != STDIN_PATH {
+        return std::fs::read_to_string(path);
+    }
+    if let Some(content) = context.stdin.get() {
+        return Ok(content.clone());
+    }
+    let content = std::io::read_to_string(std::io::stdin())?;
+    Ok(context.stdin.get_or_init(|| content).clone())
+}
+
+/// Read a `.hex` bytecode file as bare hex digits
+///
+/// Surrounding whitespace, such as the trailing newline left by a shell
+/// redirect, and a `0x` prefix are removed.
+pub fn read_hex_from_file(path: &str) -> Result<String, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let content = content.trim();
+    let digits = content
+        .strip_prefix("0x")
+        .or_else(|| content.strip_prefix("0X"))
+        .unwrap_or(content);
+    Ok(digits.to_string())
+}
+
+/// Render the contract registry as one `name address` line per contract
+///
+/// When `byte_code_dir` is given, each line also reports whether the
+/// contract's `.hex` file is present and its size in bytes.
+pub fn list_contracts(registry: &ContractRegistry, byte_code_dir: Option<&str>) -> String {
+    let contracts = registry.contracts();
+    let name_width = contracts
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or_default();
+    let mut lines = Vec::with_capacity(contracts.len());
+    for (name, address) in contracts {
+        let mut line = format!("{:<name_width$}  {}", name, address);
+        if let Some(byte_code_dir) = byte_code_dir {
+            let hex_path = format!("{}/{}.hex", byte_code_dir, name);
+            match std::fs::metadata(&hex_path) {
+                Ok(metadata) => line.push_str(&format!("  {} bytes", metadata.len())),
+                Err(_) => line.push_str("  missing"),
+            }
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_signatures::ErrorSignature;
+
+    #[test]
+    fn test_execute_transactions_reports_the_failing_index() {
+        let target = Address::with_last_byte(0x42);
+        let call = new_system_call_txn(SYSTEM_CALLER, target, Bytes::new());
+        let batch = execute_transactions(
+            revm::InMemoryDB::default(),
+            SpecId::LATEST,
+            Env::default(),
+            std::slice::from_ref(&call),
+            None,
+            &RunContext::default(),
+        )
+        .unwrap();
+        assert_eq!(batch.results.len(), 1);
+        assert_eq!(batch.first_failure(), None);
+        assert!(batch.gas_used() > 0);
+
+        let unfunded = TxEnv {
+            caller: Address::with_last_byte(0x99),
+            gas_limit: 100_000,
+            gas_price: U256::from(1),
+            ..call.clone()
+        };
+        let err = execute_transactions(
+            revm::InMemoryDB::default(),
+            SpecId::LATEST,
+            Env::default(),
+            &[call, unfunded],
+            None,
+            &RunContext::default(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, ExecError::InvalidTransaction { index: 1, .. }),
+            "{:?}",
+            err
+        );
+        assert_eq!(err.index(), 1);
+    }
+
+    #[test]
+    fn test_unbounded_loop_exceeds_the_time_budget() {
+        let target = Address::with_last_byte(0x42);
+        let mut db = revm::InMemoryDB::default();
+        // JUMPDEST; PUSH1 0; JUMP
+        db.insert_account_info(
+            target,
+            AccountInfo::from_bytecode(revm::primitives::Bytecode::new_raw(Bytes::from_static(&[
+                0x5b, 0x60, 0x00, 0x56,
+            ]))),
+        );
+        let call = new_system_call_txn(SYSTEM_CALLER, Address::with_last_byte(0x43), Bytes::new());
+        let stuck = new_system_call_txn(SYSTEM_CALLER, target, Bytes::new());
+        let err = execute_revm_sequential_timed(
+            db,
+            SpecId::LATEST,
+            Env::default(),
+            &[call, stuck],
+            None,
+            Duration::from_millis(50),
+            &RunContext::default(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, EVMError::Custom(message) if message == "transaction 2 exceeded time budget of 50ms"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_decode_system_error_arguments() {
+        let output = ISystem::InvalidValue {
+            key: "epochInterval".to_string(),
+            value: Bytes::from(vec![0x01, 0x02]),
+        }
+        .abi_encode();
+        assert_eq!(
+            decode_revert_output(&output, &RunContext::default()).as_deref(),
+            Some("InvalidValue(key: \"epochInterval\", value: 0x0102)")
+        );
+
+        let output = ISystem::OnlySystemCaller {
+            errorAddress: SYSTEM_CALLER,
+        }
+        .abi_encode();
+        assert_eq!(
+            decode_revert_output(&output, &RunContext::default()),
+            Some(format!(
+                "OnlySystemCaller(errorAddress: SystemCaller ({}))",
+                SYSTEM_CALLER
+            ))
+        );
+    }
+
+    #[test]
+    fn test_both_debug_log_signatures_are_decoded() {
+        let emitter = Address::with_last_byte(0x20);
+        let log = |data| alloy_primitives::Log {
+            address: emitter,
+            data,
+        };
+        let with_value = log(IDebugLog::Log_1 {
+            message: "epoch".to_string(),
+            value: U256::from(3),
+        }
+        .encode_log_data());
+        let message_only = log(IDebugLog::Log_0 {
+            message: "initialized".to_string(),
+        }
+        .encode_log_data());
+        let result = ExecutionResult::Success {
+            reason: revm_primitives::SuccessReason::Stop,
+            gas_used: 100,
+            gas_refunded: 0,
+            logs: vec![with_value, message_only],
+            output: revm_primitives::Output::Call(Bytes::new()),
+        };
+        assert_eq!(
+            analyze_txn_result(&result, &RunContext::default()),
+            format!(
+                "Success with gas used: 100, txn event Log(message: \"epoch\", value: 3) from {emitter}.\
+                 txn event Log(message: \"initialized\") from {emitter}."
+            )
+        );
+    }
+
+    #[test]
+    fn test_halts_are_explained() {
+        let result = ExecutionResult::Halt {
+            reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+            gas_used: 100,
+        };
+        assert_eq!(
+            analyze_txn_result(&result, &RunContext::default()),
+            "Halt: OutOfGas(Basic) with gas used: 100\n\
+             Explanation: contract ran out of gas; increase the gas limit or check for an unbounded loop in initialize"
+        );
+        assert_eq!(
+            explain_halt(&HaltReason::InvalidFEOpcode),
+            "contract hit an undefined or INVALID (0xfe) opcode; the bytecode file is likely truncated, not runtime code, or built for a newer EVM version"
+        );
+    }
+
+    #[test]
+    fn test_decode_standard_revert_and_panic() {
+        let output = Revert {
+            reason: "not allowed".to_string(),
+        }
+        .abi_encode();
+        assert_eq!(
+            decode_revert_output(&output, &RunContext::default()).as_deref(),
+            Some("Error(\"not allowed\")")
+        );
+
+        let output = Panic {
+            code: U256::from(0x11),
+        }
+        .abi_encode();
+        let decoded = decode_revert_output(&output, &RunContext::default()).unwrap();
+        assert!(decoded.starts_with("Panic(0x11)"), "{}", decoded);
+
+        assert_eq!(
+            decode_revert_output(&[0xde, 0xad, 0xbe, 0xef], &RunContext::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_custom_errors_come_from_the_run_context() {
+        let signature = ErrorSignature {
+            name: "StakeTooLow".to_string(),
+            param_types: vec!["uint256".to_string()],
+        };
+        let mut output = alloy_primitives::keccak256(signature.to_string())[..4].to_vec();
+        output.extend_from_slice(&U256::from(7).to_be_bytes::<32>());
+        let context = RunContext::default().with_error_signatures(
+            [(output[..4].try_into().unwrap(), signature)]
+                .into_iter()
+                .collect(),
+        );
+
+        let decoded = decode_revert_output(&output, &context).unwrap();
+        assert!(
+            decoded.starts_with("StakeTooLow(uint256) with arguments 0x"),
+            "{}",
+            decoded
+        );
+        assert_eq!(decode_revert_output(&output, &RunContext::default()), None);
+    }
+
+    #[test]
+    fn test_list_contracts_lists_every_contract() {
+        let output = list_contracts(&ContractRegistry::default(), None);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), CONTRACTS.len());
+        for ((name, address), line) in CONTRACTS.iter().zip(&lines) {
+            assert!(line.starts_with(name), "unexpected line: {}", line);
+            assert!(
+                line.contains(&address.to_string()),
+                "missing address in line: {}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_list_contracts_reports_hex_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Genesis.hex"), "6080").unwrap();
+
+        let output = list_contracts(&ContractRegistry::default(), dir.path().to_str());
+        let genesis = output.lines().find(|l| l.starts_with("Genesis ")).unwrap();
+        assert!(genesis.ends_with("4 bytes"), "unexpected line: {}", genesis);
+        let block = output.lines().find(|l| l.starts_with("Block ")).unwrap();
+        assert!(block.ends_with("missing"), "unexpected line: {}", block);
+    }
+
+    #[test]
+    fn test_read_hex_from_file_normalizes_content() {
+        let dir = tempfile::tempdir().unwrap();
+        for (content, expected) in [
+            ("6080\n", "6080"),
+            ("0x6080", "6080"),
+            ("  0X60AB\r\n", "60AB"),
+            ("60Ab", "60Ab"),
+        ] {
+            let path = dir.path().join("Contract.hex");
+            std::fs::write(&path, content).unwrap();
+            let hex = read_hex_from_file(path.to_str().unwrap()).unwrap();
+            assert_eq!(hex, expected, "{:?}", content);
+            assert!(hex::decode(&hex).is_ok(), "{:?}", content);
+        }
+
+        let missing = dir.path().join("Missing.hex");
+        let err = read_hex_from_file(missing.to_str().unwrap()).unwrap_err();
+        assert!(err.starts_with("Failed to open "), "{}", err);
+    }
+
+    #[test]
+    fn test_gas_limits_flag_and_cap_transactions() {
+        use revm::primitives::{Output, SuccessReason};
+
+        let txs = vec![
+            new_system_call_txn(SYSTEM_CALLER, SYSTEM_CALLER, Bytes::new()),
+            new_system_create_txn(SYSTEM_CALLER, "00", Bytes::new()),
+            new_system_call_txn(SYSTEM_CALLER, SYSTEM_CALLER, Bytes::new()),
+        ];
+        let results: Vec<_> = [500, 2_000, 1_000]
+            .map(|gas_used| ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used,
+                gas_refunded: 0,
+                logs: Vec::new(),
+                output: Output::Call(Bytes::new()),
+            })
+            .to_vec();
+
+        let limits = GasLimits {
+            call: Some(800),
+            enforce: false,
+        };
+        assert_eq!(limits.exceeded(&txs, &results), vec![(2, 1_000, 800)]);
+        let mut capped = txs.clone();
+        limits.apply(&mut capped);
+        assert_eq!(capped, txs);
+
+        let limits = GasLimits {
+            enforce: true,
+            ..limits
+        };
+        limits.apply(&mut capped);
+        let gas_limits: Vec<_> = capped.iter().map(|tx| tx.gas_limit).collect();
+        assert_eq!(gas_limits, vec![800, u64::MAX, 800]);
+    }
+
+    #[test]
+    fn test_pre_bundle_storage_survives_new_transactions() {
+        use revm::InMemoryDB;
+        use revm_primitives::Bytecode;
+
+        let target = address!("00000000000000000000000000000000000c0de0");
+        // CALLDATASIZE CALLDATASIZE SSTORE STOP: stores the calldata size at that slot
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x36, 0x36, 0x55, 0x00]));
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(SYSTEM_CALLER, SYSTEM_ACCOUNT_INFO);
+        db.insert_account_info(target, AccountInfo::from_bytecode(code));
+        let env = Env::default();
+
+        let run = |calldata: &'static [u8], pre_bundle| {
+            let tx = new_system_call_txn(SYSTEM_CALLER, target, Bytes::from_static(calldata));
+            let (results, bundle) = execute_revm_sequential(
+                &db,
+                SpecId::LATEST,
+                env.clone(),
+                &[tx],
+                pre_bundle,
+                &RunContext::default(),
+            )
+            .unwrap();
+            assert!(results[0].is_success());
+            bundle
+        };
+        let base = run(&[0], None);
+        let layered = run(&[0, 0], Some(base));
+
+        let storage = &layered.state[&target].storage;
+        assert_eq!(storage[&U256::from(1)].present_value, U256::from(1));
+        assert_eq!(storage[&U256::from(2)].present_value, U256::from(2));
+    }
 }
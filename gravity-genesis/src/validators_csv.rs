@@ -0,0 +1,219 @@
+use revm_primitives::{Address, U256, hex};
+
+use crate::{
+    genesis::{
+        DEFAULT_DECIMALS, DEFAULT_MAX_TOTAL_VOTING_POWER_BITS, GenesisConfig, VotingPowerUnit,
+    },
+    network_address::parse_network_address,
+};
+
+/// Header row a validators CSV must start with
+pub const VALIDATORS_CSV_COLUMNS: [&str; 6] = [
+    "operator",
+    "aptos_address",
+    "consensus_key",
+    "voting_power",
+    "validator_network_addr",
+    "fullnode_network_addr",
+];
+
+fn split_row(line: &str) -> Vec<&str> {
+    line.split(',').map(str::trim).collect()
+}
+
+/// Check the fields of one validator row that can be checked on their own
+///
+/// Consensus keys are left to [`crate::config_validation::validate_config`],
+/// since how they are decoded depends on `rawConsensusKeys`.
+fn check_row(row: &[&str]) -> Result<(), String> {
+    let [
+        operator,
+        aptos_address,
+        _,
+        voting_power,
+        validator_network_addr,
+        fullnode_network_addr,
+    ] = row
+    else {
+        unreachable!("rows are checked to have one cell per column");
+    };
+    operator
+        .parse::<Address>()
+        .map_err(|e| format!("invalid operator {:?}: {}", operator, e))?;
+    let aptos_bytes = hex::decode(aptos_address)
+        .map_err(|e| format!("invalid aptos_address {:?}: {}", aptos_address, e))?;
+    if aptos_bytes.len() != 32 {
+        return Err(format!(
+            "aptos_address {:?} is {} bytes, expected 32",
+            aptos_address,
+            aptos_bytes.len()
+        ));
+    }
+    voting_power
+        .parse::<U256>()
+        .map_err(|e| format!("invalid voting_power {:?}: {}", voting_power, e))?;
+    for (column, address) in [
+        ("validator_network_addr", validator_network_addr),
+        ("fullnode_network_addr", fullnode_network_addr),
+    ] {
+        if !address.is_empty() {
+            parse_network_address(address)
+                .map_err(|reason| format!("{} {:?} {}", column, address, reason))?;
+        }
+    }
+    Ok(())
+}
+
+/// Build a genesis config from a CSV with one validator per row
+///
+/// The first non-blank line must be the [`VALIDATORS_CSV_COLUMNS`] header.
+/// Every other setting keeps its default. Errors name the 1-based line they
+/// were found on.
+pub fn parse_validators_csv(content: &str) -> Result<GenesisConfig, String> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let Some((header_line, header)) = lines.next() else {
+        return Err("validators CSV is empty".to_string());
+    };
+    if split_row(header) != VALIDATORS_CSV_COLUMNS {
+        return Err(format!(
+            "line {}: expected header {:?}, found {:?}",
+            header_line,
+            VALIDATORS_CSV_COLUMNS.join(","),
+            header.trim()
+        ));
+    }
+
+    let mut config = GenesisConfig {
+        validator_addresses: Vec::new(),
+        consensus_public_keys: Vec::new(),
+        voting_powers: Vec::new(),
+        validator_network_addresses: Vec::new(),
+        fullnode_network_addresses: Vec::new(),
+        aptos_addresses: Vec::new(),
+        max_total_voting_power_bits: DEFAULT_MAX_TOTAL_VOTING_POWER_BITS,
+        voting_power_unit: VotingPowerUnit::default(),
+        decimals: DEFAULT_DECIMALS,
+        raw_consensus_keys: false,
+        prefunded_accounts: Vec::new(),
+        monikers: Vec::new(),
+        commissions: Vec::new(),
+        randomness_config: None,
+    };
+    for (line_number, line) in lines {
+        let row = split_row(line);
+        if row.len() != VALIDATORS_CSV_COLUMNS.len() {
+            return Err(format!(
+                "line {}: expected {} columns, found {}",
+                line_number,
+                VALIDATORS_CSV_COLUMNS.len(),
+                row.len()
+            ));
+        }
+        check_row(&row).map_err(|e| format!("line {}: {}", line_number, e))?;
+        config.validator_addresses.push(row[0].to_string());
+        config.aptos_addresses.push(row[1].to_string());
+        config.consensus_public_keys.push(row[2].to_string());
+        config.voting_powers.push(row[3].to_string());
+        config.validator_network_addresses.push(row[4].to_string());
+        config.fullnode_network_addresses.push(row[5].to_string());
+    }
+    Ok(config)
+}
+
+/// Replace the validators of `config` with those of `validators`
+///
+/// Monikers and commissions are parallel to the validators they replace, so
+/// they are taken from `validators` too; the other settings of `config` are kept.
+pub fn replace_validators(config: &mut GenesisConfig, validators: GenesisConfig) {
+    config.validator_addresses = validators.validator_addresses;
+    config.consensus_public_keys = validators.consensus_public_keys;
+    config.voting_powers = validators.voting_powers;
+    config.validator_network_addresses = validators.validator_network_addresses;
+    config.fullnode_network_addresses = validators.fullnode_network_addresses;
+    config.aptos_addresses = validators.aptos_addresses;
+    config.monikers = validators.monikers;
+    config.commissions = validators.commissions;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "operator,aptos_address,consensus_key,voting_power,validator_network_addr,fullnode_network_addr";
+    const KEY: &str = "2d86b40a1d692c0749a0a0426e2021ee24e2430da0f5bb9c2ae6c586bf3e0a0f";
+
+    #[test]
+    fn test_validators_csv_rows_stay_aligned() {
+        let content = format!(
+            "{HEADER}\n\
+             0x1111111111111111111111111111111111111111,0000000000000000000000001111111111111111111111111111111111111111,{},1,/ip4/127.0.0.1/tcp/2024/noise-ik/{KEY}/handshake/0,\n\
+             \n\
+             0x2222222222222222222222222222222222222222, 0000000000000000000000002222222222222222222222222222222222222222 ,{},2,,\n",
+            "aa".repeat(48),
+            "bb".repeat(48),
+        );
+        let config = parse_validators_csv(&content).unwrap();
+        assert_eq!(config.validator_addresses.len(), 2);
+        assert_eq!(config.voting_powers, vec!["1", "2"]);
+        assert_eq!(
+            config.aptos_addresses[1],
+            "0000000000000000000000002222222222222222222222222222222222222222"
+        );
+        assert_eq!(config.consensus_public_keys[1], "bb".repeat(48));
+        assert!(config.validator_network_addresses[0].starts_with("/ip4/"));
+        assert_eq!(config.fullnode_network_addresses, vec!["", ""]);
+
+        let mut base = config.clone();
+        base.monikers = vec!["old".to_string()];
+        base.prefunded_accounts = vec![crate::genesis::PrefundedAccount {
+            address: "0x3333333333333333333333333333333333333333".to_string(),
+            balance: "1".to_string(),
+        }];
+        replace_validators(&mut base, config.clone());
+        assert!(base.monikers.is_empty());
+        assert_eq!(base.prefunded_accounts.len(), 1);
+        assert_eq!(base.validator_addresses, config.validator_addresses);
+    }
+
+    #[test]
+    fn test_validators_csv_errors_name_the_line() {
+        let cases = [
+            ("".to_string(), "validators CSV is empty"),
+            (
+                "operator,consensus_key\n".to_string(),
+                "line 1: expected header",
+            ),
+            (
+                format!("{HEADER}\n0x11,00,aa,1,\n"),
+                "line 2: expected 6 columns, found 5",
+            ),
+            (
+                format!("{HEADER}\n\n0x11,{},aa,1,,\n", "00".repeat(32)),
+                "line 3: invalid operator \"0x11\"",
+            ),
+            (
+                format!(
+                    "{HEADER}\n0x1111111111111111111111111111111111111111,{},aa,ten,,\n",
+                    "00".repeat(32)
+                ),
+                "line 2: invalid voting_power \"ten\"",
+            ),
+            (
+                format!(
+                    "{HEADER}\n0x1111111111111111111111111111111111111111,{},aa,1,/ip4/127.0.0.1/tcp/2024,\n",
+                    "00".repeat(32)
+                ),
+                "line 2: validator_network_addr \"/ip4/127.0.0.1/tcp/2024\" is missing",
+            ),
+        ];
+        for (content, reason) in cases {
+            let err = parse_validators_csv(&content).unwrap_err();
+            assert!(err.starts_with(reason), "{:?}: {}", content, err);
+        }
+    }
+}
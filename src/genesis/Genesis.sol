@@ -41,7 +41,9 @@ contract Genesis is System {
         uint256[] calldata votingPowers,
         bytes[] calldata validatorNetworkAddresses,
         bytes[] calldata fullnodeNetworkAddresses,
-        bytes[] calldata aptosAddresses
+        bytes[] calldata aptosAddresses,
+        string[] calldata monikers,
+        IValidatorManager.Commission[] calldata commissions
     ) external onlySystemCaller {
         if (genesisCompleted) revert GenesisAlreadyCompleted();
         if (consensusPublicKeys.length == 0) revert InvalidInitialValidators();
@@ -53,7 +55,9 @@ contract Genesis is System {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // 2. Initialize epoch module
@@ -108,7 +112,9 @@ contract Genesis is System {
         uint256[] calldata votingPowers,
         bytes[] calldata validatorNetworkAddresses,
         bytes[] calldata fullnodeNetworkAddresses,
-        bytes[] calldata aptosAddresses
+        bytes[] calldata aptosAddresses,
+        string[] calldata monikers,
+        IValidatorManager.Commission[] calldata commissions
     ) internal {
         // Initialize StakeConfig
         IStakeConfig(STAKE_CONFIG_ADDR).initialize();
@@ -120,7 +126,9 @@ contract Genesis is System {
             votingPowers: votingPowers,
             validatorNetworkAddresses: validatorNetworkAddresses,
             fullnodeNetworkAddresses: fullnodeNetworkAddresses,
-            aptosAddresses: aptosAddresses
+            aptosAddresses: aptosAddresses,
+            monikers: monikers,
+            commissions: commissions
         });
 
         IValidatorManager(VALIDATOR_MANAGER_ADDR).initialize(initParams);
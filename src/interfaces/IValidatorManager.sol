@@ -144,6 +144,8 @@ interface IValidatorManager is IReconfigurableModule {
         bytes[] validatorNetworkAddresses;
         bytes[] fullnodeNetworkAddresses;
         bytes[] aptosAddresses;
+        string[] monikers; // optional, empty for default "VAL<index>" names
+        Commission[] commissions; // optional, empty for the default commission
     }
 
     /**
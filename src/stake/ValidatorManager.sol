@@ -9,6 +9,7 @@ import "@openzeppelin/contracts/utils/ReentrancyGuard.sol";
 import "@src/access/Protectable.sol";
 import "@src/interfaces/IStakeCredit.sol";
 import "@openzeppelin/contracts/utils/structs/EnumerableSet.sol";
+import "@openzeppelin/contracts/utils/Strings.sol";
 import "@src/stake/StakeCredit.sol";
 import "@src/interfaces/IValidatorManager.sol";
 import "@openzeppelin/contracts/proxy/transparent/TransparentUpgradeableProxy.sol";
@@ -112,6 +113,8 @@ contract ValidatorManager is System, ReentrancyGuard, Protectable, IValidatorMan
                 || params.validatorAddresses.length != params.votingPowers.length
                 || params.validatorAddresses.length != params.validatorNetworkAddresses.length
                 || params.validatorAddresses.length != params.fullnodeNetworkAddresses.length
+                || (params.monikers.length != 0 && params.validatorAddresses.length != params.monikers.length)
+                || (params.commissions.length != 0 && params.validatorAddresses.length != params.commissions.length)
         ) revert ArrayLengthMismatch();
 
         initialized = true;
@@ -130,20 +133,35 @@ contract ValidatorManager is System, ReentrancyGuard, Protectable, IValidatorMan
 
             if (votingPower == 0) revert InvalidVotingPower(votingPower);
 
+            string memory moniker = params.monikers.length != 0
+                ? params.monikers[i]
+                : string.concat("VAL", Strings.toString(i)); // generate default name
+            // same rules as validateRegistrationParams
+            IValidatorManagerUtils(VALIDATOR_MANAGER_UTILS_ADDR).validateMoniker(moniker);
+            bytes32 monikerHash = keccak256(abi.encodePacked(moniker));
+            if (_monikerSet[monikerHash]) revert DuplicateMoniker(moniker);
+            _monikerSet[monikerHash] = true;
+
+            Commission memory commission = params.commissions.length != 0
+                ? params.commissions[i]
+                : Commission({
+                    rate: 0,
+                    maxRate: 5000, // default max commission rate 50%
+                    maxChangeRate: 500 // default max daily change rate 5%
+                });
+            if (
+                commission.maxRate > IStakeConfig(STAKE_CONFIG_ADDR).MAX_COMMISSION_RATE()
+                    || commission.rate > commission.maxRate || commission.maxChangeRate > commission.maxRate
+            ) revert InvalidCommission();
+
             // deploy StakeCredit contract for initial validator
-            address stakeCreditAddress = _deployStakeCreditWithValue(
-                validator, string(abi.encodePacked("VAL", uint256(i))), validator, votingPower
-            );
+            address stakeCreditAddress = _deployStakeCreditWithValue(validator, moniker, validator, votingPower);
 
             // create basic validator info
             validatorInfos[validator] = ValidatorInfo({
                 consensusPublicKey: consensusPublicKey,
-                commission: Commission({
-                    rate: 0,
-                    maxRate: 5000, // default max commission rate 50%
-                    maxChangeRate: 500 // default max daily change rate 5%
-                }),
-                moniker: string(abi.encodePacked("VAL", uint256(i))), // generate default name
+                commission: commission,
+                moniker: moniker,
                 registered: true,
                 stakeCreditAddress: stakeCreditAddress,
                 status: ValidatorStatus.ACTIVE,
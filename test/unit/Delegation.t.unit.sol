@@ -58,7 +58,9 @@ contract DelegationTest is Test, TestConstants {
             votingPowers: new uint256[](0),
             validatorNetworkAddresses: new bytes[](0),
             fullnodeNetworkAddresses: new bytes[](0),
-            aptosAddresses: new bytes[](0)
+            aptosAddresses: new bytes[](0),
+            monikers: new string[](0),
+            commissions: new IValidatorManager.Commission[](0)
         });
         ValidatorManagerMock(VALIDATOR_MANAGER_ADDR).initialize(emptyParams);
         StakeConfigMock(STAKE_CONFIG_ADDR).initialize();
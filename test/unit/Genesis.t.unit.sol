@@ -3,6 +3,8 @@ pragma solidity 0.8.30;
 
 import "forge-std/Test.sol";
 import "@src/genesis/Genesis.sol";
+import "@src/stake/ValidatorManager.sol";
+import "@src/lib/ValidatorManagerUtils.sol";
 import "@test/utils/TestConstants.sol";
 
 // Import all the mocks we'll need
@@ -31,6 +33,8 @@ contract GenesisTest is Test, TestConstants {
     bytes[] public validatorNetworkAddresses;
     bytes[] public fullnodeNetworkAddresses;
     bytes[] public aptosAddresses;
+    string[] public monikers;
+    IValidatorManager.Commission[] public commissions;
 
     function setUp() public {
         // Deploy Genesis contract
@@ -108,7 +112,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -127,7 +133,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert - Check that all subsystems were initialized
@@ -147,7 +155,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -171,7 +181,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
     }
 
@@ -184,7 +196,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         assertTrue(genesis.isGenesisCompleted());
@@ -201,7 +215,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
         assertTrue(genesis.isGenesisCompleted());
 
@@ -214,7 +230,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
     }
 
@@ -238,7 +256,9 @@ contract GenesisTest is Test, TestConstants {
             emptyPowers,
             emptyValidatorNetworkAddresses,
             emptyFullnodeNetworkAddresses,
-            emptyAptosAddresses
+            emptyAptosAddresses,
+            monikers,
+            commissions
         );
     }
 
@@ -265,7 +285,9 @@ contract GenesisTest is Test, TestConstants {
             singlePower,
             singleValidatorNetworkAddresses,
             singleFullnodeNetworkAddresses,
-            singleAptosAddresses
+            singleAptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -300,7 +322,9 @@ contract GenesisTest is Test, TestConstants {
             largeVotingPowers,
             largeValidatorNetworkAddresses,
             largeFullnodeNetworkAddresses,
-            largeAptosAddresses
+            largeAptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -321,7 +345,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -342,7 +368,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -358,7 +386,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -381,7 +411,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
@@ -405,7 +437,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
     }
 
@@ -421,7 +455,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert - Verify final state
@@ -436,7 +472,9 @@ contract GenesisTest is Test, TestConstants {
             votingPowers,
             validatorNetworkAddresses,
             fullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
     }
 
@@ -490,10 +528,134 @@ contract GenesisTest is Test, TestConstants {
             realisticPowers,
             realisticValidatorNetworkAddresses,
             realisticFullnodeNetworkAddresses,
-            aptosAddresses
+            aptosAddresses,
+            monikers,
+            commissions
         );
 
         // Assert
         assertTrue(genesis.isGenesisCompleted());
     }
+
+    // ============ VALIDATOR METADATA TESTS ============
+
+    function _setupValidatorMetadata() internal {
+        monikers = new string[](3);
+        monikers[0] = "Alpha";
+        monikers[1] = "Beta";
+        monikers[2] = "Gamma";
+
+        commissions.push(IValidatorManager.Commission({ rate: 1000, maxRate: 2000, maxChangeRate: 100 }));
+        commissions.push(IValidatorManager.Commission({ rate: 0, maxRate: 5000, maxChangeRate: 500 }));
+        commissions.push(IValidatorManager.Commission({ rate: 5000, maxRate: 5000, maxChangeRate: 5000 }));
+    }
+
+    /// Run Genesis against the real ValidatorManager, which checks the validator metadata
+    function _useRealValidatorManager() internal {
+        vm.etch(GENESIS_ADDR, address(genesis).code);
+        genesis = Genesis(GENESIS_ADDR);
+        vm.etch(VALIDATOR_MANAGER_ADDR, address(new ValidatorManager()).code);
+        vm.etch(VALIDATOR_MANAGER_UTILS_ADDR, address(new ValidatorManagerUtils()).code);
+        StakeConfigMock(STAKE_CONFIG_ADDR).setMAX_COMMISSION_RATE(MAX_COMMISSION_RATE);
+    }
+
+    function _initializeExpectingRevert(
+        bytes memory revertData
+    ) internal {
+        vm.prank(SYSTEM_CALLER);
+        vm.expectRevert(revertData);
+        genesis.initialize(
+            validatorAddresses,
+            consensusPublicKeys,
+            votingPowers,
+            validatorNetworkAddresses,
+            fullnodeNetworkAddresses,
+            aptosAddresses,
+            monikers,
+            commissions
+        );
+    }
+
+    function test_initialize_withMonikersAndCommissions_shouldWork() public {
+        // Arrange
+        _setupValidatorMetadata();
+
+        // Act
+        vm.prank(SYSTEM_CALLER);
+        vm.expectEmit(true, true, true, true);
+        emit Genesis.GenesisCompleted(block.timestamp, 3);
+        genesis.initialize(
+            validatorAddresses,
+            consensusPublicKeys,
+            votingPowers,
+            validatorNetworkAddresses,
+            fullnodeNetworkAddresses,
+            aptosAddresses,
+            monikers,
+            commissions
+        );
+
+        // Assert
+        assertTrue(genesis.isGenesisCompleted());
+    }
+
+    function test_initialize_monikerCountMismatch_shouldRevert() public {
+        // Arrange - Real ValidatorManager with one moniker for three validators
+        _setupValidatorMetadata();
+        _useRealValidatorManager();
+        monikers.pop();
+
+        // Act & Assert
+        _initializeExpectingRevert(abi.encodeWithSelector(IValidatorManager.ArrayLengthMismatch.selector));
+    }
+
+    function test_initialize_invalidMoniker_shouldRevert() public {
+        // Arrange - Monikers must start with an uppercase letter
+        _setupValidatorMetadata();
+        _useRealValidatorManager();
+        monikers[0] = "alpha";
+
+        // Act & Assert
+        _initializeExpectingRevert(abi.encodeWithSelector(IValidatorManager.InvalidMoniker.selector, "alpha"));
+    }
+
+    function test_initialize_monikerWithSeparator_shouldRevert() public {
+        // Arrange - Only letters and digits are allowed
+        _setupValidatorMetadata();
+        _useRealValidatorManager();
+        monikers[0] = "East-0";
+
+        // Act & Assert
+        _initializeExpectingRevert(abi.encodeWithSelector(IValidatorManager.InvalidMoniker.selector, "East-0"));
+    }
+
+    function test_initialize_maxRateAboveStakeConfigLimit_shouldRevert() public {
+        // Arrange - maxRate is capped by StakeConfig.MAX_COMMISSION_RATE
+        _setupValidatorMetadata();
+        _useRealValidatorManager();
+        commissions[0] = IValidatorManager.Commission({ rate: 0, maxRate: 5001, maxChangeRate: 100 });
+
+        // Act & Assert
+        _initializeExpectingRevert(abi.encodeWithSelector(IValidatorManager.InvalidCommission.selector));
+    }
+
+    function test_initialize_rateAboveMaxRate_shouldRevert() public {
+        // Arrange
+        _setupValidatorMetadata();
+        _useRealValidatorManager();
+        commissions[0] = IValidatorManager.Commission({ rate: 3000, maxRate: 2000, maxChangeRate: 100 });
+
+        // Act & Assert
+        _initializeExpectingRevert(abi.encodeWithSelector(IValidatorManager.InvalidCommission.selector));
+    }
+
+    function test_initialize_maxChangeRateAboveMaxRate_shouldRevert() public {
+        // Arrange
+        _setupValidatorMetadata();
+        _useRealValidatorManager();
+        commissions[0] = IValidatorManager.Commission({ rate: 1000, maxRate: 2000, maxChangeRate: 2001 });
+
+        // Act & Assert
+        _initializeExpectingRevert(abi.encodeWithSelector(IValidatorManager.InvalidCommission.selector));
+    }
 }